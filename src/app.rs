@@ -6,6 +6,7 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::borrow::Cow;
 use std::io::Write;
 
+use crate::__into_owned;
 use crate::schema::{SCHEMAS_EXTENDED, SCHEMA_DOC_PROPS_V_TYPES, SCHEMA_XML};
 
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
@@ -16,6 +17,8 @@ pub enum App<'a> {
     AppWithApNamespace(AppWithApNamespace<'a>),
 }
 
+__into_owned! { enum App { AppNoApNamespace, AppWithApNamespace } }
+
 #[derive(Debug, XmlRead, Clone)]
 #[xml(tag = "Properties")]
 pub struct AppNoApNamespace<'a> {
@@ -53,6 +56,14 @@ pub struct AppNoApNamespace<'a> {
     pub app_version: Option<Cow<'a, str>>,
 }
 
+__into_owned! {
+    AppNoApNamespace {
+        template, total_time, pages, words, characters, application, doc_security, lines,
+        paragraphs, scale_crop, company, links_up_to_date, characters_with_spaces, shared_doc,
+        hyperlinks_changed, app_version
+    }
+}
+
 #[derive(Debug, XmlRead, Clone)]
 #[xml(tag = "ap:Properties")]
 pub struct AppWithApNamespace<'a> {
@@ -90,6 +101,14 @@ pub struct AppWithApNamespace<'a> {
     pub app_version: Option<Cow<'a, str>>,
 }
 
+__into_owned! {
+    AppWithApNamespace {
+        template, total_time, pages, words, characters, application, doc_security, lines,
+        paragraphs, scale_crop, company, links_up_to_date, characters_with_spaces, shared_doc,
+        hyperlinks_changed, app_version
+    }
+}
+
 impl Default for AppNoApNamespace<'static> {
     fn default() -> AppNoApNamespace<'static> {
         AppNoApNamespace {
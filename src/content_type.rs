@@ -6,17 +6,42 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::borrow::Cow;
 use std::io::Write;
 
+use crate::__into_owned;
 use crate::schema::SCHEMA_CONTENT_TYPES;
 
-const CONTENT_TYPE_XML: &str = "application/xml";
-const CONTENT_TYPE_CORE: &str = "application/vnd.openxmlformats-package.core-properties+xml";
-const CONTENT_TYPE_RELATIONSHIP: &str = "application/vnd.openxmlformats-package.relationships+xml";
-const CONTENT_TYPE_EXTENDED: &str =
+pub(crate) const CONTENT_TYPE_XML: &str = "application/xml";
+pub(crate) const CONTENT_TYPE_CORE: &str =
+    "application/vnd.openxmlformats-package.core-properties+xml";
+pub(crate) const CONTENT_TYPE_RELATIONSHIP: &str =
+    "application/vnd.openxmlformats-package.relationships+xml";
+pub(crate) const CONTENT_TYPE_EXTENDED: &str =
     "application/vnd.openxmlformats-officedocument.extended-properties+xml";
-const CONTENT_TYPE_DOCUMENT: &str =
+pub(crate) const CONTENT_TYPE_CUSTOM: &str =
+    "application/vnd.openxmlformats-officedocument.custom-properties+xml";
+pub(crate) const CONTENT_TYPE_DOCUMENT: &str =
     "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml";
-const CONTENT_TYPE_STYLES: &str =
+pub(crate) const CONTENT_TYPE_STYLES: &str =
     "application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml";
+pub(crate) const CONTENT_TYPE_FONT_TABLE: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.fontTable+xml";
+pub(crate) const CONTENT_TYPE_FOOTNOTES: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.footnotes+xml";
+pub(crate) const CONTENT_TYPE_ENDNOTES: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.endnotes+xml";
+pub(crate) const CONTENT_TYPE_SETTINGS: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml";
+pub(crate) const CONTENT_TYPE_WEB_SETTINGS: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.webSettings+xml";
+pub(crate) const CONTENT_TYPE_COMMENTS: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.comments+xml";
+pub(crate) const CONTENT_TYPE_NUMBERING: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml";
+pub(crate) const CONTENT_TYPE_HEADER: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml";
+pub(crate) const CONTENT_TYPE_FOOTER: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.footer+xml";
+pub(crate) const CONTENT_TYPE_THEME: &str =
+    "application/vnd.openxmlformats-officedocument.theme+xml";
 
 #[derive(Debug, XmlRead, Clone)]
 #[xml(tag = "Types")]
@@ -27,6 +52,8 @@ pub struct ContentTypes<'a> {
     pub overrides: Vec<OverrideContentType<'a>>,
 }
 
+__into_owned! { ContentTypes { defaults, overrides } }
+
 impl Default for ContentTypes<'static> {
     fn default() -> ContentTypes<'static> {
         ContentTypes {
@@ -62,6 +89,96 @@ impl Default for ContentTypes<'static> {
     }
 }
 
+impl<'a> ContentTypes<'a> {
+    /// Registers the content type of an extension, unless the extension has one already
+    pub fn add_default<E, T>(&mut self, ext: E, ty: T)
+    where
+        E: Into<Cow<'a, str>>,
+        T: Into<Cow<'a, str>>,
+    {
+        let ext = ext.into();
+        if !self
+            .defaults
+            .iter()
+            .any(|default| default.ext.eq_ignore_ascii_case(&ext))
+        {
+            self.defaults
+                .push(DefaultContentType { ext, ty: ty.into() });
+        }
+    }
+
+    /// Registers the content type of a part, replacing the one it had before
+    ///
+    /// `part` is the absolute name of the part, like `/word/header1.xml`.
+    pub fn add_override<P, T>(&mut self, part: P, ty: T)
+    where
+        P: Into<Cow<'a, str>>,
+        T: Into<Cow<'a, str>>,
+    {
+        let (part, ty) = (part.into(), ty.into());
+        match self.overrides.iter_mut().find(|o| o.part == part) {
+            Some(existing) => existing.ty = ty,
+            None => self.overrides.push(OverrideContentType { part, ty }),
+        }
+    }
+
+    /// Returns the content type of a part, given by its override or by the default of its extension
+    ///
+    /// `part` is the absolute name of the part, like `/word/document.xml`.
+    pub fn content_type(&self, part: &str) -> Option<&str> {
+        if let Some(o) = self
+            .overrides
+            .iter()
+            .find(|o| o.part.eq_ignore_ascii_case(part))
+        {
+            return Some(&o.ty);
+        }
+        let (_, ext) = part.rsplit_once('.')?;
+        self.defaults
+            .iter()
+            .find(|default| default.ext.eq_ignore_ascii_case(ext))
+            .map(|default| &*default.ty)
+    }
+
+    /// Brings the declarations in line with the parts of a package
+    ///
+    /// `parts` lists every part of the package by absolute name, along with the content type
+    /// it needs an override for, if it is known. Overrides of missing parts and defaults of
+    /// unused extensions are removed, except for `rels` and `xml`; parts without a content
+    /// type get an override. Content types which are declared already are kept, so that e.g.
+    /// the main document of a template stays a template.
+    pub(crate) fn reconcile(&mut self, parts: &[(String, Option<&'static str>)]) {
+        self.overrides.retain(|o| {
+            parts
+                .iter()
+                .any(|(part, _)| part.eq_ignore_ascii_case(&o.part))
+        });
+        self.defaults.retain(|default| {
+            ["rels", "xml"]
+                .iter()
+                .any(|ext| default.ext.eq_ignore_ascii_case(ext))
+                || parts.iter().any(|(part, _)| {
+                    part.rsplit_once('.')
+                        .is_some_and(|(_, ext)| default.ext.eq_ignore_ascii_case(ext))
+                })
+        });
+        self.add_default("rels", CONTENT_TYPE_RELATIONSHIP);
+        self.add_default("xml", CONTENT_TYPE_XML);
+
+        for (part, ty) in parts {
+            if let Some(ty) = ty {
+                if !self
+                    .overrides
+                    .iter()
+                    .any(|o| o.part.eq_ignore_ascii_case(part))
+                {
+                    self.add_override(part.clone(), *ty);
+                }
+            }
+        }
+    }
+}
+
 impl<'a> XmlWrite for ContentTypes<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let ContentTypes {
@@ -104,6 +221,8 @@ pub struct DefaultContentType<'a> {
     pub ty: Cow<'a, str>,
 }
 
+__into_owned! { DefaultContentType { ext, ty } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[xml(tag = "Override")]
 pub struct OverrideContentType<'a> {
@@ -112,3 +231,5 @@ pub struct OverrideContentType<'a> {
     #[xml(attr = "ContentType")]
     pub ty: Cow<'a, str>,
 }
+
+__into_owned! { OverrideContentType { part, ty } }
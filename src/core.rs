@@ -6,6 +6,7 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::borrow::Cow;
 use std::io::Write;
 
+use crate::__into_owned;
 use crate::schema::{SCHEMA_CORE_2, SCHEMA_DC, SCHEMA_XML};
 
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
@@ -16,6 +17,8 @@ pub enum Core<'a> {
     CoreNoNamespace(CoreNoNamespace<'a>),
 }
 
+__into_owned! { enum Core { CoreNamespace, CoreNoNamespace } }
+
 #[derive(Debug, Default, XmlRead, Clone)]
 #[xml(tag = "cp:coreProperties")]
 pub struct CoreNamespace<'a> {
@@ -35,6 +38,12 @@ pub struct CoreNamespace<'a> {
     pub revision: Option<Cow<'a, str>>,
 }
 
+__into_owned! {
+    CoreNamespace {
+        title, subject, creator, keywords, description, last_modified_by, revision
+    }
+}
+
 #[derive(Debug, Default, XmlRead, Clone)]
 #[xml(tag = "coreProperties")]
 pub struct CoreNoNamespace<'a> {
@@ -54,6 +63,12 @@ pub struct CoreNoNamespace<'a> {
     pub revision: Option<Cow<'a, str>>,
 }
 
+__into_owned! {
+    CoreNoNamespace {
+        title, subject, creator, keywords, description, last_modified_by, revision
+    }
+}
+
 impl<'a> XmlWrite for CoreNamespace<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let CoreNamespace {
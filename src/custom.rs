@@ -0,0 +1,603 @@
+//! Custom File Properties part
+//!
+//! The corresponding ZIP item is `/docProps/custom.xml`.
+
+use hard_xml::xmlparser::{ElementEnd, Token};
+use hard_xml::{XmlError, XmlRead, XmlReader, XmlResult, XmlWrite, XmlWriter};
+use std::borrow::Cow;
+use std::io::Write;
+
+use crate::document::DateTime;
+use crate::schema::{SCHEMA_CUSTOM_PROPERTIES, SCHEMA_DOC_PROPS_V_TYPES, SCHEMA_XML};
+use crate::{__into_owned, __xml_test_suites, IntoOwned};
+
+/// The format id every custom property has
+pub const FMTID_CUSTOM_PROPERTIES: &str = "{D5CDD505-2E9C-101B-9397-08002B2CF9AE}";
+
+/// The properties a document carries for its users, like a document id or a classification
+///
+/// ```rust
+/// use docx_rust::custom::CustomProperties;
+///
+/// let mut custom = CustomProperties::default();
+/// custom.set("DocumentId", "DMS-4711");
+/// custom.set("Confidential", true);
+/// custom.set("Version", 3);
+///
+/// assert_eq!(custom.get("DocumentId").unwrap().as_str(), Some("DMS-4711"));
+/// custom.set("Version", 4);
+/// assert_eq!(custom.get("Version").unwrap().as_i32(), Some(4));
+/// ```
+#[derive(Debug, Default, XmlRead, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "Properties")]
+pub struct CustomProperties<'a> {
+    #[xml(child = "property")]
+    pub properties: Vec<CustomProperty<'a>>,
+}
+
+__into_owned! { CustomProperties { properties } }
+
+impl<'a> CustomProperties<'a> {
+    /// Returns the value of the property named `name`
+    pub fn get(&self, name: &str) -> Option<&CustomValue<'a>> {
+        self.properties
+            .iter()
+            .find(|property| property.name.as_deref() == Some(name))
+            .and_then(|property| property.value.as_ref())
+    }
+
+    /// Sets the value of the property named `name`, adding the property if it is missing
+    pub fn set<N, V>(&mut self, name: N, value: V)
+    where
+        N: Into<Cow<'a, str>>,
+        V: Into<CustomValue<'a>>,
+    {
+        let name = name.into();
+        match self
+            .properties
+            .iter_mut()
+            .find(|property| property.name.as_deref() == Some(&*name))
+        {
+            Some(property) => property.value = Some(value.into()),
+            None => {
+                // ids 0 and 1 are reserved
+                let pid = self
+                    .properties
+                    .iter()
+                    .map(|property| property.pid + 1)
+                    .max()
+                    .unwrap_or(2)
+                    .max(2);
+                self.properties.push(CustomProperty {
+                    fmtid: FMTID_CUSTOM_PROPERTIES.into(),
+                    pid,
+                    name: Some(name),
+                    link_target: None,
+                    value: Some(value.into()),
+                });
+            }
+        }
+    }
+
+    /// Removes the property named `name` and returns it
+    pub fn remove(&mut self, name: &str) -> Option<CustomProperty<'a>> {
+        let index = self
+            .properties
+            .iter()
+            .position(|property| property.name.as_deref() == Some(name))?;
+        Some(self.properties.remove(index))
+    }
+}
+
+impl<'a> XmlWrite for CustomProperties<'a> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        let CustomProperties { properties } = self;
+
+        log::debug!("[CustomProperties] Started writing.");
+        let _ = write!(writer.inner, "{}", SCHEMA_XML);
+
+        writer.write_element_start("Properties")?;
+
+        writer.write_attribute("xmlns", SCHEMA_CUSTOM_PROPERTIES)?;
+        writer.write_attribute("xmlns:vt", SCHEMA_DOC_PROPS_V_TYPES)?;
+
+        if properties.is_empty() {
+            writer.write_element_end_empty()?;
+        } else {
+            writer.write_element_end_open()?;
+            for property in properties {
+                property.to_writer(writer)?;
+            }
+            writer.write_element_end_close("Properties")?;
+        }
+
+        log::debug!("[CustomProperties] Finished writing.");
+
+        Ok(())
+    }
+}
+
+/// A custom property
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "property")]
+pub struct CustomProperty<'a> {
+    /// Specifies the format id, which is [`FMTID_CUSTOM_PROPERTIES`]
+    #[xml(attr = "fmtid")]
+    pub fmtid: Cow<'a, str>,
+    /// Specifies the id of the property, unique in the part and starting at 2
+    #[xml(attr = "pid")]
+    pub pid: usize,
+    #[xml(attr = "name")]
+    pub name: Option<Cow<'a, str>>,
+    /// Specifies the bookmark the value is linked to
+    #[xml(attr = "linkTarget")]
+    pub link_target: Option<Cow<'a, str>>,
+    /// Specifies the value
+    #[xml(
+        child = "vt:lpwstr",
+        child = "vt:i4",
+        child = "vt:r8",
+        child = "vt:bool",
+        child = "vt:filetime",
+        child = "vt:vector",
+        child = "vt:array",
+        child = "vt:blob",
+        child = "vt:oblob",
+        child = "vt:empty",
+        child = "vt:null",
+        child = "vt:i1",
+        child = "vt:i2",
+        child = "vt:i8",
+        child = "vt:int",
+        child = "vt:ui1",
+        child = "vt:ui2",
+        child = "vt:ui4",
+        child = "vt:ui8",
+        child = "vt:uint",
+        child = "vt:r4",
+        child = "vt:decimal",
+        child = "vt:lpstr",
+        child = "vt:bstr",
+        child = "vt:date",
+        child = "vt:cy",
+        child = "vt:error",
+        child = "vt:stream",
+        child = "vt:ostream",
+        child = "vt:storage",
+        child = "vt:ostorage",
+        child = "vt:vstream",
+        child = "vt:clsid"
+    )]
+    pub value: Option<CustomValue<'a>>,
+}
+
+__into_owned! { CustomProperty { fmtid, name, link_target, value; pid } }
+
+/// The value of a custom property
+#[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum CustomValue<'a> {
+    #[xml(tag = "vt:lpwstr")]
+    Text(TextValue<'a>),
+    #[xml(tag = "vt:i4")]
+    Integer(IntegerValue),
+    #[xml(tag = "vt:r8")]
+    Float(FloatValue),
+    #[xml(tag = "vt:bool")]
+    Bool(BoolValue),
+    #[xml(tag = "vt:filetime")]
+    FileTime(FileTimeValue<'a>),
+    /// A value of any other type, kept as it is
+    #[xml(
+        tag = "vt:vector",
+        tag = "vt:array",
+        tag = "vt:blob",
+        tag = "vt:oblob",
+        tag = "vt:empty",
+        tag = "vt:null",
+        tag = "vt:i1",
+        tag = "vt:i2",
+        tag = "vt:i8",
+        tag = "vt:int",
+        tag = "vt:ui1",
+        tag = "vt:ui2",
+        tag = "vt:ui4",
+        tag = "vt:ui8",
+        tag = "vt:uint",
+        tag = "vt:r4",
+        tag = "vt:decimal",
+        tag = "vt:lpstr",
+        tag = "vt:bstr",
+        tag = "vt:date",
+        tag = "vt:cy",
+        tag = "vt:error",
+        tag = "vt:stream",
+        tag = "vt:ostream",
+        tag = "vt:storage",
+        tag = "vt:ostorage",
+        tag = "vt:vstream",
+        tag = "vt:clsid"
+    )]
+    Other(OtherValue<'a>),
+}
+
+__into_owned! { enum CustomValue { Text, FileTime, Other; Integer, Float, Bool } }
+
+impl<'a> CustomValue<'a> {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CustomValue::Text(text) => Some(&text.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            CustomValue::Integer(integer) => Some(integer.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            CustomValue::Float(float) => Some(float.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            CustomValue::Bool(boolean) => Some(boolean.value),
+            _ => None,
+        }
+    }
+
+    /// Returns the date of a file time value, in UTC
+    pub fn as_date_time(&self) -> Option<DateTime> {
+        match self {
+            CustomValue::FileTime(time) => DateTime::parse(&time.value),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for CustomValue<'a> {
+    fn from(value: &'a str) -> Self {
+        CustomValue::Text(TextValue {
+            value: value.into(),
+        })
+    }
+}
+
+impl From<String> for CustomValue<'_> {
+    fn from(value: String) -> Self {
+        CustomValue::Text(TextValue {
+            value: value.into(),
+        })
+    }
+}
+
+impl From<i32> for CustomValue<'_> {
+    fn from(value: i32) -> Self {
+        CustomValue::Integer(IntegerValue { value })
+    }
+}
+
+impl From<f64> for CustomValue<'_> {
+    fn from(value: f64) -> Self {
+        CustomValue::Float(FloatValue { value })
+    }
+}
+
+impl From<bool> for CustomValue<'_> {
+    fn from(value: bool) -> Self {
+        CustomValue::Bool(BoolValue { value })
+    }
+}
+
+impl From<DateTime> for CustomValue<'_> {
+    /// Takes the date as UTC
+    fn from(value: DateTime) -> Self {
+        CustomValue::FileTime(FileTimeValue {
+            value: format!("{}Z", value).into(),
+        })
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:lpwstr")]
+pub struct TextValue<'a> {
+    #[xml(text)]
+    pub value: Cow<'a, str>,
+}
+
+__into_owned! { TextValue { value } }
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:i4")]
+pub struct IntegerValue {
+    #[xml(text)]
+    pub value: i32,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:r8")]
+pub struct FloatValue {
+    #[xml(text)]
+    pub value: f64,
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:bool")]
+pub struct BoolValue {
+    #[xml(text)]
+    pub value: bool,
+}
+
+/// A date and time in UTC, like `2024-03-05T14:30:00Z`
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "vt:filetime")]
+pub struct FileTimeValue<'a> {
+    #[xml(text)]
+    pub value: Cow<'a, str>,
+}
+
+__into_owned! { FileTimeValue { value } }
+
+/// A value of a type [`CustomValue`] has no variant of its own for, like `vt:lpstr` or
+/// `vt:vector`
+///
+/// The element is written back as it was read.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct OtherValue<'a> {
+    /// The tag of the element, like `vt:lpstr`
+    pub tag: Cow<'a, str>,
+    /// The attributes of the element, like the `size` and `baseType` of a `vt:vector`
+    pub attributes: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    /// The content of the element, as XML
+    pub content: Cow<'a, str>,
+}
+
+impl<'a> OtherValue<'a> {
+    /// Returns the text of a value of a simple type, like a `vt:lpstr` or a `vt:i8`
+    pub fn text(&self) -> Option<Cow<'_, str>> {
+        if self.content.contains('<') {
+            return None;
+        }
+        hard_xml::utils::xml_unescape(&self.content).ok()
+    }
+}
+
+impl<'a> IntoOwned for OtherValue<'a> {
+    type Owned = OtherValue<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        OtherValue {
+            tag: IntoOwned::into_owned(self.tag),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|(key, value)| (IntoOwned::into_owned(key), IntoOwned::into_owned(value)))
+                .collect(),
+            content: IntoOwned::into_owned(self.content),
+        }
+    }
+}
+
+impl<'a> XmlRead<'a> for OtherValue<'a> {
+    fn from_reader(reader: &mut XmlReader<'a>) -> XmlResult<Self> {
+        let tag = reader
+            .find_element_start(None)?
+            .ok_or(XmlError::UnexpectedEof)?;
+        reader.next();
+        let mut value = OtherValue {
+            tag: tag.into(),
+            ..Default::default()
+        };
+        while let Some((key, attr)) = reader.find_attribute()? {
+            value.attributes.push((key.into(), attr));
+        }
+        if let Some(Token::ElementEnd {
+            end: ElementEnd::Empty,
+            ..
+        }) = reader.next().transpose()?
+        {
+            return Ok(value);
+        }
+
+        // the content is copied token by token, up to the end of the element
+        let mut depth = 0;
+        while let Some(token) = reader.next() {
+            let span = match token? {
+                Token::ElementEnd {
+                    end: ElementEnd::Close(..),
+                    ..
+                } if depth == 0 => return Ok(value),
+                Token::ElementStart { span, .. } => {
+                    depth += 1;
+                    span
+                }
+                Token::ElementEnd { end, span } => {
+                    if !matches!(end, ElementEnd::Open) {
+                        depth -= 1;
+                    }
+                    span
+                }
+                Token::Attribute { span, .. } => {
+                    value.content.to_mut().push(' ');
+                    span
+                }
+                Token::Text { text } => text,
+                Token::Cdata { span, .. } | Token::Comment { span, .. } => span,
+                _ => continue,
+            };
+            if value.content.is_empty() {
+                value.content = Cow::Borrowed(span.as_str());
+            } else {
+                value.content.to_mut().push_str(span.as_str());
+            }
+        }
+        Err(XmlError::UnexpectedEof)
+    }
+}
+
+impl<'a> XmlWrite for OtherValue<'a> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start(&self.tag)?;
+        for (key, value) in &self.attributes {
+            writer.write_attribute(key, value)?;
+        }
+        if self.content.is_empty() {
+            writer.write_element_end_empty()?;
+        } else {
+            writer.write_element_end_open()?;
+            write!(writer.inner, "{}", self.content)?;
+            writer.write_element_end_close(&self.tag)?;
+        }
+        Ok(())
+    }
+}
+
+__xml_test_suites!(
+    CustomProperty,
+    CustomProperty {
+        fmtid: FMTID_CUSTOM_PROPERTIES.into(),
+        pid: 2,
+        name: Some("DocumentId".into()),
+        value: Some("DMS-4711".into()),
+        ..Default::default()
+    },
+    r#"<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" name="DocumentId"><vt:lpwstr>DMS-4711</vt:lpwstr></property>"#,
+    CustomProperty {
+        fmtid: FMTID_CUSTOM_PROPERTIES.into(),
+        pid: 3,
+        name: Some("Confidential".into()),
+        value: Some(true.into()),
+        ..Default::default()
+    },
+    r#"<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="3" name="Confidential"><vt:bool>true</vt:bool></property>"#,
+    CustomProperty {
+        fmtid: FMTID_CUSTOM_PROPERTIES.into(),
+        pid: 4,
+        name: Some("Reviewed".into()),
+        value: Some(DateTime::parse("2024-03-05T14:30").unwrap().into()),
+        ..Default::default()
+    },
+    r#"<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="4" name="Reviewed"><vt:filetime>2024-03-05T14:30:00Z</vt:filetime></property>"#,
+    CustomProperty {
+        fmtid: FMTID_CUSTOM_PROPERTIES.into(),
+        pid: 5,
+        name: Some("Pages".into()),
+        link_target: Some("_Pages".into()),
+        value: Some((-2).into()),
+    },
+    r#"<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="5" name="Pages" linkTarget="_Pages"><vt:i4>-2</vt:i4></property>"#,
+    CustomProperty {
+        fmtid: FMTID_CUSTOM_PROPERTIES.into(),
+        pid: 6,
+        name: Some("Rate".into()),
+        value: Some(1.5.into()),
+        ..Default::default()
+    },
+    r#"<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="6" name="Rate"><vt:r8>1.5</vt:r8></property>"#,
+    CustomProperty {
+        fmtid: FMTID_CUSTOM_PROPERTIES.into(),
+        pid: 7,
+        name: Some("Code".into()),
+        value: Some(CustomValue::Other(OtherValue {
+            tag: "vt:lpstr".into(),
+            content: "A &amp; B".into(),
+            ..Default::default()
+        })),
+        ..Default::default()
+    },
+    r#"<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="7" name="Code"><vt:lpstr>A &amp; B</vt:lpstr></property>"#,
+    CustomProperty {
+        fmtid: FMTID_CUSTOM_PROPERTIES.into(),
+        pid: 8,
+        name: Some("Authors".into()),
+        value: Some(CustomValue::Other(OtherValue {
+            tag: "vt:vector".into(),
+            attributes: vec![
+                ("size".into(), "2".into()),
+                ("baseType".into(), "lpstr".into())
+            ],
+            content: "<vt:lpstr>Jane</vt:lpstr><vt:lpstr/>".into(),
+        })),
+        ..Default::default()
+    },
+    r#"<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="8" name="Authors"><vt:vector size="2" baseType="lpstr"><vt:lpstr>Jane</vt:lpstr><vt:lpstr/></vt:vector></property>"#,
+    CustomProperty {
+        fmtid: FMTID_CUSTOM_PROPERTIES.into(),
+        pid: 9,
+        name: Some("Nothing".into()),
+        value: Some(CustomValue::Other(OtherValue {
+            tag: "vt:empty".into(),
+            ..Default::default()
+        })),
+        ..Default::default()
+    },
+    r#"<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="9" name="Nothing"><vt:empty/></property>"#,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_remove() {
+        let mut custom = CustomProperties::default();
+        custom.set("DocumentId", "DMS-4711");
+        custom.set("Classification", String::from("internal"));
+        custom.set("DocumentId", "DMS-4712");
+
+        let pids: Vec<_> = custom.properties.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, [2, 3]);
+        assert_eq!(custom.get("DocumentId").unwrap().as_str(), Some("DMS-4712"));
+        assert_eq!(custom.get("DocumentId").unwrap().as_i32(), None);
+
+        let removed = custom.remove("DocumentId").unwrap();
+        assert_eq!(removed.pid, 2);
+        assert!(custom.get("DocumentId").is_none());
+        custom.set("Reviewed", DateTime::parse("2024-03-05").unwrap());
+        assert_eq!(custom.properties[1].pid, 4);
+        assert_eq!(
+            custom.get("Reviewed").unwrap().as_date_time(),
+            DateTime::parse("2024-03-05")
+        );
+    }
+
+    #[test]
+    fn read_part() -> XmlResult<()> {
+        let custom = CustomProperties::from_str(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/custom-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+                <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" name="Approved"><vt:bool>1</vt:bool></property>
+                <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="3" name="Code"><vt:lpstr>ABC</vt:lpstr></property>
+            </Properties>"#,
+        )?;
+        assert_eq!(custom.get("Approved").unwrap().as_bool(), Some(true));
+        // values of other types are kept as they are
+        let Some(CustomValue::Other(code)) = custom.get("Code") else {
+            panic!("missing value")
+        };
+        assert_eq!(code.tag, "vt:lpstr");
+        assert_eq!(code.content, "ABC");
+        assert_eq!(code.text().as_deref(), Some("ABC"));
+
+        let written = custom.to_string()?;
+        assert!(written.starts_with(SCHEMA_XML));
+        assert!(written.contains(r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/custom-properties" xmlns:vt="#));
+        assert!(written.contains("<vt:bool>true</vt:bool>"));
+        assert!(written.contains(r#"name="Code"><vt:lpstr>ABC</vt:lpstr></property>"#));
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+//! Renumbering of the ids a document refers to, before it is appended to another one
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::document::{
+    BodyContent, BookmarkStart, Drawing, HeaderFooterReference, Hyperlink, Paragraph,
+    ParagraphContent, RevisionIds, Run, RunContent, SimpleField, Table, TableCell,
+    TableCellContent, VisitMut,
+};
+use crate::formatting::{ParagraphProperty, SectionProperty};
+
+/// Rewrites the ids used by the content of a document, so they don't clash with the ones of
+/// the document it is appended to
+#[derive(Default)]
+pub(crate) struct IdRemapper {
+    /// Relationships of the main document part
+    pub relationships: HashMap<String, String>,
+    /// `w:numId` of numbering definition instances
+    pub numberings: HashMap<isize, isize>,
+    pub footnotes: HashMap<String, String>,
+    pub endnotes: HashMap<String, String>,
+    pub comments: HashMap<String, String>,
+    pub bookmarks: HashMap<String, String>,
+    /// Added to the id of every bookmark
+    pub bookmark_offset: usize,
+    /// Added to the id of every `wp:docPr`
+    pub drawing_offset: isize,
+    /// Shifts the ids of revisions
+    pub revisions: RevisionIds,
+}
+
+impl IdRemapper {
+    pub fn remap_paragraph_property(&self, property: &mut ParagraphProperty) {
+        if let Some(id) = property.numbering.as_mut().and_then(|n| n.id.as_mut()) {
+            if let Some(new_id) = self.numberings.get(&id.value) {
+                id.value = *new_id;
+            }
+        }
+        if let Some(section) = &mut property.section_property {
+            self.remap_section(section);
+        }
+    }
+
+    pub fn remap_section(&self, section: &mut SectionProperty) {
+        for reference in section.header_footer_references.iter_mut() {
+            let id = match reference {
+                HeaderFooterReference::Header(header) => &mut header.id,
+                HeaderFooterReference::Footer(footer) => &mut footer.id,
+            };
+            if let Some(id) = id {
+                remap(&self.relationships, id);
+            }
+        }
+    }
+
+    fn remap_bookmark(&self, start: &mut BookmarkStart) {
+        self.remap_bookmark_id(&mut start.id);
+        if let Some(name) = &mut start.name {
+            remap(&self.bookmarks, name);
+        }
+    }
+
+    fn remap_bookmark_id(&self, id: &mut Option<Cow<str>>) {
+        if let Some(value) = id.as_ref().and_then(|id| id.parse::<usize>().ok()) {
+            *id = Some((value + self.bookmark_offset).to_string().into());
+        }
+    }
+
+    /// Renames the bookmarks which are arguments of a field instruction, like `REF` or `PAGEREF`
+    fn remap_instruction(&self, instr: &mut Cow<str>) {
+        if self.bookmarks.is_empty() {
+            return;
+        }
+        let words: Vec<&str> = instr.split(' ').collect();
+        if words.iter().any(|word| self.bookmarks.contains_key(*word)) {
+            let words: Vec<&str> = words
+                .into_iter()
+                .map(|word| self.bookmarks.get(word).map_or(word, |name| name))
+                .collect();
+            *instr = words.join(" ").into();
+        }
+    }
+
+    fn remap_drawing(&self, drawing: &mut Drawing) {
+        let inline = drawing
+            .inline
+            .iter_mut()
+            .map(|inline| (&mut inline.doc_property.id, &mut inline.graphic));
+        let anchor = drawing
+            .anchor
+            .iter_mut()
+            .map(|anchor| (&mut anchor.doc_property.id, &mut anchor.graphic));
+        for (id, graphic) in inline.chain(anchor) {
+            if let Some(id) = id {
+                *id += self.drawing_offset;
+            }
+            for picture in graphic.iter_mut().flat_map(|g| g.data.children.iter_mut()) {
+                let blip = &mut picture.fill.blip;
+                remap(&self.relationships, &mut blip.embed);
+                for ext in blip
+                    .ext_lst
+                    .iter_mut()
+                    .flat_map(|l| l.extensions.iter_mut())
+                {
+                    if let Some(svg) = &mut ext.svg_blip {
+                        remap(&self.relationships, &mut svg.embed);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn remap(map: &HashMap<String, String>, value: &mut Cow<str>) {
+    if let Some(new_value) = map.get(value.as_ref()) {
+        *value = Cow::Owned(new_value.clone());
+    }
+}
+
+impl<'a> VisitMut<'a> for IdRemapper {
+    fn visit_block(&mut self, block: &mut BodyContent<'a>) {
+        self.revisions.visit_block(block);
+    }
+
+    fn visit_inline(&mut self, content: &mut ParagraphContent<'a>) {
+        self.revisions.visit_inline(content);
+        match content {
+            ParagraphContent::BookmarkStart(start) => self.remap_bookmark(start),
+            ParagraphContent::BookmarkEnd(end) => self.remap_bookmark_id(&mut end.id),
+            ParagraphContent::CommentRangeStart(start) => remap(&self.comments, &mut start.id),
+            ParagraphContent::CommentRangeEnd(end) => remap(&self.comments, &mut end.id),
+            _ => {}
+        }
+    }
+
+    fn visit_paragraph(&mut self, paragraph: &mut Paragraph<'a>) {
+        self.revisions.visit_paragraph(paragraph);
+        if let Some(property) = &mut paragraph.property {
+            self.remap_paragraph_property(property);
+        }
+    }
+
+    fn visit_table(&mut self, table: &mut Table<'a>) {
+        self.revisions.visit_table(table);
+    }
+
+    fn visit_cell(&mut self, cell: &mut TableCell<'a>) {
+        self.revisions.visit_cell(cell);
+        for content in cell.content.iter_mut() {
+            match content {
+                TableCellContent::BookmarkStart(start) => self.remap_bookmark(start),
+                TableCellContent::BookmarkEnd(end) => self.remap_bookmark_id(&mut end.id),
+                TableCellContent::CommentRangeStart(start) => remap(&self.comments, &mut start.id),
+                TableCellContent::CommentRangeEnd(end) => remap(&self.comments, &mut end.id),
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_hyperlink(&mut self, link: &mut Hyperlink<'a>) {
+        self.revisions.visit_hyperlink(link);
+        if let Some(id) = &mut link.id {
+            remap(&self.relationships, id);
+        }
+        if let Some(anchor) = &mut link.anchor {
+            remap(&self.bookmarks, anchor);
+        }
+    }
+
+    fn visit_simple_field(&mut self, field: &mut SimpleField<'a>) {
+        self.remap_instruction(&mut field.instr);
+    }
+
+    fn visit_run(&mut self, run: &mut Run<'a>) {
+        self.revisions.visit_run(run);
+        for content in run.content.iter_mut() {
+            let (map, id) = match content {
+                RunContent::InstrText(instr) => {
+                    self.remap_instruction(&mut instr.text);
+                    continue;
+                }
+                RunContent::Drawing(drawing) => {
+                    self.remap_drawing(drawing);
+                    continue;
+                }
+                RunContent::FootnoteReference(note) => (&self.footnotes, &mut note.id),
+                RunContent::EndnoteReference(note) => (&self.endnotes, &mut note.id),
+                RunContent::CommentReference(comment) => (&self.comments, &mut comment.id),
+                _ => continue,
+            };
+            if let Some(id) = id {
+                remap(map, id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{BookmarkEnd, FootnoteReference, Insertion, TableRow, TableRowContent};
+    use crate::formatting::CharacterProperty;
+
+    #[test]
+    fn remaps_references() {
+        let mut remapper = IdRemapper {
+            bookmark_offset: 10,
+            ..Default::default()
+        };
+        remapper.bookmarks.insert("intro".into(), "intro_1".into());
+        remapper.footnotes.insert("1".into(), "4".into());
+
+        let mut para = Paragraph::default()
+            .push(BookmarkStart {
+                id: Some("0".into()),
+                name: Some("intro".into()),
+            })
+            .push(SimpleField::default().instr("PAGEREF intro \\h"))
+            .push(Run::default().push(FootnoteReference {
+                id: Some("1".into()),
+                ..Default::default()
+            }));
+        crate::document::walk_paragraph_mut(&mut remapper, &mut para);
+
+        let ParagraphContent::BookmarkStart(start) = &para.content[0] else {
+            unreachable!()
+        };
+        assert_eq!(start.id.as_deref(), Some("10"));
+        assert_eq!(start.name.as_deref(), Some("intro_1"));
+        let ParagraphContent::SimpleField(field) = &para.content[1] else {
+            unreachable!()
+        };
+        assert_eq!(field.instr, "PAGEREF intro_1 \\h");
+        let ParagraphContent::Run(run) = &para.content[2] else {
+            unreachable!()
+        };
+        let RunContent::FootnoteReference(note) = &run.content[0] else {
+            unreachable!()
+        };
+        assert_eq!(note.id.as_deref(), Some("4"));
+    }
+
+    #[test]
+    fn remaps_cell_markers_and_revisions() {
+        let mut remapper = IdRemapper {
+            bookmark_offset: 10,
+            revisions: RevisionIds {
+                offset: 5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        remapper.bookmarks.insert("cell".into(), "cell_1".into());
+
+        let mut cell = TableCell::paragraph(
+            Paragraph::default().push(
+                Insertion::default()
+                    .id(1isize)
+                    .push(Run::default().push_text("new")),
+            ),
+        );
+        cell.content.insert(
+            0,
+            TableCellContent::BookmarkStart(BookmarkStart {
+                id: Some("0".into()),
+                name: Some("cell".into()),
+            }),
+        );
+        cell.content
+            .push(TableCellContent::BookmarkEnd(BookmarkEnd {
+                id: Some("0".into()),
+            }));
+        let mut blocks = vec![BodyContent::Table(
+            Table::default().push_row(TableRow::default().push_cell(cell)),
+        )];
+        let mut run = Run::default().property(CharacterProperty {
+            inserted: Some(Default::default()),
+            ..Default::default()
+        });
+        crate::document::walk_blocks_mut(&mut remapper, &mut blocks);
+        remapper.visit_run(&mut run);
+
+        let BodyContent::Table(table) = &blocks[0] else {
+            unreachable!()
+        };
+        let TableRowContent::TableCell(cell) = &table.rows[0].cells[0] else {
+            unreachable!()
+        };
+        let TableCellContent::BookmarkStart(start) = &cell.content[0] else {
+            unreachable!()
+        };
+        assert_eq!(start.id.as_deref(), Some("10"));
+        assert_eq!(start.name.as_deref(), Some("cell_1"));
+        let TableCellContent::BookmarkEnd(end) = &cell.content[2] else {
+            unreachable!()
+        };
+        assert_eq!(end.id.as_deref(), Some("10"));
+        let TableCellContent::Paragraph(para) = &cell.content[1] else {
+            unreachable!()
+        };
+        let ParagraphContent::Insertion(ins) = &para.content[0] else {
+            unreachable!()
+        };
+        assert_eq!(ins.id, 6);
+        let mark = run.property.as_ref().and_then(|p| p.inserted.as_ref());
+        assert_eq!(mark.unwrap().id, 5);
+        assert_eq!(remapper.revisions.max_id, 6);
+    }
+}
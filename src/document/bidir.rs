@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__setter, __xml_test_suites, document::Run};
+use crate::{__into_owned, __setter, __xml_test_suites, document::Run};
 
 /// A bidirectional embedding, which can nest to more bidirectional embeddings
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
@@ -16,6 +16,8 @@ pub struct BidirectionalEmbedding<'a> {
     pub nested_levels: Vec<BidirectionalEmbedding<'a>>,
 }
 
+__into_owned! { BidirectionalEmbedding { runs, nested_levels } }
+
 impl<'a> BidirectionalEmbedding<'a> {
     __setter!(runs: Vec<Run<'a>>);
     __setter!(nested_levels: Vec<BidirectionalEmbedding<'a>>);
@@ -2,9 +2,13 @@ use derive_more::From;
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Borrow;
 
-use crate::__xml_test_suites;
-use crate::document::{Paragraph, Run, Table, TableCell};
+use crate::document::{
+    paragraph::replace_inline_tracked, Deletion, Insertion, MoveFrom, MoveFromRangeEnd,
+    MoveFromRangeStart, MoveTo, MoveToRangeEnd, MoveToRangeStart, Paragraph, ParagraphContent, Run,
+    Table, TableCell, TrackChanges,
+};
 use crate::formatting::SectionProperty;
+use crate::{__into_owned, __xml_test_suites};
 
 use super::SDT;
 
@@ -16,16 +20,90 @@ use super::SDT;
 #[xml(tag = "w:body")]
 pub struct Body<'a> {
     /// Specifies the contents of the body of the document.
-    #[xml(child = "w:p", child = "w:tbl", child = "w:sectPr", child = "w:sdt")]
+    #[xml(
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sectPr",
+        child = "w:sdt",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:moveFromRangeStart",
+        child = "w:moveFromRangeEnd",
+        child = "w:moveToRangeStart",
+        child = "w:moveToRangeEnd"
+    )]
     pub content: Vec<BodyContent<'a>>,
 }
 
+__into_owned! { Body { content } }
+
 impl<'a> Body<'a> {
+    /// Appends content to the body
+    ///
+    /// Content is put before the section properties at the end of the body, which have to
+    /// stay its last child.
     pub fn push<T: Into<BodyContent<'a>>>(&mut self, content: T) -> &mut Self {
-        self.content.push(content.into());
+        let content = content.into();
+        match self.content.last() {
+            Some(BodyContent::SectionProperty(_))
+                if !matches!(content, BodyContent::SectionProperty(_)) =>
+            {
+                self.content.insert(self.content.len() - 1, content);
+            }
+            _ => self.content.push(content),
+        }
         self
     }
 
+    /// Returns the section properties at the end of the body, adding them if they are missing
+    ///
+    /// They describe the last section, which is the whole document unless a paragraph
+    /// ends an earlier one.
+    pub fn section_property_mut(&mut self) -> &mut SectionProperty<'a> {
+        let index = match self
+            .content
+            .iter()
+            .rposition(|content| matches!(content, BodyContent::SectionProperty(_)))
+        {
+            Some(index) => index,
+            None => {
+                self.content.push(SectionProperty::default().into());
+                self.content.len() - 1
+            }
+        };
+        match &mut self.content[index] {
+            BodyContent::SectionProperty(sect_pr) => sect_pr,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the properties of every section, in document order
+    ///
+    /// Sections ended by a paragraph come first, followed by the one at the end of the body.
+    pub fn sections_mut(&mut self) -> Vec<&mut SectionProperty<'a>> {
+        let mut sections = Vec::new();
+        let mut last = None;
+        for content in self.content.iter_mut() {
+            match content {
+                BodyContent::Paragraph(para) => {
+                    if let Some(sect_pr) = para
+                        .property
+                        .as_mut()
+                        .and_then(|prop| prop.section_property.as_mut())
+                    {
+                        sections.push(sect_pr);
+                    }
+                }
+                BodyContent::SectionProperty(sect_pr) => last = Some(sect_pr),
+                _ => {}
+            }
+        }
+        sections.extend(last);
+        sections
+    }
+
     pub fn text(&self) -> String {
         let v: Vec<_> = self
             .content
@@ -37,6 +115,9 @@ impl<'a> Body<'a> {
                 BodyContent::Sdt(sdt) => Some(sdt.text()),
                 BodyContent::TableCell(_) => None,
                 BodyContent::Run(_) => None,
+                BodyContent::Insertion(ins) => Some(ins.text()),
+                BodyContent::MoveTo(to) => Some(to.text()),
+                _ => None,
             })
             .collect();
         v.join("\r\n")
@@ -67,11 +148,35 @@ impl<'a> Body<'a> {
                 BodyContent::Sdt(_) => {}
                 BodyContent::TableCell(_) => {}
                 BodyContent::Run(_) => {}
+                BodyContent::Insertion(ins) => {
+                    ins.replace_text(dic)?;
+                }
+                BodyContent::MoveTo(to) => {
+                    to.replace_text(dic)?;
+                }
+                _ => {}
             }
         }
         Ok(())
     }
 
+    /// Replaces text like [`Body::replace_text`], but records each replacement as a tracked change
+    ///
+    /// Every match becomes a `w:del` of the old text followed by a `w:ins` of the new text,
+    /// attributed to the author and date configured in `track`.
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        replace_blocks_tracked(&mut self.content, dic, track)
+    }
+
     // pub fn iter_text(&self) -> impl Iterator<Item = &Cow<'a, str>> {
     //     self.content
     //         .iter()
@@ -91,6 +196,63 @@ impl<'a> Body<'a> {
     // }
 }
 
+/// Replaces text in block content, recording each replacement as a tracked change
+pub(crate) fn replace_blocks_tracked<'a, 'b, I, T, S>(
+    content: &mut Vec<BodyContent<'a>>,
+    dic: T,
+    track: &mut TrackChanges,
+) -> crate::DocxResult<()>
+where
+    S: AsRef<str> + 'b,
+    T: IntoIterator<Item = I> + Copy,
+    I: Borrow<(S, S)>,
+{
+    for item in std::mem::take(content) {
+        match item {
+            BodyContent::Paragraph(mut p) => {
+                p.replace_text_tracked(dic, track)?;
+                content.push(p.into());
+            }
+            BodyContent::Table(mut t) => {
+                t.replace_text_tracked(dic, track)?;
+                content.push(t.into());
+            }
+            BodyContent::Sdt(mut sdt) => {
+                sdt.replace_text_tracked(dic, track)?;
+                content.push(sdt.into());
+            }
+            BodyContent::TableCell(mut tc) => {
+                tc.replace_text_tracked(dic, track)?;
+                content.push(tc.into());
+            }
+            BodyContent::Run(run) => match run.replace_text_tracked(dic, track) {
+                Some(replaced) => content.extend(replaced.into_iter().map(run_level_block)),
+                None => content.push(run.into()),
+            },
+            BodyContent::Insertion(mut ins) => {
+                replace_inline_tracked(&mut ins.content, dic, track)?;
+                content.push(ins.into());
+            }
+            BodyContent::MoveTo(mut to) => {
+                replace_inline_tracked(&mut to.content, dic, track)?;
+                content.push(to.into());
+            }
+            item => content.push(item),
+        }
+    }
+    Ok(())
+}
+
+/// Turns the runs and revisions a body-level run is split into back into block content
+fn run_level_block(content: ParagraphContent) -> BodyContent {
+    match content {
+        ParagraphContent::Insertion(ins) => ins.into(),
+        ParagraphContent::Deletion(del) => del.into(),
+        ParagraphContent::Run(run) => run.into(),
+        content => Paragraph::default().push(content).into(),
+    }
+}
+
 /// A set of elements that can be contained in the body
 #[derive(Debug, From, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -107,6 +269,30 @@ pub enum BodyContent<'a> {
     TableCell(TableCell<'a>),
     #[xml(tag = "w:r")]
     Run(Run<'a>),
+    #[xml(tag = "w:ins")]
+    Insertion(Insertion<'a>),
+    #[xml(tag = "w:del")]
+    Deletion(Deletion<'a>),
+    #[xml(tag = "w:moveFrom")]
+    MoveFrom(MoveFrom<'a>),
+    #[xml(tag = "w:moveTo")]
+    MoveTo(MoveTo<'a>),
+    #[xml(tag = "w:moveFromRangeStart")]
+    MoveFromRangeStart(MoveFromRangeStart<'a>),
+    #[xml(tag = "w:moveFromRangeEnd")]
+    MoveFromRangeEnd(MoveFromRangeEnd),
+    #[xml(tag = "w:moveToRangeStart")]
+    MoveToRangeStart(MoveToRangeStart<'a>),
+    #[xml(tag = "w:moveToRangeEnd")]
+    MoveToRangeEnd(MoveToRangeEnd),
+}
+
+__into_owned! {
+    enum BodyContent {
+        Paragraph, Table, Sdt, SectionProperty, TableCell, Run, Insertion, Deletion, MoveFrom,
+        MoveTo, MoveFromRangeStart, MoveToRangeStart;
+        MoveFromRangeEnd, MoveToRangeEnd
+    }
 }
 
 __xml_test_suites!(
@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__setter, __xml_test_suites};
+use crate::{__into_owned, __setter, __xml_test_suites};
 
 /// End of bookmark
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
@@ -13,6 +13,8 @@ pub struct BookmarkEnd<'a> {
     pub id: Option<Cow<'a, str>>,
 }
 
+__into_owned! { BookmarkEnd { id } }
+
 impl<'a> BookmarkEnd<'a> {
     __setter!(id: Option<Cow<'a, str>>);
 }
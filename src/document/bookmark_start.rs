@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__setter, __xml_test_suites};
+use crate::{__into_owned, __setter, __xml_test_suites};
 
 /// Beginning of bookmark
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
@@ -16,6 +16,8 @@ pub struct BookmarkStart<'a> {
     pub name: Option<Cow<'a, str>>,
 }
 
+__into_owned! { BookmarkStart { id, name } }
+
 impl<'a> BookmarkStart<'a> {
     __setter!(id: Option<Cow<'a, str>>);
     __setter!(name: Option<Cow<'a, str>>);
@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::__setter;
+use crate::{__into_owned, __setter};
 
 /// Start of comment
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
@@ -13,6 +13,8 @@ pub struct CommentRangeStart<'a> {
     pub id: Cow<'a, str>,
 }
 
+__into_owned! { CommentRangeStart { id } }
+
 impl<'a> CommentRangeStart<'a> {
     __setter!(id: Cow<'a, str>);
 }
@@ -27,6 +29,8 @@ pub struct CommentRangeEnd<'a> {
     pub id: Cow<'a, str>,
 }
 
+__into_owned! { CommentRangeEnd { id } }
+
 impl<'a> CommentRangeEnd<'a> {
     __setter!(id: Cow<'a, str>);
 }
@@ -7,6 +7,7 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::{borrow::Cow, io::Write};
 
 use crate::{
+    __into_owned,
     document::Paragraph,
     schema::{SCHEMA_MAIN, SCHEMA_WORDML_14},
 };
@@ -21,6 +22,8 @@ pub struct Comments<'a> {
     pub comments: Vec<Comment<'a>>,
 }
 
+__into_owned! { Comments { comments } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:comment")]
@@ -37,6 +40,8 @@ pub struct Comment<'a> {
     pub content: Paragraph<'a>,
 }
 
+__into_owned! { Comment { author, content; id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:annotationRef")]
@@ -50,6 +55,8 @@ pub struct CommentReference<'a> {
     pub id: Option<Cow<'a, str>>,
 }
 
+__into_owned! { CommentReference { id } }
+
 impl<'a> XmlWrite for Comments<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let Comments { comments } = self;
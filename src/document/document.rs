@@ -6,8 +6,8 @@
 use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::io::Write;
 
-use crate::__xml_test_suites;
 use crate::schema::{SCHEMA_MAIN, SCHEMA_RELATIONSHIPS_DOCUMENT, SCHEMA_WORDML_14, SCHEMA_WP};
+use crate::{__into_owned, __xml_test_suites};
 
 use crate::document::{Body, BodyContent};
 
@@ -21,6 +21,8 @@ pub struct Document<'a> {
     pub body: Body<'a>,
 }
 
+__into_owned! { Document { body } }
+
 impl<'a> Document<'a> {
     pub fn push<T: Into<BodyContent<'a>>>(&mut self, content: T) -> &mut Self {
         self.body.push(content);
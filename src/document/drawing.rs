@@ -5,7 +5,11 @@ use std::borrow::Cow;
 use derive_more::From;
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__define_enum, __string_enum};
+use crate::schema::{SCHEMA_DRAWINGML, SCHEMA_PICTURE, SCHEMA_SVG};
+use crate::{__define_enum, __into_owned, __string_enum};
+
+/// Uri of the `a:ext` which holds an `asvg:svgBlip`
+const SVG_BLIP_EXTENSION: &str = "{96DAC541-7B7A-43D3-8B79-37D633B846F1}";
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -18,6 +22,8 @@ pub struct Drawing<'a> {
     pub inline: Option<Inline<'a>>,
 }
 
+__into_owned! { Drawing { anchor, inline } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "wp:anchor")]
@@ -65,6 +71,15 @@ pub struct Anchor<'a> {
     pub graphic: Option<Graphic<'a>>,
 }
 
+__into_owned! {
+    Anchor {
+        doc_property, graphic;
+        dist_t, dist_b, dist_l, dist_r, simple_pos_attr, relative_height, behind_doc, locked,
+        layout_in_cell, allow_overlap, simple_pos, position_horizontal, position_vertical, extent,
+        wrap
+    }
+}
+
 #[derive(Debug, From, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum Wrap {
@@ -239,6 +254,14 @@ pub struct Inline<'a> {
     pub graphic: Option<Graphic<'a>>,
 }
 
+__into_owned! {
+    Inline {
+        doc_property, graphic;
+        dist_t, dist_b, dist_l, dist_r, simple_pos_attr, relative_height, behind_doc, locked,
+        layout_in_cell, allow_overlap, simple_pos, position_horizontal, position_vertical, extent
+    }
+}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "wp:docPr")]
@@ -251,6 +274,8 @@ pub struct DocPr<'a> {
     pub descr: Option<Cow<'a, str>>,
 }
 
+__into_owned! { DocPr { name, descr; id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:graphic")]
@@ -261,6 +286,8 @@ pub struct Graphic<'a> {
     pub data: GraphicData<'a>,
 }
 
+__into_owned! { Graphic { a, data } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:graphicData")]
@@ -272,6 +299,8 @@ pub struct GraphicData<'a> {
     pub children: Vec<Picture<'a>>,
 }
 
+__into_owned! { GraphicData { uri, children } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "pic:pic")]
@@ -286,6 +315,8 @@ pub struct Picture<'a> {
     pub sp_pr: SpPr<'a>,
 }
 
+__into_owned! { Picture { a, nv_pic_pr, fill, sp_pr } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "pic:spPr")]
@@ -296,6 +327,8 @@ pub struct SpPr<'a> {
     pub prst_geom: Option<PrstGeom<'a>>,
 }
 
+__into_owned! { SpPr { prst_geom; xfrm } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:prstGeom")]
@@ -306,6 +339,8 @@ pub struct PrstGeom<'a> {
     pub av_lst: Option<AvList>,
 }
 
+__into_owned! { PrstGeom { prst; av_lst } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:avLst")]
@@ -361,6 +396,8 @@ pub struct NvPicPr<'a> {
     pub c_nv_pic_pr: Option<CNvPicPr>,
 }
 
+__into_owned! { NvPicPr { c_nv_pr; c_nv_pic_pr } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "pic:cNvPr")]
@@ -373,6 +410,8 @@ pub struct CNvPr<'a> {
     pub descr: Option<Cow<'a, str>>,
 }
 
+__into_owned! { CNvPr { name, descr; id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "pic:cNvPicPr")]
@@ -388,6 +427,8 @@ pub struct BlipFill<'a> {
     pub stretch: Option<Stretch>,
 }
 
+__into_owned! { BlipFill { blip; stretch } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:blip")]
@@ -396,8 +437,62 @@ pub struct Blip<'a> {
     pub embed: Cow<'a, str>,
     #[xml(default, attr = "cstate")]
     pub cstate: Option<Cow<'a, str>>,
+    #[xml(child = "a:extLst")]
+    pub ext_lst: Option<BlipExtensionList<'a>>,
+}
+
+__into_owned! { Blip { embed, cstate, ext_lst } }
+
+impl<'a> Blip<'a> {
+    /// Returns the relationship id of the SVG image, if the blip has one
+    ///
+    /// `embed` then refers to its raster fallback.
+    pub fn svg_embed(&self) -> Option<&str> {
+        self.ext_lst
+            .iter()
+            .flat_map(|list| list.extensions.iter())
+            .find_map(|ext| ext.svg_blip.as_ref())
+            .map(|svg| &*svg.embed)
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "a:extLst")]
+pub struct BlipExtensionList<'a> {
+    #[xml(child = "a:ext")]
+    pub extensions: Vec<BlipExtension<'a>>,
+}
+
+__into_owned! { BlipExtensionList { extensions } }
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "a:ext")]
+pub struct BlipExtension<'a> {
+    #[xml(default, attr = "uri")]
+    pub uri: Cow<'a, str>,
+    #[xml(child = "asvg:svgBlip")]
+    pub svg_blip: Option<SvgBlip<'a>>,
 }
 
+__into_owned! { BlipExtension { uri, svg_blip } }
+
+/// SVG image of a picture, introduced by Office 2016
+///
+/// Consumers which don't support it display the `a:blip` it extends.
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "asvg:svgBlip")]
+pub struct SvgBlip<'a> {
+    #[xml(attr = "xmlns:asvg")]
+    pub asvg: Option<Cow<'a, str>>,
+    #[xml(default, attr = "r:embed")]
+    pub embed: Cow<'a, str>,
+}
+
+__into_owned! { SvgBlip { asvg, embed } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:stretch")]
@@ -421,3 +516,101 @@ pub struct Extent {
     #[xml(default, attr = "cy")]
     pub cy: u64,
 }
+
+impl<'a> Drawing<'a> {
+    /// Creates an inline picture showing the image of the relationship `embed`
+    ///
+    /// `id` has to be unique among the drawing objects of the document,
+    /// `cx` and `cy` are the displayed size in EMUs.
+    pub fn picture<S: Into<Cow<'a, str>>>(embed: S, id: isize, cx: u64, cy: u64) -> Self {
+        let name: Cow<'a, str> = format!("Picture {}", id).into();
+        Drawing {
+            anchor: None,
+            inline: Some(Inline {
+                dist_t: Some(0),
+                dist_b: Some(0),
+                dist_l: Some(0),
+                dist_r: Some(0),
+                extent: Some(Extent { cx, cy }),
+                doc_property: DocPr {
+                    id: Some(id),
+                    name: Some(name.clone()),
+                    descr: None,
+                },
+                graphic: Some(Graphic {
+                    a: SCHEMA_DRAWINGML.into(),
+                    data: GraphicData {
+                        uri: SCHEMA_PICTURE.into(),
+                        children: vec![Picture {
+                            a: SCHEMA_PICTURE.into(),
+                            nv_pic_pr: NvPicPr {
+                                c_nv_pr: Some(CNvPr {
+                                    id: Some(0),
+                                    name: Some(name),
+                                    descr: None,
+                                }),
+                                c_nv_pic_pr: Some(CNvPicPr {}),
+                            },
+                            fill: BlipFill {
+                                blip: Blip {
+                                    embed: embed.into(),
+                                    cstate: None,
+                                    ext_lst: None,
+                                },
+                                stretch: Some(Stretch {
+                                    fill_rect: Some(FillRect {}),
+                                }),
+                            },
+                            sp_pr: SpPr {
+                                xfrm: Some(Xfrm {
+                                    offset: Some(Offset {
+                                        x: Some(0),
+                                        y: Some(0),
+                                    }),
+                                    ext: Some(Ext {
+                                        cx: Some(cx as isize),
+                                        cy: Some(cy as isize),
+                                    }),
+                                }),
+                                prst_geom: Some(PrstGeom {
+                                    prst: Some("rect".into()),
+                                    av_lst: Some(AvList {}),
+                                }),
+                            },
+                        }],
+                    },
+                }),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Creates an inline picture showing the SVG image of the relationship `svg_embed`
+    ///
+    /// Consumers which don't support SVG show the raster image of `embed` instead.
+    pub fn svg_picture<S, T>(embed: S, svg_embed: T, id: isize, cx: u64, cy: u64) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+        T: Into<Cow<'a, str>>,
+    {
+        let svg_embed = svg_embed.into();
+        let mut drawing = Drawing::picture(embed, id, cx, cy);
+        let pictures = drawing
+            .inline
+            .iter_mut()
+            .flat_map(|inline| inline.graphic.iter_mut())
+            .flat_map(|graphic| graphic.data.children.iter_mut());
+        for picture in pictures {
+            picture.fill.blip.ext_lst = Some(BlipExtensionList {
+                extensions: vec![BlipExtension {
+                    uri: SVG_BLIP_EXTENSION.into(),
+                    svg_blip: Some(SvgBlip {
+                        asvg: Some(SCHEMA_SVG.into()),
+                        embed: svg_embed.clone(),
+                    }),
+                }],
+            });
+        }
+        drawing
+    }
+}
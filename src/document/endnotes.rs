@@ -7,8 +7,8 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::borrow::Cow;
 use std::io::Write;
 
-use crate::__xml_test_suites;
 use crate::schema::{SCHEMA_MAIN, SCHEMA_WORDML_14};
+use crate::{__into_owned, __xml_test_suites};
 
 use crate::document::BodyContent;
 
@@ -22,6 +22,8 @@ pub struct EndNotes<'a> {
     pub content: Vec<EndNote<'a>>,
 }
 
+__into_owned! { EndNotes { content } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:endnote")]
@@ -30,10 +32,25 @@ pub struct EndNote<'a> {
     pub ty: Option<NoteSeparator>,
     #[xml(attr = "w:id")]
     pub id: Option<isize>,
-    #[xml(child = "w:sdt", child = "w:p", child = "w:tbl", child = "w:sectPr")]
+    #[xml(
+        child = "w:sdt",
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sectPr",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:moveFromRangeStart",
+        child = "w:moveFromRangeEnd",
+        child = "w:moveToRangeStart",
+        child = "w:moveToRangeEnd"
+    )]
     pub content: Vec<BodyContent<'a>>,
 }
 
+__into_owned! { EndNote { content; ty, id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:endnoteRef")]
@@ -50,6 +67,8 @@ pub struct EndnoteReference<'a> {
     pub id: Option<Cow<'a, str>>,
 }
 
+__into_owned! { EndnoteReference { id; supress_reference_mark } }
+
 impl<'a> EndNote<'a> {
     pub fn push<T: Into<BodyContent<'a>>>(&mut self, content: T) -> &mut Self {
         self.content.push(content.into());
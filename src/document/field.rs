@@ -0,0 +1,893 @@
+use hard_xml::{XmlRead, XmlWrite};
+use std::borrow::{Borrow, Cow};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+use crate::{
+    __into_owned, __setter, __xml_test_suites,
+    document::{
+        field_format::{format_number, format_text, DateTime},
+        instrtext::{DelInstrText, InstrText},
+        CharType, FieldChar, Paragraph, ParagraphContent, Run, RunContent, Text, TextSpace, Visit,
+        VisitMut,
+    },
+};
+
+/// Simple Field
+///
+/// A field whose instruction is kept in an attribute and whose result is the runs inside it.
+///
+/// ```rust
+/// use docx_rust::document::*;
+///
+/// let field = SimpleField::default()
+///     .instr("PAGE")
+///     .push(Run::default().push_text("1"));
+/// ```
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:fldSimple")]
+pub struct SimpleField<'a> {
+    /// Specifies the field instruction
+    #[xml(attr = "w:instr")]
+    pub instr: Cow<'a, str>,
+    /// Specifies that the result shall not be recalculated
+    #[xml(attr = "w:fldLock")]
+    pub lock: Option<bool>,
+    /// Specifies that the result is stale and shall be recalculated
+    #[xml(attr = "w:dirty")]
+    pub dirty: Option<bool>,
+    /// Specifies the cached result of the field
+    #[xml(child = "w:r")]
+    pub content: Vec<Run<'a>>,
+}
+
+__into_owned! { SimpleField { instr, content; lock, dirty } }
+
+impl<'a> SimpleField<'a> {
+    __setter!(instr: Cow<'a, str>);
+    __setter!(lock: Option<bool>);
+    __setter!(dirty: Option<bool>);
+
+    #[inline(always)]
+    pub fn push(mut self, run: Run<'a>) -> Self {
+        self.content.push(run);
+        self
+    }
+
+    pub fn text(&self) -> String {
+        self.iter_text()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    pub fn iter_text(&self) -> Box<dyn Iterator<Item = &Cow<'a, str>> + '_> {
+        Box::new(self.content.iter().flat_map(|run| run.iter_text()))
+    }
+
+    pub fn iter_text_mut(&mut self) -> Box<dyn Iterator<Item = &mut Cow<'a, str>> + '_> {
+        Box::new(self.content.iter_mut().flat_map(|run| run.iter_text_mut()))
+    }
+
+    pub fn replace_text<'b, I, T, S>(&mut self, dic: T) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        for run in self.content.iter_mut() {
+            run.replace_text(dic)?;
+        }
+        Ok(())
+    }
+}
+
+/// A field of the document
+///
+/// Fields are read from `w:fldSimple` elements as well as from complex fields, whose
+/// instruction and result are spread over runs between `w:fldChar` elements and may span
+/// several paragraphs.
+///
+/// ```rust
+/// use docx_rust::document::{Field, FieldKind};
+///
+/// let field = Field::new(r#" MERGEFIELD FirstName \* Upper "#);
+/// assert_eq!(field.kind, FieldKind::MergeField("FirstName".into()));
+/// assert_eq!(field.switch("*").and_then(|s| s.value.as_deref()), Some("Upper"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Field<'a> {
+    /// The instruction, with nested fields replaced by their result
+    pub instruction: String,
+    /// The type of the field and its main argument
+    pub kind: FieldKind,
+    /// The arguments in front of the first switch, including the main one
+    pub arguments: Vec<String>,
+    /// The switches in the order of the instruction
+    pub switches: Vec<FieldSwitch>,
+    /// The runs of the cached result, holding the content between the separate and the end
+    /// character of a complex field
+    pub result: Vec<Run<'a>>,
+}
+
+impl<'a> Field<'a> {
+    /// Parses a field instruction
+    ///
+    /// Arguments and switch values may be quoted. A switch takes the token following it as
+    /// its value, unless that is another switch.
+    pub fn new<S: Into<String>>(instruction: S) -> Self {
+        let instruction = instruction.into().trim().to_string();
+        let mut tokens = tokenize(&instruction).into_iter().peekable();
+        let name = match tokens.next() {
+            Some(Token::Word(name)) => name.to_ascii_uppercase(),
+            _ => String::new(),
+        };
+
+        let mut arguments = Vec::new();
+        let mut switches = Vec::new();
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Switch(name) => {
+                    let value = match tokens.next_if(|token| matches!(token, Token::Word(_))) {
+                        Some(Token::Word(value)) => Some(value),
+                        _ => None,
+                    };
+                    switches.push(FieldSwitch { name, value });
+                }
+                Token::Word(word) if switches.is_empty() => arguments.push(word),
+                Token::Word(_) => {}
+            }
+        }
+
+        let argument = arguments.first().cloned().unwrap_or_default();
+        let kind = match name.as_str() {
+            "PAGE" => FieldKind::Page,
+            "NUMPAGES" => FieldKind::NumPages,
+            "REF" => FieldKind::Ref(argument),
+            "PAGEREF" => FieldKind::PageRef(argument),
+            "HYPERLINK" => FieldKind::Hyperlink {
+                target: arguments.first().cloned(),
+                anchor: switch(&switches, "l").and_then(|s| s.value.clone()),
+            },
+            "TOC" => FieldKind::Toc,
+            "MERGEFIELD" => FieldKind::MergeField(argument),
+            "MERGEREC" => FieldKind::MergeRec,
+            "IF" => FieldKind::If,
+            "NEXT" => FieldKind::Next,
+            "NEXTIF" => FieldKind::NextIf,
+            "SEQ" => FieldKind::Seq(argument),
+            "DATE" => FieldKind::Date,
+            "TIME" => FieldKind::Time,
+            "DOCPROPERTY" => FieldKind::DocProperty(argument),
+            "AUTHOR" => FieldKind::Author,
+            "TITLE" => FieldKind::Title,
+            "SUBJECT" => FieldKind::Subject,
+            "KEYWORDS" => FieldKind::Keywords,
+            "COMMENTS" => FieldKind::Comments,
+            "LASTSAVEDBY" => FieldKind::LastSavedBy,
+            _ => FieldKind::Other(name),
+        };
+
+        Field {
+            instruction,
+            kind,
+            arguments,
+            switches,
+            result: Vec::new(),
+        }
+    }
+
+    /// Returns the first switch called `name`, given without the backslash, like `"*"` or `"h"`
+    pub fn switch(&self, name: &str) -> Option<&FieldSwitch> {
+        switch(&self.switches, name)
+    }
+
+    pub fn has_switch(&self, name: &str) -> bool {
+        self.switch(name).is_some()
+    }
+
+    /// Applies the format switches of the field to `value`
+    ///
+    /// A date-time picture given by `\@` is applied to values like `2024-03-05` or
+    /// `2024-03-05T14:30:00`, a numeric picture given by `\#` to numbers, and every `\*`
+    /// switch changing the case of the text, like `Upper` or `Caps`, to the outcome.
+    ///
+    /// ```rust
+    /// use docx_rust::document::Field;
+    ///
+    /// let field = Field::new(r#"MERGEFIELD Due \@ "d MMMM yyyy" \* Upper"#);
+    /// assert_eq!(field.format("2024-03-05"), "5 MARCH 2024");
+    /// ```
+    pub fn format(&self, value: &str) -> String {
+        let mut value = value.to_string();
+        if let Some(picture) = self.switch("@").and_then(|s| s.value.as_deref()) {
+            if let Some(date) = DateTime::parse(&value) {
+                value = date.format(picture);
+            }
+        }
+        if let Some(picture) = self.switch("#").and_then(|s| s.value.as_deref()) {
+            if let Ok(number) = value.trim().parse::<f64>() {
+                value = format_number(number, picture);
+            }
+        }
+        for switch in self.switches.iter().filter(|s| s.name == "*") {
+            if let Some(format) = &switch.value {
+                value = format_text(&value, format);
+            }
+        }
+        value
+    }
+
+    /// Returns the text of the cached result
+    pub fn result_text(&self) -> String {
+        self.result
+            .iter()
+            .flat_map(|run| run.content.iter())
+            .filter_map(|content| match content {
+                RunContent::Text(text) => Some(text.text.as_ref()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The type of a field, read from the first word of its instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    /// `PAGE`, the current page number
+    Page,
+    /// `NUMPAGES`, the number of pages
+    NumPages,
+    /// `REF`, the content of a bookmark
+    Ref(String),
+    /// `PAGEREF`, the page number of a bookmark
+    PageRef(String),
+    /// `HYPERLINK`, a link to a target or, with `\l`, to a bookmark
+    Hyperlink {
+        target: Option<String>,
+        anchor: Option<String>,
+    },
+    /// `TOC`, a table of contents
+    Toc,
+    /// `MERGEFIELD`, a column of the mail merge data source
+    MergeField(String),
+    /// `MERGEREC`, the number of the current mail merge record
+    MergeRec,
+    /// `IF`, one of two texts, chosen by comparing two values
+    If,
+    /// `NEXT`, moves mail merge on to the next record
+    Next,
+    /// `NEXTIF`, moves mail merge on to the next record if two values compare as given
+    NextIf,
+    /// `SEQ`, the number of an item in a sequence, like a figure caption
+    Seq(String),
+    /// `DATE`, the current date
+    Date,
+    /// `TIME`, the current time
+    Time,
+    /// `DOCPROPERTY`, a property of the document, like `Title` or `Company`
+    DocProperty(String),
+    /// `AUTHOR`, the author of the document
+    Author,
+    /// `TITLE`, the title of the document
+    Title,
+    /// `SUBJECT`, the subject of the document
+    Subject,
+    /// `KEYWORDS`, the keywords of the document
+    Keywords,
+    /// `COMMENTS`, the description of the document
+    Comments,
+    /// `LASTSAVEDBY`, the name of the last one who modified the document
+    LastSavedBy,
+    /// Any other field, with its name in upper case
+    Other(String),
+}
+
+/// A switch of a field instruction, like `\h` or `\@ "dd.MM.yyyy"`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FieldSwitch {
+    /// The name of the switch, without the backslash
+    pub name: String,
+    pub value: Option<String>,
+}
+
+fn switch<'s>(switches: &'s [FieldSwitch], name: &str) -> Option<&'s FieldSwitch> {
+    switches.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+enum Token {
+    Word(String),
+    Switch(String),
+}
+
+/// Splits an instruction at whitespace, keeping quoted text together
+fn tokenize(instruction: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = instruction.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if c == '"' {
+            let mut word = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' if matches!(chars.peek(), Some('"' | '\\')) => word.extend(chars.next()),
+                    c => word.push(c),
+                }
+            }
+            tokens.push(Token::Word(word));
+        } else {
+            let mut word = String::from(c);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                word.push(c);
+            }
+            match word.strip_prefix('\\') {
+                Some(name) => tokens.push(Token::Switch(name.to_string())),
+                None => tokens.push(Token::Word(word)),
+            }
+        }
+    }
+    tokens
+}
+
+/// The position of a run content item: the number of the run in the walk and the index of
+/// the item in the run
+type Position = (usize, usize);
+
+enum Segment {
+    Text(String),
+    Field(usize),
+}
+
+enum Location {
+    Complex {
+        begin: Position,
+        separate: Option<Position>,
+        end: Position,
+        /// The characters and the instruction of the field, with the fields nested in it
+        code: Vec<Position>,
+        /// The items between the separate and the end character
+        result: Vec<Position>,
+        first_text: Option<Position>,
+    },
+    Simple {
+        index: usize,
+        runs: Range<usize>,
+    },
+}
+
+struct ScannedField<'a> {
+    start: usize,
+    instruction: Vec<Segment>,
+    result: Vec<Run<'a>>,
+    location: Location,
+}
+
+impl<'a> ScannedField<'a> {
+    /// Parses the field, using `results` for the fields nested in the instruction
+    ///
+    /// Nested results are quoted, unless they are part of a quoted argument already, so
+    /// empty results and results with spaces stay a single argument.
+    fn field(&self, results: &[String]) -> Field<'a> {
+        let mut instruction = String::new();
+        let mut quoted = false;
+        for segment in self.instruction.iter() {
+            match segment {
+                Segment::Text(text) => {
+                    let mut escaped = false;
+                    for c in text.chars() {
+                        match c {
+                            '"' if !escaped => quoted = !quoted,
+                            '\\' => {
+                                escaped = !escaped;
+                                continue;
+                            }
+                            _ => {}
+                        }
+                        escaped = false;
+                    }
+                    instruction.push_str(text);
+                }
+                Segment::Field(id) => {
+                    let result = results[*id].replace('\\', "\\\\").replace('"', "\\\"");
+                    if quoted {
+                        instruction.push_str(&result);
+                    } else {
+                        instruction.push('"');
+                        instruction.push_str(&result);
+                        instruction.push('"');
+                    }
+                }
+            }
+        }
+        Field {
+            result: self.result.clone(),
+            ..Field::new(instruction)
+        }
+    }
+
+    fn span(&self) -> (Position, Position) {
+        match &self.location {
+            Location::Complex { begin, end, .. } => (*begin, *end),
+            Location::Simple { runs, .. } => ((runs.start, 0), (runs.end, 0)),
+        }
+    }
+}
+
+struct OpenField<'a> {
+    start: usize,
+    begin: Position,
+    separate: Option<Position>,
+    instruction: Vec<Segment>,
+    code: Vec<Position>,
+    result: Vec<Run<'a>>,
+    positions: Vec<Position>,
+    first_text: Option<Position>,
+}
+
+impl<'a> OpenField<'a> {
+    fn push_result(&mut self, run: &Run<'a>, content: &RunContent<'a>, position: Position) {
+        if self.positions.last().map(|(run, _)| *run) != Some(position.0) {
+            self.result.push(Run {
+                property: run.property.clone(),
+                ..Default::default()
+            });
+        }
+        if let Some(last) = self.result.last_mut() {
+            last.content.push(content.clone());
+        }
+        if self.first_text.is_none() && matches!(content, RunContent::Text(_)) {
+            self.first_text = Some(position);
+        }
+        self.positions.push(position);
+    }
+
+    fn close(mut self, end: Position) -> ScannedField<'a> {
+        self.code.push(end);
+        ScannedField {
+            start: self.start,
+            instruction: self.instruction,
+            result: self.result,
+            location: Location::Complex {
+                begin: self.begin,
+                separate: self.separate,
+                end,
+                code: self.code,
+                result: self.positions,
+                first_text: self.first_text,
+            },
+        }
+    }
+}
+
+/// Collects the fields of the runs it visits
+///
+/// Fields are kept in the order they end in, so nested fields come before the field they
+/// are nested in.
+#[derive(Default)]
+pub(crate) struct FieldScanner<'a> {
+    run: usize,
+    simple: usize,
+    start: usize,
+    open: Vec<OpenField<'a>>,
+    fields: Vec<ScannedField<'a>>,
+}
+
+impl<'a> FieldScanner<'a> {
+    /// Returns the fields in the order they start in
+    pub(crate) fn into_fields(self) -> Vec<Field<'a>> {
+        let mut results = Vec::with_capacity(self.fields.len());
+        let mut fields = Vec::with_capacity(self.fields.len());
+        for scanned in self.fields.iter() {
+            let field = scanned.field(&results);
+            results.push(field.result_text());
+            fields.push((scanned.start, field));
+        }
+        fields.sort_by_key(|(start, _)| *start);
+        fields.into_iter().map(|(_, field)| field).collect()
+    }
+
+    /// Asks `replace` for the new result of every field and returns a visitor writing them
+    ///
+    /// Nested fields are asked first, so the fields they are nested in see the new results
+    /// in their instruction.
+    pub(crate) fn into_writer<F>(self, mut replace: F) -> FieldWriter<'a>
+    where
+        F: FnMut(&Field<'a>) -> Option<String>,
+    {
+        let mut results = Vec::with_capacity(self.fields.len());
+        let mut replaced = Vec::new();
+        for (id, scanned) in self.fields.iter().enumerate() {
+            let field = scanned.field(&results);
+            match replace(&field) {
+                Some(value) => {
+                    results.push(value.clone());
+                    replaced.push((id, value));
+                }
+                None => results.push(field.result_text()),
+            }
+        }
+
+        // the result of a replaced field is removed as a whole, with the fields inside it
+        let removed: Vec<_> = replaced
+            .iter()
+            .filter_map(|(id, _)| match &self.fields[*id].location {
+                Location::Complex {
+                    separate: Some(separate),
+                    end,
+                    ..
+                } => Some((*separate, *end)),
+                _ => None,
+            })
+            .collect();
+
+        let mut writer = FieldWriter::default();
+        for (id, value) in replaced {
+            let field = &self.fields[id];
+            let (begin, end) = field.span();
+            if !removed.iter().any(|(from, to)| *from < begin && end < *to) {
+                writer.replace(&field.location, value);
+            }
+        }
+        writer
+    }
+
+    /// Returns a visitor removing the code of the complex fields selected by `predicate`,
+    /// and unwrapping the simple fields it selects, so only their results are left
+    pub(crate) fn into_unlinker<P>(self, mut predicate: P) -> FieldUnlinker<'a, P>
+    where
+        P: FnMut(&Field<'a>) -> bool,
+    {
+        let mut results = Vec::with_capacity(self.fields.len());
+        let mut writer = FieldWriter::default();
+        for scanned in self.fields.iter() {
+            let field = scanned.field(&results);
+            if let Location::Complex { code, .. } = &scanned.location {
+                if predicate(&field) {
+                    writer.replaced += 1;
+                    for position in code {
+                        writer.edit(*position, Edit::Remove);
+                    }
+                }
+            }
+            results.push(field.result_text());
+        }
+        FieldUnlinker { writer, predicate }
+    }
+
+    fn start(&mut self) -> usize {
+        self.start += 1;
+        self.start
+    }
+
+    /// Adds a content item to the code or the result of every open field
+    fn push_item(&mut self, run: &Run<'a>, content: &RunContent<'a>, position: Position) {
+        for field in self.open.iter_mut() {
+            if field.separate.is_some() {
+                field.push_result(run, content, position);
+            } else {
+                field.code.push(position);
+            }
+        }
+    }
+
+    fn push_instruction(&mut self, text: &str) {
+        match self.open.last_mut() {
+            Some(field) if field.separate.is_none() => match field.instruction.last_mut() {
+                Some(Segment::Text(instruction)) => instruction.push_str(text),
+                _ => field.instruction.push(Segment::Text(text.to_string())),
+            },
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, field: ScannedField<'a>) {
+        let id = self.fields.len();
+        self.fields.push(field);
+        match self.open.last_mut() {
+            Some(parent) if parent.separate.is_none() => {
+                parent.instruction.push(Segment::Field(id));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> Visit<'a> for FieldScanner<'a> {
+    fn visit_simple_field(&mut self, field: &SimpleField<'a>) {
+        let scanned = ScannedField {
+            start: self.start(),
+            instruction: vec![Segment::Text(field.instr.to_string())],
+            result: field.content.clone(),
+            location: Location::Simple {
+                index: self.simple,
+                runs: self.run..self.run + field.content.len(),
+            },
+        };
+        self.simple += 1;
+        self.close(scanned);
+    }
+
+    fn visit_run(&mut self, run: &Run<'a>) {
+        for (index, content) in run.content.iter().enumerate() {
+            let position = (self.run, index);
+            match content {
+                RunContent::FieldChar(FieldChar {
+                    ty: Some(CharType::Begin),
+                }) => {
+                    self.push_item(run, content, position);
+                    let start = self.start();
+                    self.open.push(OpenField {
+                        start,
+                        begin: position,
+                        separate: None,
+                        instruction: Vec::new(),
+                        code: vec![position],
+                        result: Vec::new(),
+                        positions: Vec::new(),
+                        first_text: None,
+                    });
+                }
+                RunContent::FieldChar(FieldChar {
+                    ty: Some(CharType::Separate),
+                }) => {
+                    self.push_item(run, content, position);
+                    if let Some(field) = self.open.last_mut() {
+                        if field.separate.is_none() {
+                            field.separate = Some(position);
+                        }
+                    }
+                }
+                RunContent::FieldChar(FieldChar {
+                    ty: Some(CharType::End),
+                }) => {
+                    if let Some(field) = self.open.pop() {
+                        self.close(field.close(position));
+                    }
+                    self.push_item(run, content, position);
+                }
+                RunContent::InstrText(InstrText { text, .. })
+                | RunContent::DelInstrText(DelInstrText { text, .. }) => {
+                    self.push_item(run, content, position);
+                    self.push_instruction(text);
+                }
+                _ => self.push_item(run, content, position),
+            }
+        }
+        self.run += 1;
+    }
+}
+
+enum Edit<'a> {
+    Remove,
+    SetText(String),
+    InsertBefore(Vec<RunContent<'a>>),
+    InsertAfter(Vec<RunContent<'a>>),
+}
+
+/// Writes new field results into the runs it visits
+///
+/// It has to visit the same runs in the same order as the [`FieldScanner`] it comes from.
+#[derive(Default)]
+pub(crate) struct FieldWriter<'a> {
+    run: usize,
+    simple: usize,
+    /// The number of runs to pass over, which replace the result of a simple field
+    skip: usize,
+    edits: HashMap<usize, BTreeMap<usize, Edit<'a>>>,
+    simple_results: HashMap<usize, (usize, String)>,
+    /// The number of fields whose result is replaced
+    pub(crate) replaced: usize,
+}
+
+impl<'a> FieldWriter<'a> {
+    fn replace(&mut self, location: &Location, value: String) {
+        self.replaced += 1;
+        match location {
+            Location::Complex {
+                separate: Some(separate),
+                result,
+                first_text,
+                ..
+            } => {
+                for position in result {
+                    let edit = if Some(*position) == *first_text {
+                        Edit::SetText(value.clone())
+                    } else {
+                        Edit::Remove
+                    };
+                    self.edit(*position, edit);
+                }
+                if first_text.is_none() {
+                    self.edit(
+                        *separate,
+                        Edit::InsertAfter(vec![result_text(value).into()]),
+                    );
+                }
+            }
+            Location::Complex {
+                separate: None,
+                end,
+                ..
+            } => {
+                let separate = FieldChar::from(CharType::Separate);
+                let content = vec![separate.into(), result_text(value).into()];
+                self.edit(*end, Edit::InsertBefore(content));
+            }
+            Location::Simple { index, runs } => {
+                self.simple_results.insert(*index, (runs.len(), value));
+            }
+        }
+    }
+
+    fn edit(&mut self, (run, index): Position, edit: Edit<'a>) {
+        self.edits.entry(run).or_default().insert(index, edit);
+    }
+}
+
+impl<'a> VisitMut<'a> for FieldWriter<'a> {
+    fn visit_simple_field(&mut self, field: &mut SimpleField<'a>) {
+        if let Some((runs, value)) = self.simple_results.remove(&self.simple) {
+            let run = Run {
+                property: field.content.first().and_then(|run| run.property.clone()),
+                ..Default::default()
+            };
+            field.content = vec![run.push_text(result_text(value))];
+            self.run += runs;
+            self.skip = field.content.len();
+        }
+        self.simple += 1;
+    }
+
+    fn visit_run(&mut self, run: &mut Run<'a>) {
+        if self.skip > 0 {
+            self.skip -= 1;
+            return;
+        }
+        if let Some(edits) = self.edits.remove(&self.run) {
+            for (index, edit) in edits.into_iter().rev() {
+                match edit {
+                    Edit::Remove => {
+                        run.content.remove(index);
+                    }
+                    Edit::SetText(value) => run.content[index] = result_text(value).into(),
+                    Edit::InsertBefore(content) => {
+                        run.content.splice(index..index, content);
+                    }
+                    Edit::InsertAfter(content) => {
+                        run.content.splice(index + 1..index + 1, content);
+                    }
+                }
+            }
+        }
+        self.run += 1;
+    }
+}
+
+/// Replaces fields by their results in the runs it visits
+///
+/// It has to visit the same runs in the same order as the [`FieldScanner`] it comes from.
+pub(crate) struct FieldUnlinker<'a, P> {
+    writer: FieldWriter<'a>,
+    predicate: P,
+}
+
+impl<'a, P: FnMut(&Field<'a>) -> bool> FieldUnlinker<'a, P> {
+    /// Returns the number of unlinked fields
+    pub(crate) fn unlinked(&self) -> usize {
+        self.writer.replaced
+    }
+
+    fn unwrap_simple_fields(&mut self, content: &mut Vec<ParagraphContent<'a>>) {
+        let mut index = 0;
+        while index < content.len() {
+            match &mut content[index] {
+                ParagraphContent::SimpleField(field) => {
+                    let parsed = Field {
+                        result: field.content.clone(),
+                        ..Field::new(field.instr.as_ref())
+                    };
+                    if (self.predicate)(&parsed) {
+                        let runs = std::mem::take(&mut field.content);
+                        let count = runs.len();
+                        content.splice(index..=index, runs.into_iter().map(Into::into));
+                        self.writer.replaced += 1;
+                        index += count;
+                        continue;
+                    }
+                }
+                ParagraphContent::Insertion(ins) => self.unwrap_simple_fields(&mut ins.content),
+                ParagraphContent::Deletion(del) => self.unwrap_simple_fields(&mut del.content),
+                ParagraphContent::MoveFrom(from) => self.unwrap_simple_fields(&mut from.content),
+                ParagraphContent::MoveTo(to) => self.unwrap_simple_fields(&mut to.content),
+                _ => {}
+            }
+            index += 1;
+        }
+    }
+}
+
+impl<'a, P: FnMut(&Field<'a>) -> bool> VisitMut<'a> for FieldUnlinker<'a, P> {
+    fn visit_paragraph(&mut self, paragraph: &mut Paragraph<'a>) {
+        // the runs of unwrapped fields are walked in the same order as before
+        self.unwrap_simple_fields(&mut paragraph.content);
+    }
+
+    fn visit_run(&mut self, run: &mut Run<'a>) {
+        self.writer.visit_run(run);
+    }
+}
+
+fn result_text<'a>(value: String) -> Text<'a> {
+    if value.starts_with(char::is_whitespace) || value.ends_with(char::is_whitespace) {
+        Text::from((value, TextSpace::Preserve))
+    } else {
+        Text::from(value)
+    }
+}
+
+__xml_test_suites!(
+    SimpleField,
+    SimpleField::default().instr("PAGE"),
+    r#"<w:fldSimple w:instr="PAGE"/>"#,
+    SimpleField::default()
+        .instr(" DATE ")
+        .dirty(true)
+        .push(Run::default().push_text("today")),
+    r#"<w:fldSimple w:instr=" DATE " w:dirty="true"><w:r><w:t>today</w:t></w:r></w:fldSimple>"#,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_instruction() {
+        let field = Field::new(r#" TOC \o "1-3" \h \z \u "#);
+        assert_eq!(field.kind, FieldKind::Toc);
+        assert_eq!(field.switch("o").unwrap().value.as_deref(), Some("1-3"));
+        assert!(field.has_switch("h") && field.has_switch("z") && field.has_switch("u"));
+        assert_eq!(field.switch("h").unwrap().value, None);
+
+        let field = Field::new(r#"HYPERLINK \l "_Toc100000001" \o "Tip""#);
+        assert_eq!(
+            field.kind,
+            FieldKind::Hyperlink {
+                target: None,
+                anchor: Some("_Toc100000001".into())
+            }
+        );
+
+        let field = Field::new(r#"hyperlink "https://example.com/a b""#);
+        assert_eq!(field.arguments, ["https://example.com/a b"]);
+
+        let field = Field::new(r#"DATE \@ "dd.MM.yyyy" \* MERGEFORMAT"#);
+        assert_eq!(field.kind, FieldKind::Date);
+        assert_eq!(
+            field.switch("@").unwrap().value.as_deref(),
+            Some("dd.MM.yyyy")
+        );
+        assert_eq!(
+            field.switch("*").unwrap().value.as_deref(),
+            Some("MERGEFORMAT")
+        );
+
+        let field = Field::new(r#"IF "a \"b\"" = "x" "yes" "no""#);
+        assert_eq!(field.kind, FieldKind::If);
+        assert_eq!(field.arguments, [r#"a "b""#, "=", "x", "yes", "no"]);
+
+        assert_eq!(
+            Field::new("SEQ Figure").kind,
+            FieldKind::Seq("Figure".into())
+        );
+        assert_eq!(
+            Field::new(r#"DOCPROPERTY "Company" \* MERGEFORMAT"#).kind,
+            FieldKind::DocProperty("Company".into())
+        );
+        assert_eq!(Field::new("author").kind, FieldKind::Author);
+        assert_eq!(Field::new("").kind, FieldKind::Other(String::new()));
+    }
+}
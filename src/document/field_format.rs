@@ -0,0 +1,412 @@
+//! Formatting of field results by the `\*`, `\@` and `\#` switches
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// A date and time as used by date-time pictures
+///
+/// It is displayed as `2024-03-05T14:30:00`, which is also one of the forms it parses.
+///
+/// ```rust
+/// use docx_rust::document::DateTime;
+///
+/// let date = DateTime::parse("2024-03-05 14:30").unwrap();
+/// assert_eq!(date.format("dddd, d MMMM yyyy h:mm am/pm"), "Tuesday, 5 March 2024 2:30 pm");
+/// assert_eq!(date.to_string(), "2024-03-05T14:30:00");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: i32,
+    /// The month, from 1 to 12
+    pub month: u32,
+    /// The day of the month, from 1 to 31
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl DateTime {
+    /// Parses `YYYY-MM-DD`, optionally followed by `THH:MM` or `THH:MM:SS`, where `T` may
+    /// also be a space
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (date, time) = match value.find(['T', ' ']) {
+            Some(index) => (&value[..index], Some(&value[index + 1..])),
+            None => (value, None),
+        };
+        let mut date = date.splitn(3, '-');
+        let year = date.next()?.parse().ok()?;
+        let month = date.next()?.parse().ok().filter(|m| (1..=12).contains(m))?;
+        let day = date.next()?.parse().ok().filter(|d| (1..=31).contains(d))?;
+
+        let (mut hour, mut minute, mut second) = (0, 0, 0);
+        if let Some(time) = time {
+            // drop fractions of a second and a time zone
+            let time = time.split(['.', 'Z', '+']).next().unwrap_or_default();
+            let mut time = time.splitn(3, ':');
+            hour = time.next()?.parse().ok().filter(|h| *h < 24)?;
+            minute = time.next()?.parse().ok().filter(|m| *m < 60)?;
+            second = match time.next() {
+                Some(second) => second.parse().ok().filter(|s| *s < 60)?,
+                None => 0,
+            };
+        }
+
+        Some(DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Returns the current date and time in UTC
+    pub fn now_utc() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs() as i64);
+        let (days, time) = (seconds.div_euclid(86400), seconds.rem_euclid(86400));
+
+        // civil date of the days since 1970-01-01, counting years from March on
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let doe = days - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        DateTime {
+            year: year as i32,
+            month: month as u32,
+            day: day as u32,
+            hour: (time / 3600) as u32,
+            minute: (time % 3600 / 60) as u32,
+            second: (time % 60) as u32,
+        }
+    }
+
+    /// Returns the English name of the month, if it is between 1 and 12
+    fn month_name(&self) -> Option<&'static str> {
+        let index = (self.month as usize).checked_sub(1)?;
+        MONTHS.get(index).copied()
+    }
+
+    /// Returns the day of the week, starting with Sunday as 0
+    fn weekday(&self) -> usize {
+        // days since 1970-01-01, which was a Thursday
+        let (y, m) = if self.month <= 2 {
+            (self.year as i64 - 1, self.month as i64 + 9)
+        } else {
+            (self.year as i64, self.month as i64 - 3)
+        };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * m + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146097 + doe - 719468;
+        (days + 4).rem_euclid(7) as usize
+    }
+
+    /// Formats the date with a date-time picture, like `dd.MM.yyyy` or `h:mm am/pm`
+    ///
+    /// Text in single quotes is copied as it is, so are characters with no meaning.
+    pub fn format(&self, picture: &str) -> String {
+        let mut output = String::new();
+        let chars: Vec<char> = picture.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '\'' {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|c| *c == '\'')
+                    .map_or(chars.len(), |end| i + 1 + end);
+                output.extend(&chars[i + 1..end]);
+                i = end + 1;
+                continue;
+            }
+            let rest: String = chars[i..].iter().take(5).collect();
+            if rest.eq_ignore_ascii_case("am/pm") {
+                let pm = self.hour >= 12;
+                let text = match (pm, rest.starts_with('a')) {
+                    (false, true) => "am",
+                    (true, true) => "pm",
+                    (false, false) => "AM",
+                    (true, false) => "PM",
+                };
+                output.push_str(text);
+                i += 5;
+                continue;
+            }
+
+            let count = chars[i..].iter().take_while(|n| **n == c).count();
+            let hour12 = match self.hour % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            match c {
+                'd' | 'D' => match count {
+                    1 => output.push_str(&self.day.to_string()),
+                    2 => output.push_str(&format!("{:02}", self.day)),
+                    3 => output.push_str(&WEEKDAYS[self.weekday()][..3]),
+                    _ => output.push_str(WEEKDAYS[self.weekday()]),
+                },
+                'M' => match (count, self.month_name()) {
+                    (3, Some(name)) => output.push_str(&name[..3]),
+                    (4.., Some(name)) => output.push_str(name),
+                    (1, _) => output.push_str(&self.month.to_string()),
+                    // months out of range, like the one of `DateTime::default()`, are numbers
+                    _ => output.push_str(&format!("{:02}", self.month)),
+                },
+                'y' | 'Y' => match count {
+                    1 | 2 => output.push_str(&format!("{:02}", self.year.rem_euclid(100))),
+                    _ => output.push_str(&format!("{:04}", self.year)),
+                },
+                'h' => output.push_str(&pad(hour12, count)),
+                'H' => output.push_str(&pad(self.hour, count)),
+                'm' => output.push_str(&pad(self.minute, count)),
+                's' | 'S' => output.push_str(&pad(self.second, count)),
+                _ => {
+                    output.extend(&chars[i..i + count]);
+                }
+            }
+            i += count;
+        }
+        output
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+fn pad(value: u32, count: usize) -> String {
+    if count > 1 {
+        format!("{:02}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats a number with a numeric picture, like `0.00` or `#,##0 €`
+///
+/// The digits are taken from the `0` and `#` placeholders: their count after the decimal
+/// point gives the number of decimals, and a comma among them groups thousands. The text in
+/// front of and behind the placeholders is kept.
+pub(crate) fn format_number(number: f64, picture: &str) -> String {
+    let Some(first) = picture.find(['0', '#']) else {
+        return picture.to_string();
+    };
+    let last = picture.rfind(['0', '#']).unwrap_or(first);
+    let (prefix, digits, suffix) = (
+        &picture[..first],
+        &picture[first..=last],
+        &picture[last + 1..],
+    );
+
+    let (integer_part, decimal_part) = digits.split_once('.').unwrap_or((digits, ""));
+    let decimals = decimal_part
+        .chars()
+        .filter(|c| matches!(c, '0' | '#'))
+        .count();
+    let min_digits = integer_part.chars().filter(|c| *c == '0').count();
+    let grouped = integer_part.contains(',');
+
+    let formatted = format!("{:.*}", decimals, number.abs());
+    let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+    let mut integer = integer.trim_start_matches('0').to_string();
+    while integer.len() < min_digits {
+        integer.insert(0, '0');
+    }
+    if grouped {
+        let mut with_groups = String::new();
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                with_groups.push(',');
+            }
+            with_groups.push(c);
+        }
+        integer = with_groups;
+    }
+
+    let mut output = String::new();
+    if number < 0.0 && formatted.chars().any(|c| matches!(c, '1'..='9')) {
+        output.push('-');
+    }
+    output.push_str(prefix);
+    output.push_str(&integer);
+    if !fraction.is_empty() {
+        output.push('.');
+        output.push_str(fraction);
+    }
+    output.push_str(suffix);
+    output
+}
+
+/// Applies a general format switch, like `\* Upper`
+///
+/// `Roman` and `Alphabetic` write numbers as roman numerals or letters, in upper case if the
+/// format starts with an upper case letter. Formats which only concern the formatting of the
+/// result, like `MERGEFORMAT`, and unknown formats leave the text unchanged.
+pub(crate) fn format_text(text: &str, format: &str) -> String {
+    let upper = format.starts_with(|c: char| c.is_ascii_uppercase());
+    let number = text.trim().parse::<u32>().ok().filter(|n| *n > 0);
+    match format.to_ascii_lowercase().as_str() {
+        // Word stops at 32767
+        "roman" if number.is_some_and(|n| n <= 32767) => {
+            let roman = to_roman(number.unwrap_or_default());
+            if upper {
+                roman
+            } else {
+                roman.to_lowercase()
+            }
+        }
+        // Word stops at 780, which is 30 times "Z"
+        "alphabetic" if number.is_some_and(|n| n <= 780) => {
+            // 27 is "AA", 28 is "BB", like Word does
+            let number = number.unwrap_or_default() - 1;
+            let letter = (b'a' + (number % 26) as u8) as char;
+            let letter = if upper {
+                letter.to_ascii_uppercase()
+            } else {
+                letter
+            };
+            std::iter::repeat_n(letter, number as usize / 26 + 1).collect()
+        }
+        "upper" => text.to_uppercase(),
+        "lower" => text.to_lowercase(),
+        "firstcap" => {
+            let mut chars = text.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+        "caps" => text
+            .split(' ')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first
+                        .to_uppercase()
+                        .chain(chars.flat_map(char::to_lowercase))
+                        .collect(),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" "),
+        _ => text.to_string(),
+    }
+}
+
+fn to_roman(mut number: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+    roman
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_pictures() {
+        let date = DateTime::parse("2024-03-05T14:07:09").unwrap();
+        assert_eq!(date.format("dd.MM.yyyy"), "05.03.2024");
+        assert_eq!(date.format("d MMMM yy"), "5 March 24");
+        assert_eq!(date.format("dddd, MMM d"), "Tuesday, Mar 5");
+        assert_eq!(date.format("h:mm AM/PM"), "2:07 PM");
+        assert_eq!(date.format("HH:mm:ss 'Uhr'"), "14:07:09 Uhr");
+        assert_eq!(DateTime::parse("2000-01-01").unwrap().format("ddd"), "Sat");
+        assert_eq!(DateTime::parse("2024-13-01"), None);
+        assert_eq!(DateTime::parse("Alice"), None);
+        assert_eq!(DateTime::default().format("d MMMM yyyy"), "0 00 0000");
+        assert_eq!(DateTime::parse(&date.to_string()), Some(date));
+
+        let now = DateTime::now_utc();
+        assert!(now.year >= 2024);
+        assert_eq!(DateTime::parse(&now.to_string()), Some(now));
+    }
+
+    #[test]
+    fn number_pictures() {
+        assert_eq!(format_number(1234.5, "0.00"), "1234.50");
+        assert_eq!(format_number(1234567.891, "#,##0.00"), "1,234,567.89");
+        assert_eq!(format_number(-3.0, "$#,##0"), "-$3");
+        assert_eq!(format_number(25.0, "0%"), "25%");
+        assert_eq!(format_number(7.0, "000"), "007");
+    }
+
+    #[test]
+    fn text_formats() {
+        assert_eq!(format_text("alice smith", "Upper"), "ALICE SMITH");
+        assert_eq!(format_text("alice SMITH", "Caps"), "Alice Smith");
+        assert_eq!(format_text("alice smith", "FirstCap"), "Alice smith");
+        assert_eq!(format_text("Alice", "MERGEFORMAT"), "Alice");
+        assert_eq!(format_text("14", "ROMAN"), "XIV");
+        assert_eq!(format_text("1994", "roman"), "mcmxciv");
+        assert_eq!(format_text("3", "ALPHABETIC"), "C");
+        assert_eq!(format_text("28", "alphabetic"), "bb");
+        assert_eq!(format_text("780", "ALPHABETIC"), "Z".repeat(30));
+        assert_eq!(format_text("4000000000", "alphabetic"), "4000000000");
+        assert_eq!(format_text("x", "Roman"), "x");
+    }
+}
@@ -0,0 +1,378 @@
+//! Evaluation of fields whose results are known without laying out the pages
+
+use std::collections::HashMap;
+
+use crate::__setter;
+use crate::app::App;
+use crate::core::Core;
+use crate::custom::{CustomProperties, CustomValue};
+use crate::document::{
+    Break, BreakType, DateTime, Field, FieldKind, HyperlinkContent, Paragraph, ParagraphContent,
+    Run, RunContent, Visit,
+};
+use crate::formatting::SectionType;
+
+/// The values fields are updated with by [`Docx::update_fields`]
+///
+/// [`Docx::update_fields`]: crate::Docx::update_fields
+///
+/// ```rust
+/// use docx_rust::document::{DateTime, FieldContext};
+///
+/// let context = FieldContext::default()
+///     .now(DateTime::parse("2024-03-05T14:30").unwrap())
+///     .pages(12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FieldContext {
+    /// The date and time shown by `DATE` and `TIME` fields, by default the current time in UTC
+    pub now: DateTime,
+    /// The number of pages shown by `NUMPAGES` fields
+    ///
+    /// If it is `None`, the number is estimated from the breaks in the body.
+    pub pages: Option<usize>,
+}
+
+impl Default for FieldContext {
+    fn default() -> Self {
+        FieldContext {
+            now: DateTime::now_utc(),
+            pages: None,
+        }
+    }
+}
+
+impl FieldContext {
+    __setter!(now: DateTime);
+
+    pub fn pages(mut self, pages: usize) -> Self {
+        self.pages = Some(pages);
+        self
+    }
+}
+
+/// Returns the properties of the document, as named by `DOCPROPERTY` in lower case
+///
+/// Custom properties come first, so the built-in ones replace them if they share a name. Yes
+/// or no values are shown as `Y` or `N`, like Word does, and dates so that `\@` pictures
+/// apply to them.
+pub(crate) fn document_properties(
+    core: Option<&Core>,
+    app: Option<&App>,
+    custom: Option<&CustomProperties>,
+) -> HashMap<String, String> {
+    macro_rules! core_properties {
+        ($core:expr) => {
+            [
+                ("title", &$core.title),
+                ("subject", &$core.subject),
+                ("author", &$core.creator),
+                ("keywords", &$core.keywords),
+                ("comments", &$core.description),
+                ("lastsavedby", &$core.last_modified_by),
+                ("revisionnumber", &$core.revision),
+            ]
+        };
+    }
+    macro_rules! app_properties {
+        ($app:expr) => {
+            [
+                ("template", &$app.template),
+                ("totaleditingtime", &$app.total_time),
+                ("pages", &$app.pages),
+                ("words", &$app.words),
+                ("characters", &$app.characters),
+                ("nameofapplication", &$app.application),
+                ("security", &$app.doc_security),
+                ("lines", &$app.lines),
+                ("paragraphs", &$app.paragraphs),
+                ("company", &$app.company),
+                ("characterswithspaces", &$app.characters_with_spaces),
+            ]
+        };
+    }
+
+    let core = core.map(|core| match core {
+        Core::CoreNamespace(core) => core_properties!(core),
+        Core::CoreNoNamespace(core) => core_properties!(core),
+    });
+    let app = app.map(|app| match app {
+        App::AppNoApNamespace(app) => app_properties!(app),
+        App::AppWithApNamespace(app) => app_properties!(app),
+    });
+
+    let mut properties = HashMap::new();
+    for property in custom.iter().flat_map(|custom| custom.properties.iter()) {
+        let (Some(name), Some(value)) = (&property.name, &property.value) else {
+            continue;
+        };
+        let value = match value {
+            CustomValue::Text(text) => text.value.to_string(),
+            CustomValue::Integer(integer) => integer.value.to_string(),
+            CustomValue::Float(float) => float.value.to_string(),
+            CustomValue::Bool(boolean) => if boolean.value { "Y" } else { "N" }.to_string(),
+            CustomValue::FileTime(_) => match value.as_date_time() {
+                Some(date) => date.to_string(),
+                None => continue,
+            },
+            CustomValue::Other(other) => match other.text() {
+                Some(text) => text.to_string(),
+                None => continue,
+            },
+        };
+        properties.insert(name.to_lowercase(), value);
+    }
+
+    let core = core.into_iter().flatten();
+    let app = app.into_iter().flatten();
+    for (name, value) in core.chain(app) {
+        if let Some(value) = value {
+            properties.insert(name.to_string(), value.to_string());
+        }
+    }
+    properties
+}
+
+/// Collects the text and the page of the bookmarks of the paragraphs it visits
+///
+/// Pages are only counted at page breaks, at paragraphs starting on a new page and at
+/// section breaks, as telling where text flows onto the next page takes a layout engine.
+/// Runs are read along with their paragraph, as bookmarks start and end between them.
+pub(crate) struct BookmarkTexts {
+    page: usize,
+    paragraphs: usize,
+    /// The ids and names of the bookmarks started but not ended yet
+    open: Vec<(Option<String>, String)>,
+    texts: HashMap<String, String>,
+    pages: HashMap<String, usize>,
+}
+
+impl Default for BookmarkTexts {
+    fn default() -> Self {
+        BookmarkTexts {
+            page: 1,
+            paragraphs: 0,
+            open: Vec::new(),
+            texts: HashMap::new(),
+            pages: HashMap::new(),
+        }
+    }
+}
+
+impl BookmarkTexts {
+    fn push_text(&mut self, text: &str) {
+        for (_, name) in self.open.iter() {
+            if let Some(bookmark) = self.texts.get_mut(name) {
+                bookmark.push_str(text);
+            }
+        }
+    }
+
+    fn push_inline(&mut self, content: &[ParagraphContent]) {
+        for item in content {
+            match item {
+                ParagraphContent::BookmarkStart(start) => {
+                    if let Some(name) = &start.name {
+                        self.texts.entry(name.to_string()).or_default();
+                        self.pages.entry(name.to_string()).or_insert(self.page);
+                        let id = start.id.as_ref().map(|id| id.to_string());
+                        self.open.push((id, name.to_string()));
+                    }
+                }
+                ParagraphContent::BookmarkEnd(end) => {
+                    let id = end.id.as_deref();
+                    self.open.retain(|(open, _)| open.as_deref() != id);
+                }
+                ParagraphContent::Run(run) => self.push_run(run),
+                ParagraphContent::Link(link) => {
+                    for content in link.content.iter() {
+                        match content {
+                            HyperlinkContent::Run(run) => self.push_run(run),
+                            HyperlinkContent::Insertion(ins) => self.push_inline(&ins.content),
+                            HyperlinkContent::Deletion(_) => {}
+                        }
+                    }
+                }
+                ParagraphContent::SimpleField(field) => {
+                    for run in field.content.iter() {
+                        self.push_run(run);
+                    }
+                }
+                ParagraphContent::Insertion(ins) => self.push_inline(&ins.content),
+                ParagraphContent::MoveTo(to) => self.push_inline(&to.content),
+                _ => {}
+            }
+        }
+    }
+
+    fn push_run(&mut self, run: &Run) {
+        for content in run.content.iter() {
+            match content {
+                RunContent::Text(text) => self.push_text(&text.text),
+                RunContent::Tab(_) => self.push_text("\t"),
+                RunContent::Break(Break {
+                    ty: Some(BreakType::Page),
+                }) => self.page += 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for BookmarkTexts {
+    fn visit_paragraph(&mut self, paragraph: &Paragraph<'a>) {
+        let property = paragraph.property.as_ref();
+        let break_before = property
+            .and_then(|property| property.page_break_before.as_ref())
+            .is_some_and(|page_break| page_break.value != Some(false));
+        if break_before && self.paragraphs > 0 {
+            self.page += 1;
+        }
+        self.paragraphs += 1;
+
+        self.push_inline(&paragraph.content);
+        // bookmarks spanning paragraphs get a space for each paragraph mark
+        self.push_text(" ");
+
+        let section_break = property
+            .and_then(|property| property.section_property.as_ref())
+            .is_some_and(|sect_pr| {
+                let ty = sect_pr.ty.as_ref().and_then(|ty| ty.ty.as_ref());
+                !matches!(ty, Some(SectionType::Continuous))
+            });
+        if section_break {
+            self.page += 1;
+        }
+    }
+}
+
+/// Computes the results of the fields [`Docx::update_fields`] supports
+///
+/// [`Docx::update_fields`]: crate::Docx::update_fields
+pub(crate) struct FieldUpdater<'c> {
+    context: &'c FieldContext,
+    properties: HashMap<String, String>,
+    bookmarks: BookmarkTexts,
+    /// The last number of each `SEQ` identifier, in lower case
+    sequences: HashMap<String, usize>,
+}
+
+impl<'c> FieldUpdater<'c> {
+    pub(crate) fn new(
+        context: &'c FieldContext,
+        properties: HashMap<String, String>,
+        bookmarks: BookmarkTexts,
+    ) -> Self {
+        FieldUpdater {
+            context,
+            properties,
+            bookmarks,
+            sequences: HashMap::new(),
+        }
+    }
+
+    /// Returns the new result of a field, or `None` if it is not supported
+    pub(crate) fn result(&mut self, field: &Field) -> Option<String> {
+        match &field.kind {
+            FieldKind::Date | FieldKind::Time => {
+                let now = self.context.now;
+                if field.has_switch("@") {
+                    return Some(field.format(&now.to_string()));
+                }
+                let picture = match field.kind {
+                    FieldKind::Date => "M/d/yyyy",
+                    _ => "h:mm AM/PM",
+                };
+                Some(field.format(&now.format(picture)))
+            }
+            FieldKind::DocProperty(name) => self.property(field, name),
+            FieldKind::Author => self.property(field, "author"),
+            FieldKind::Title => self.property(field, "title"),
+            FieldKind::Subject => self.property(field, "subject"),
+            FieldKind::Keywords => self.property(field, "keywords"),
+            FieldKind::Comments => self.property(field, "comments"),
+            FieldKind::LastSavedBy => self.property(field, "lastsavedby"),
+            FieldKind::Ref(name) => {
+                // paragraph numbers, positions and note references are not known
+                if ["f", "n", "p", "r", "w"]
+                    .iter()
+                    .any(|s| field.has_switch(s))
+                {
+                    return None;
+                }
+                let text = self.bookmarks.texts.get(name)?;
+                Some(field.format(text.trim()))
+            }
+            FieldKind::PageRef(name) => {
+                if field.has_switch("p") {
+                    return None;
+                }
+                let page = self.bookmarks.pages.get(name)?;
+                Some(field.format(&page.to_string()))
+            }
+            FieldKind::NumPages => {
+                let pages = self.context.pages.unwrap_or(self.bookmarks.page);
+                Some(field.format(&pages.to_string()))
+            }
+            FieldKind::Seq(name) => {
+                let number = self.sequences.entry(name.to_lowercase()).or_default();
+                match field.switch("r").and_then(|s| s.value.as_deref()) {
+                    Some(reset) => *number = reset.trim().parse().ok()?,
+                    None if field.has_switch("c") => {}
+                    None => *number += 1,
+                }
+                if field.has_switch("h") {
+                    return Some(String::new());
+                }
+                Some(field.format(&number.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    fn property(&self, field: &Field, name: &str) -> Option<String> {
+        let value = self.properties.get(&name.to_lowercase())?;
+        Some(field.format(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::BookmarkStart;
+
+    #[test]
+    fn update_results() {
+        let context = FieldContext::default()
+            .now(DateTime::parse("2024-03-05T14:07").unwrap())
+            .pages(3);
+        let properties = [("title".to_string(), "Report".to_string())].into();
+        let mut bookmarks = BookmarkTexts::default();
+        let paragraph = Paragraph::default()
+            .push(BookmarkStart::default().id("0").name("Total"))
+            .push_text("42");
+        bookmarks.visit_paragraph(&paragraph);
+        let mut updater = FieldUpdater::new(&context, properties, bookmarks);
+        let mut result = |instruction: &str| updater.result(&Field::new(instruction));
+
+        assert_eq!(result("DATE").unwrap(), "3/5/2024");
+        assert_eq!(result(r#"DATE \@ "d MMMM yyyy""#).unwrap(), "5 March 2024");
+        assert_eq!(result("TIME").unwrap(), "2:07 PM");
+        assert_eq!(result(r#"DOCPROPERTY Title \* Upper"#).unwrap(), "REPORT");
+        assert_eq!(result("TITLE").unwrap(), "Report");
+        assert_eq!(result("AUTHOR"), None);
+        assert_eq!(result("REF Total").unwrap(), "42");
+        assert_eq!(result(r#"REF Total \# "0.00""#).unwrap(), "42.00");
+        assert_eq!(result("REF Total \\n"), None);
+        assert_eq!(result("PAGEREF Total").unwrap(), "1");
+        assert_eq!(result("NUMPAGES").unwrap(), "3");
+        assert_eq!(result("SEQ Figure").unwrap(), "1");
+        assert_eq!(result("SEQ Figure \\* ROMAN").unwrap(), "II");
+        assert_eq!(result("SEQ Table").unwrap(), "1");
+        assert_eq!(result("SEQ figure \\c").unwrap(), "2");
+        assert_eq!(result("SEQ Figure \\r 5").unwrap(), "5");
+        assert_eq!(result("SEQ Figure \\h").unwrap(), "");
+        assert_eq!(result("SEQ Figure").unwrap(), "7");
+        assert_eq!(result("PAGE"), None);
+    }
+}
@@ -4,27 +4,61 @@
 //!
 
 use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
+use std::borrow::Borrow;
 use std::io::Write;
 
-use crate::__xml_test_suites;
 use crate::schema::{SCHEMA_MAIN, SCHEMA_WORDML_14};
+use crate::{__into_owned, __xml_test_suites};
 
-use crate::document::BodyContent;
+use crate::document::{body::replace_blocks_tracked, BodyContent, TrackChanges};
 
 /// The root element of the main document part.
 #[derive(Debug, Default, XmlRead, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:ftr")]
 pub struct Footer<'a> {
-    #[xml(child = "w:sdt", child = "w:p", child = "w:tbl", child = "w:sectPr")]
+    #[xml(
+        child = "w:sdt",
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sectPr",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:moveFromRangeStart",
+        child = "w:moveFromRangeEnd",
+        child = "w:moveToRangeStart",
+        child = "w:moveToRangeEnd"
+    )]
     pub content: Vec<BodyContent<'a>>,
 }
 
+__into_owned! { Footer { content } }
+
 impl<'a> Footer<'a> {
     pub fn push<T: Into<BodyContent<'a>>>(&mut self, content: T) -> &mut Self {
         self.content.push(content.into());
         self
     }
+
+    /// Replaces text in the footer, recording each replacement as a tracked change
+    ///
+    /// Works like [`Body::replace_text_tracked`].
+    ///
+    /// [`Body::replace_text_tracked`]: crate::document::Body::replace_text_tracked
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        replace_blocks_tracked(&mut self.content, dic, track)
+    }
 }
 
 impl<'a> XmlWrite for Footer<'a> {
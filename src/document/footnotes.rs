@@ -8,7 +8,7 @@ use std::borrow::Cow;
 use std::io::Write;
 
 use crate::schema::{SCHEMA_MAIN, SCHEMA_WORDML_14};
-use crate::{__string_enum, __xml_test_suites};
+use crate::{__into_owned, __string_enum, __xml_test_suites};
 
 use crate::document::BodyContent;
 
@@ -20,6 +20,8 @@ pub struct FootNotes<'a> {
     pub content: Vec<FootNote<'a>>,
 }
 
+__into_owned! { FootNotes { content } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:footnote")]
@@ -28,10 +30,25 @@ pub struct FootNote<'a> {
     pub ty: Option<NoteSeparator>,
     #[xml(attr = "w:id")]
     pub id: Option<isize>,
-    #[xml(child = "w:sdt", child = "w:p", child = "w:tbl", child = "w:sectPr")]
+    #[xml(
+        child = "w:sdt",
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sectPr",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:moveFromRangeStart",
+        child = "w:moveFromRangeEnd",
+        child = "w:moveToRangeStart",
+        child = "w:moveToRangeEnd"
+    )]
     pub content: Vec<BodyContent<'a>>,
 }
 
+__into_owned! { FootNote { content; ty, id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:footnoteRef")]
@@ -48,6 +65,8 @@ pub struct FootnoteReference<'a> {
     pub id: Option<Cow<'a, str>>,
 }
 
+__into_owned! { FootnoteReference { id; supress_reference_mark } }
+
 impl<'a> FootNote<'a> {
     pub fn push<T: Into<BodyContent<'a>>>(&mut self, content: T) -> &mut Self {
         self.content.push(content.into());
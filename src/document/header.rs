@@ -7,20 +7,35 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::borrow::Borrow;
 use std::io::Write;
 
-use crate::__xml_test_suites;
 use crate::schema::{SCHEMA_MAIN, SCHEMA_WORDML_14};
+use crate::{__into_owned, __xml_test_suites};
 
-use crate::document::BodyContent;
+use crate::document::{body::replace_blocks_tracked, BodyContent, TrackChanges};
 
 /// The root element of the main document part.
 #[derive(Debug, Default, XmlRead, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:hdr")]
 pub struct Header<'a> {
-    #[xml(child = "w:p", child = "w:tbl", child = "w:sectPr", child = "w:sdt")]
+    #[xml(
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sectPr",
+        child = "w:sdt",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:moveFromRangeStart",
+        child = "w:moveFromRangeEnd",
+        child = "w:moveToRangeStart",
+        child = "w:moveToRangeEnd"
+    )]
     pub content: Vec<BodyContent<'a>>,
 }
 
+__into_owned! { Header { content } }
+
 impl<'a> Header<'a> {
     pub fn push<T: Into<BodyContent<'a>>>(&mut self, content: T) -> &mut Self {
         self.content.push(content.into());
@@ -49,10 +64,35 @@ impl<'a> Header<'a> {
                 BodyContent::Sdt(_) => {}
                 BodyContent::TableCell(_) => {}
                 BodyContent::Run(_) => {}
+                BodyContent::Insertion(ins) => {
+                    ins.replace_text(dic)?;
+                }
+                BodyContent::MoveTo(to) => {
+                    to.replace_text(dic)?;
+                }
+                _ => {}
             }
         }
         Ok(())
     }
+
+    /// Replaces text in the header, recording each replacement as a tracked change
+    ///
+    /// Works like [`Body::replace_text_tracked`].
+    ///
+    /// [`Body::replace_text_tracked`]: crate::document::Body::replace_text_tracked
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        replace_blocks_tracked(&mut self.content, dic, track)
+    }
 }
 
 impl<'a> XmlWrite for Header<'a> {
@@ -3,7 +3,7 @@ use derive_more::From;
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__setter, __string_enum, __xml_test_suites};
+use crate::{__into_owned, __setter, __string_enum, __xml_test_suites};
 
 /// A set of elements that can be contained as the content of a run.
 #[derive(Debug, From, XmlRead, XmlWrite, Clone)]
@@ -15,6 +15,8 @@ pub enum HeaderFooterReference<'a> {
     Footer(FooterReference<'a>),
 }
 
+__into_owned! { enum HeaderFooterReference { Header, Footer } }
+
 /// HeaderReference
 ///
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
@@ -28,6 +30,8 @@ pub struct HeaderReference<'a> {
     pub id: Option<Cow<'a, str>>,
 }
 
+__into_owned! { HeaderReference { id; ty } }
+
 impl<'a> HeaderReference<'a> {
     __setter!(ty: Option<HeaderFooterReferenceType>);
     __setter!(id: Option<Cow<'a, str>>);
@@ -46,6 +50,8 @@ pub struct FooterReference<'a> {
     pub id: Option<Cow<'a, str>>,
 }
 
+__into_owned! { FooterReference { id; ty } }
+
 impl<'a> FooterReference<'a> {
     __setter!(ty: Option<HeaderFooterReferenceType>);
     __setter!(id: Option<Cow<'a, str>>);
@@ -1,31 +1,37 @@
 #![allow(unused_must_use)]
 
+use derive_more::From;
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
-use derive_more::From;
 
-use crate::{__setter, __xml_test_suites, document::bidir::BidirectionalEmbedding, document::Run, document::Text};
+use crate::document::{
+    paragraph::replace_inline_tracked, Deletion, Insertion, ParagraphContent, TrackChanges,
+};
+use crate::{
+    __into_owned, __setter, __xml_test_suites, document::bidir::BidirectionalEmbedding,
+    document::Run, document::Text,
+};
 
 /// The root element of a hyperlink within the paragraph
-/// 
+///
 /// Supports multiple runs to handle complex hyperlinks like table of contents entries.
 /// Each run can have different formatting while being part of the same hyperlink.
-/// 
+///
 /// # Examples
-/// 
+///
 /// Creating a simple hyperlink:
 /// ```rust
 /// use docx_rust::document::{Hyperlink, Run};
-/// 
+///
 /// let hyperlink = Hyperlink::default()
 ///     .id("link1")
 ///     .push_run(Run::default().push_text("Click here"));
 /// ```
-/// 
+///
 /// Creating a complex table of contents hyperlink:
 /// ```rust
 /// use docx_rust::document::{Hyperlink, Run};
-/// 
+///
 /// let toc_hyperlink = Hyperlink::default()
 ///     .id("_Toc123456789")
 ///     .push_run(Run::default().push_text("Chapter 1"))
@@ -38,11 +44,33 @@ use crate::{__setter, __xml_test_suites, document::bidir::BidirectionalEmbedding
 /// Content types that can appear within a hyperlink
 #[derive(Debug, From, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
 pub enum HyperlinkContent<'a> {
     /// A run of text with formatting
     #[xml(tag = "w:r")]
     Run(Run<'a>),
+    /// Runs inserted with change tracking on
+    #[xml(tag = "w:ins")]
+    Insertion(Insertion<'a>),
+    /// Runs deleted with change tracking on
+    #[xml(tag = "w:del")]
+    Deletion(Deletion<'a>),
+}
+
+__into_owned! { enum HyperlinkContent { Run, Insertion, Deletion } }
+
+impl<'a> HyperlinkContent<'a> {
+    /// Converts paragraph content which can be part of a hyperlink
+    pub(crate) fn from_inline(content: ParagraphContent<'a>) -> Option<Self> {
+        match content {
+            ParagraphContent::Run(run) => Some(run.into()),
+            ParagraphContent::Insertion(ins) => Some(ins.into()),
+            ParagraphContent::Deletion(del) => Some(del.into()),
+            _ => None,
+        }
+    }
 }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:hyperlink")]
@@ -53,7 +81,7 @@ pub struct Hyperlink<'a> {
     /// Specifies the name of a bookmark within the document.
     #[xml(attr = "w:anchor")]
     pub anchor: Option<Cow<'a, str>>,
-    #[xml(child = "w:r")]
+    #[xml(child = "w:r", child = "w:ins", child = "w:del")]
     /// Link content - supports multiple runs for complex hyperlinks
     pub content: Vec<HyperlinkContent<'a>>,
     #[xml(child = "w:dir")]
@@ -61,6 +89,8 @@ pub struct Hyperlink<'a> {
     pub bidirectional_embedding: Option<BidirectionalEmbedding<'a>>,
 }
 
+__into_owned! { Hyperlink { id, anchor, content, bidirectional_embedding } }
+
 impl<'a> Hyperlink<'a> {
     __setter!(id: Option<Cow<'a, str>>);
     __setter!(anchor: Option<Cow<'a, str>>);
@@ -71,27 +101,30 @@ impl<'a> Hyperlink<'a> {
         self.content.push(HyperlinkContent::Run(run));
         self
     }
-    
+
     /// Add text as a new Run to the hyperlink content
     pub fn add_text<T: Into<Text<'a>>>(mut self, text: T) -> Self {
-        self.content.push(HyperlinkContent::Run(Run::default().push_text(text)));
+        self.content
+            .push(HyperlinkContent::Run(Run::default().push_text(text)));
         self
     }
-    
+
     /// Get the first run (for backward compatibility)
     pub fn first_run(&self) -> Option<&Run<'a>> {
         self.content.first().and_then(|content| match content {
             HyperlinkContent::Run(run) => Some(run),
+            _ => None,
         })
     }
-    
+
     /// Get mutable reference to the first run (for backward compatibility)
     pub fn first_run_mut(&mut self) -> Option<&mut Run<'a>> {
         self.content.first_mut().and_then(|content| match content {
             HyperlinkContent::Run(run) => Some(run),
+            _ => None,
         })
     }
-    
+
     /// Create hyperlink from a single run (migration helper)
     pub fn from_single_run(run: Run<'a>) -> Self {
         Self {
@@ -99,7 +132,7 @@ impl<'a> Hyperlink<'a> {
             ..Default::default()
         }
     }
-    
+
     /// Add text replacement support for all runs in the hyperlink
     pub fn replace_text<'b, I, T, S>(&mut self, dic: T) -> crate::DocxResult<()>
     where
@@ -110,11 +143,47 @@ impl<'a> Hyperlink<'a> {
         for content in self.content.iter_mut() {
             match content {
                 HyperlinkContent::Run(run) => run.replace_text(dic)?,
+                HyperlinkContent::Insertion(ins) => ins.replace_text(dic)?,
+                HyperlinkContent::Deletion(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces text like [`Hyperlink::replace_text`], but records each replacement as a tracked change
+    ///
+    /// The `w:del` and `w:ins` of every match are put inside the hyperlink, in place of the
+    /// run they are taken from.
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: std::borrow::Borrow<(S, S)>,
+    {
+        for item in std::mem::take(&mut self.content) {
+            match item {
+                HyperlinkContent::Run(run) => match run.replace_text_tracked(dic, track) {
+                    Some(replaced) => self.content.extend(
+                        replaced
+                            .into_iter()
+                            .filter_map(HyperlinkContent::from_inline),
+                    ),
+                    None => self.content.push(run.into()),
+                },
+                HyperlinkContent::Insertion(mut ins) => {
+                    replace_inline_tracked(&mut ins.content, dic, track)?;
+                    self.content.push(ins.into());
+                }
+                item => self.content.push(item),
             }
         }
         Ok(())
     }
-    
+
     /// Simple text replacement across all runs
     pub fn replace_text_simple<S>(&mut self, old: S, new: S)
     where
@@ -123,6 +192,10 @@ impl<'a> Hyperlink<'a> {
         for content in self.content.iter_mut() {
             match content {
                 HyperlinkContent::Run(run) => run.replace_text_simple(&old, &new),
+                HyperlinkContent::Insertion(ins) => {
+                    let _ = ins.replace_text([(old.as_ref(), new.as_ref())]);
+                }
+                HyperlinkContent::Deletion(_) => {}
             }
         }
     }
@@ -136,13 +209,18 @@ impl<'a> Hyperlink<'a> {
 
     pub fn iter_text(&self) -> Box<dyn Iterator<Item = &Cow<'a, str>> + '_> {
         Box::new(
-            self.content.iter().flat_map(|content| match content {
-                HyperlinkContent::Run(run) => run.iter_text(),
-            }).chain(
-                self.bidirectional_embedding
-                    .iter()
-                    .flat_map(|bidi| bidi.iter_text()),
-            ),
+            self.content
+                .iter()
+                .flat_map(|content| match content {
+                    HyperlinkContent::Run(run) => run.iter_text(),
+                    HyperlinkContent::Insertion(ins) => ins.iter_text(),
+                    HyperlinkContent::Deletion(_) => Box::new(std::iter::empty()),
+                })
+                .chain(
+                    self.bidirectional_embedding
+                        .iter()
+                        .flat_map(|bidi| bidi.iter_text()),
+                ),
         )
     }
 
@@ -152,6 +230,8 @@ impl<'a> Hyperlink<'a> {
                 .iter_mut()
                 .flat_map(|content| match content {
                     HyperlinkContent::Run(run) => run.iter_text_mut(),
+                    HyperlinkContent::Insertion(ins) => ins.iter_text_mut(),
+                    HyperlinkContent::Deletion(_) => Box::new(std::iter::empty()),
                 })
                 .chain(
                     self.bidirectional_embedding
@@ -172,6 +252,11 @@ __xml_test_suites!(
     r#"<w:hyperlink w:anchor="anchor"/>"#,
     Hyperlink::default().push_run(Run::default()),
     r#"<w:hyperlink><w:r/></w:hyperlink>"#,
+    Hyperlink {
+        content: vec![Insertion::default().push(Run::default()).into()],
+        ..Default::default()
+    },
+    r#"<w:hyperlink><w:ins w:id="0" w:author=""><w:r/></w:ins></w:hyperlink>"#,
 );
 
 #[cfg(test)]
@@ -184,17 +269,15 @@ mod tests {
         let hyperlink = Hyperlink::default()
             .push_run(Run::default().push_text("First "))
             .push_run(Run::default().push_text("Second"));
-        
+
         assert_eq!(hyperlink.content.len(), 2);
         assert_eq!(hyperlink.text(), "First Second");
     }
 
     #[test]
     fn test_add_text_method() {
-        let hyperlink = Hyperlink::default()
-            .add_text("Hello ")
-            .add_text("World");
-        
+        let hyperlink = Hyperlink::default().add_text("Hello ").add_text("World");
+
         assert_eq!(hyperlink.content.len(), 2);
         assert_eq!(hyperlink.text(), "Hello World");
     }
@@ -204,10 +287,13 @@ mod tests {
         let mut hyperlink = Hyperlink::default()
             .add_text("Old text in first run")
             .add_text("Old text in second run");
-        
+
         hyperlink.replace_text_simple("Old text", "New text");
-        
-        assert_eq!(hyperlink.text(), "New text in first runNew text in second run");
+
+        assert_eq!(
+            hyperlink.text(),
+            "New text in first runNew text in second run"
+        );
     }
 
     #[test]
@@ -215,19 +301,17 @@ mod tests {
         let mut hyperlink = Hyperlink::default()
             .add_text("Hello World")
             .add_text("Goodbye Moon");
-        
+
         let replacements = [("Hello", "Hi"), ("Goodbye", "Bye")];
         hyperlink.replace_text(&replacements).unwrap();
-        
+
         assert_eq!(hyperlink.text(), "Hi WorldBye Moon");
     }
 
     #[test]
     fn test_backward_compatibility_first_run() {
-        let hyperlink = Hyperlink::default()
-            .add_text("First")
-            .add_text("Second");
-        
+        let hyperlink = Hyperlink::default().add_text("First").add_text("Second");
+
         assert_eq!(hyperlink.first_run().unwrap().text(), "First");
     }
 
@@ -235,21 +319,17 @@ mod tests {
     fn test_from_single_run() {
         let run = Run::default().push_text("Single run");
         let hyperlink = Hyperlink::from_single_run(run);
-        
+
         assert_eq!(hyperlink.content.len(), 1);
         assert_eq!(hyperlink.text(), "Single run");
     }
 
     #[test]
     fn test_iter_text_multiple_runs() {
-        let hyperlink = Hyperlink::default()
-            .add_text("First")
-            .add_text(" Second");
-        
-        let texts: Vec<String> = hyperlink.iter_text()
-            .map(|cow| cow.to_string())
-            .collect();
-        
+        let hyperlink = Hyperlink::default().add_text("First").add_text(" Second");
+
+        let texts: Vec<String> = hyperlink.iter_text().map(|cow| cow.to_string()).collect();
+
         assert_eq!(texts, vec!["First", " Second"]);
     }
 
@@ -259,13 +339,13 @@ mod tests {
         let mut hyperlink = Hyperlink::default()
             .id("_Toc123456789")
             .push_run(Run::default().push_text("Chapter 1"))
-            .push_run(Run::default().push_text("    "))  // Tab spacing
+            .push_run(Run::default().push_text("    ")) // Tab spacing
             .push_run(Run::default().push_text("Introduction"));
-        
+
         assert_eq!(hyperlink.content.len(), 3);
         assert_eq!(hyperlink.text(), "Chapter 1    Introduction");
         assert_eq!(hyperlink.id.as_ref().unwrap(), "_Toc123456789");
-        
+
         // Test text replacement works across all runs
         hyperlink.replace_text_simple("Chapter 1", "Section 1");
         assert_eq!(hyperlink.text(), "Section 1    Introduction");
@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__string_enum, __xml_test_suites};
+use crate::{__into_owned, __string_enum, __xml_test_suites};
 
 /// Literal Text
 ///
@@ -26,6 +26,8 @@ pub struct InstrText<'a> {
     pub text: Cow<'a, str>,
 }
 
+__into_owned! { InstrText { text; space } }
+
 impl From<String> for InstrText<'_> {
     fn from(val: String) -> Self {
         InstrText {
@@ -74,6 +76,8 @@ pub struct DelInstrText<'a> {
     pub text: Cow<'a, str>,
 }
 
+__into_owned! { DelInstrText { text; space } }
+
 impl From<String> for DelInstrText<'_> {
     fn from(val: String) -> Self {
         DelInstrText {
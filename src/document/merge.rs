@@ -0,0 +1,200 @@
+//! Evaluation of mail merge fields
+
+use std::collections::HashMap;
+
+use crate::document::{Field, FieldKind};
+
+/// A record of a mail merge data source, mapping column names to values
+pub(crate) type Record = HashMap<String, String>;
+
+/// Returns if the field only has a meaning while merging, so it is replaced by its result
+/// in merged documents
+pub(crate) fn is_merge_field(field: &Field) -> bool {
+    matches!(
+        field.kind,
+        FieldKind::MergeField(_)
+            | FieldKind::MergeRec
+            | FieldKind::If
+            | FieldKind::Next
+            | FieldKind::NextIf
+    )
+}
+
+/// Computes the results of the mail merge fields of one merged document
+///
+/// `NEXT` and `NEXTIF` fields move on to the following records, so a document may use
+/// several of them.
+pub(crate) struct Merger<'r> {
+    records: &'r [Record],
+    /// The index of the record the fields are filled from
+    pub(crate) current: usize,
+}
+
+impl<'r> Merger<'r> {
+    pub(crate) fn new(records: &'r [Record], current: usize) -> Self {
+        Merger { records, current }
+    }
+
+    /// Returns the result of a mail merge field, or `None` for other fields
+    pub(crate) fn result(&mut self, field: &Field) -> Option<String> {
+        match &field.kind {
+            FieldKind::MergeField(name) => {
+                let value = self.value(name);
+                if value.is_empty() {
+                    return Some(String::new());
+                }
+                let text = |name| field.switch(name).and_then(|s| s.value.as_deref());
+                Some(format!(
+                    "{}{}{}",
+                    text("b").unwrap_or_default(),
+                    field.format(value),
+                    text("f").unwrap_or_default()
+                ))
+            }
+            FieldKind::MergeRec => Some(field.format(&(self.current + 1).to_string())),
+            FieldKind::If => {
+                let arguments = &field.arguments;
+                let chosen = match arguments.get(..3) {
+                    Some([left, operator, right]) if compare(left, operator, right) => {
+                        arguments.get(3)
+                    }
+                    Some(_) => arguments.get(4),
+                    None => None,
+                };
+                Some(field.format(chosen.map_or("", String::as_str)))
+            }
+            FieldKind::Next => {
+                self.current += 1;
+                Some(String::new())
+            }
+            FieldKind::NextIf => {
+                if let Some([left, operator, right]) = field.arguments.get(..3) {
+                    if compare(left, operator, right) {
+                        self.current += 1;
+                    }
+                }
+                Some(String::new())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a column of the current record
+    ///
+    /// Column names are matched ignoring case, like Word does. Missing columns and records
+    /// have an empty value.
+    fn value(&self, name: &str) -> &'r str {
+        let Some(record) = self.records.get(self.current) else {
+            return "";
+        };
+        record
+            .get(name)
+            .or_else(|| {
+                record
+                    .iter()
+                    .find(|(column, _)| column.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| value)
+            })
+            .map_or("", String::as_str)
+    }
+}
+
+/// Compares two values of an `IF` or `NEXTIF` field
+///
+/// Numbers are compared by their value. Otherwise `=` and `<>` allow the wildcards `*` and
+/// `?` in the right value, and the other operators compare the text.
+fn compare(left: &str, operator: &str, right: &str) -> bool {
+    if let (Ok(left), Ok(right)) = (left.trim().parse::<f64>(), right.trim().parse::<f64>()) {
+        return match operator {
+            "=" => left == right,
+            "<>" => left != right,
+            "<" => left < right,
+            "<=" => left <= right,
+            ">" => left > right,
+            ">=" => left >= right,
+            _ => false,
+        };
+    }
+    match operator {
+        "=" => matches_wildcards(left, right),
+        "<>" => !matches_wildcards(left, right),
+        "<" => left < right,
+        "<=" => left <= right,
+        ">" => left > right,
+        ">=" => left >= right,
+        _ => false,
+    }
+}
+
+fn matches_wildcards(text: &str, pattern: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut t, mut p) = (0, 0);
+    // the position of the last `*` in the pattern and of the text it matched up to
+    let mut star = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_values() {
+        assert!(compare("10", ">", "9"));
+        assert!(compare("10.0", "=", "10"));
+        assert!(!compare("10", "<", "9"));
+        assert!(compare("Berlin", "=", "B*n"));
+        assert!(compare("Bern", "=", "B??n"));
+        assert!(compare("Bonn", "<>", "B?"));
+        assert!(compare("", "=", ""));
+        assert!(compare("a", "<", "b"));
+    }
+
+    #[test]
+    fn merge_results() {
+        let records: Vec<Record> = vec![
+            [("Name".to_string(), "alice".to_string())].into(),
+            [("Name".to_string(), "bob".to_string())].into(),
+        ];
+        let mut merger = Merger::new(&records, 0);
+        let result =
+            |merger: &mut Merger, instruction: &str| merger.result(&Field::new(instruction));
+
+        assert_eq!(
+            result(&mut merger, r#"MERGEFIELD name \* Caps \b "Dear ""#).unwrap(),
+            "Dear Alice"
+        );
+        assert_eq!(result(&mut merger, "MERGEFIELD Missing \\b x").unwrap(), "");
+        assert_eq!(
+            result(&mut merger, r#"IF "alice" = "a*" "yes" "no""#).unwrap(),
+            "yes"
+        );
+        assert_eq!(result(&mut merger, "MERGEREC").unwrap(), "1");
+        assert_eq!(result(&mut merger, r#"NEXTIF "a" = "b""#).unwrap(), "");
+        assert_eq!(merger.current, 0);
+        assert_eq!(result(&mut merger, "NEXT").unwrap(), "");
+        assert_eq!(result(&mut merger, "MERGEFIELD Name").unwrap(), "bob");
+        assert_eq!(result(&mut merger, "PAGE"), None);
+    }
+}
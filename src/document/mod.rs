@@ -1,3 +1,4 @@
+mod append;
 mod bidir;
 mod body;
 mod bookmark_end;
@@ -9,7 +10,10 @@ mod date;
 mod document;
 mod drawing;
 mod endnotes;
+mod field;
 mod field_char;
+mod field_format;
+mod field_update;
 mod footer;
 mod footnotes;
 mod grid_column;
@@ -17,8 +21,10 @@ mod header;
 mod header_footer_reference;
 mod hyperlink;
 mod instrtext;
+mod merge;
 mod numbering;
 mod paragraph;
+mod revision;
 mod run;
 mod sdt;
 mod sym;
@@ -29,10 +35,30 @@ mod table_grid;
 mod table_row;
 mod text;
 mod theme;
+mod theme_resolver;
+mod toc;
+mod visit;
 
+pub use self::field_format::DateTime;
+pub use self::field_update::FieldContext;
+pub use self::instrtext::{DelInstrText, InstrText};
+pub use self::theme_resolver::ThemeResolver;
 pub use self::{
     body::*, bookmark_end::*, bookmark_start::*, comment_range::*, comments::*, document::*,
-    drawing::*, endnotes::*, field_char::*, footer::*, footnotes::*, grid_column::*, header::*,
-    header_footer_reference::*, hyperlink::*, numbering::*, paragraph::*, r#break::*, run::*,
-    sdt::*, tab::*, table::*, table_cell::*, table_grid::*, table_row::*, text::*, theme::*,
+    drawing::*, endnotes::*, field::*, field_char::*, footer::*, footnotes::*, grid_column::*,
+    header::*, header_footer_reference::*, hyperlink::*, numbering::*, paragraph::*, r#break::*,
+    revision::*, run::*, sdt::*, tab::*, table::*, table_cell::*, table_grid::*, table_row::*,
+    text::*, theme::*,
+};
+
+pub(crate) use self::append::IdRemapper;
+pub(crate) use self::field::FieldScanner;
+pub(crate) use self::field_update::{document_properties, BookmarkTexts, FieldUpdater};
+pub(crate) use self::merge::{is_merge_field, Merger, Record};
+pub(crate) use self::toc::{
+    add_toc_styles, heading_levels, is_toc, refresh_toc_sdt, toc_anchors, toc_field_levels,
+    toc_sdt, Bookmarks, TocHeadings,
+};
+pub(crate) use self::visit::{
+    walk_blocks, walk_blocks_mut, walk_paragraph, walk_paragraph_mut, Visit, VisitMut,
 };
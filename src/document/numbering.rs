@@ -7,7 +7,8 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::{borrow::Cow, io::Write};
 
 use crate::{
-    formatting::{CharacterProperty, Indent, JustificationVal},
+    __into_owned,
+    formatting::{CharacterProperty, Indent, JustificationVal, ParagraphStyleId},
     schema::{SCHEMA_MAIN, SCHEMA_WORDML_14},
 };
 
@@ -25,6 +26,8 @@ pub struct Numbering<'a> {
     pub numberings: Vec<Num>,
 }
 
+__into_owned! { Numbering { abstract_numberings; numberings } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:abstractNum")]
@@ -39,6 +42,8 @@ pub struct AbstractNum<'a> {
     pub levels: Vec<Level<'a>>,
 }
 
+__into_owned! { AbstractNum { nsid, multi_level_type, levels; abstract_num_id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:nsid")]
@@ -47,6 +52,8 @@ pub struct Nsid<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { Nsid { value } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:multiLevelType")]
@@ -55,6 +62,8 @@ pub struct MultiLevelType<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { MultiLevelType { value } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:lvl")]
@@ -65,6 +74,9 @@ pub struct Level<'a> {
     pub start: Option<LevelStart>,
     #[xml(child = "w:numFmt")]
     pub number_format: Option<NumFmt<'a>>,
+    /// Paragraph style which uses the level
+    #[xml(child = "w:pStyle")]
+    pub p_style: Option<ParagraphStyleId<'a>>,
     #[xml(child = "w:lvlText")]
     pub level_text: Option<LevelText<'a>>,
     #[xml(child = "w:lvlJc")]
@@ -75,6 +87,13 @@ pub struct Level<'a> {
     pub r_pr: Vec<CharacterProperty<'a>>,
 }
 
+__into_owned! {
+    Level {
+        number_format, p_style, level_text, r_pr;
+        i_level, start, justification, p_pr
+    }
+}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:pPr")]
@@ -92,6 +111,8 @@ pub struct NumFmt<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { NumFmt { value } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:start")]
@@ -109,6 +130,8 @@ pub struct LevelText<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { LevelText { value } }
+
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:lvlJc")]
@@ -2,16 +2,20 @@
 use derive_more::From;
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::{Borrow, Cow};
+use std::ops::Range;
 
 use crate::{
-    __setter, __xml_test_suites,
+    __into_owned, __setter, __xml_test_suites,
     document::{
-        BookmarkEnd, BookmarkStart, CommentRangeEnd, CommentRangeStart, Hyperlink, Run, RunContent,
-        Text, SDT,
+        BookmarkEnd, BookmarkStart, CommentRangeEnd, CommentRangeStart, Deletion, Hyperlink,
+        Insertion, MoveFrom, MoveFromRangeEnd, MoveFromRangeStart, MoveTo, MoveToRangeEnd,
+        MoveToRangeStart, Run, RunContent, SimpleField, Text, TextSpace, TrackChanges, SDT,
     },
     formatting::ParagraphProperty,
 };
 
+use super::{run::find_first_match, HyperlinkContent};
+
 /// Paragraph
 ///
 /// Paragraph is the main block-level container for content.
@@ -53,13 +57,24 @@ pub struct Paragraph<'a> {
         child = "w:commentRangeEnd",
         child = "w:r",
         child = "w:hyperlink",
+        child = "w:fldSimple",
         child = "w:bookmarkStart",
         child = "w:bookmarkEnd",
-        child = "w:sdt"
+        child = "w:sdt",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:moveFromRangeStart",
+        child = "w:moveFromRangeEnd",
+        child = "w:moveToRangeStart",
+        child = "w:moveToRangeEnd"
     )]
     pub content: Vec<ParagraphContent<'a>>,
 }
 
+__into_owned! { Paragraph { rsid_r, rsid_r_default, property, content } }
+
 impl<'a> Paragraph<'a> {
     __setter!(property: Option<ParagraphProperty<'a>>);
 
@@ -86,28 +101,13 @@ impl<'a> Paragraph<'a> {
     }
 
     pub fn iter_text(&self) -> Box<dyn Iterator<Item = &Cow<'a, str>> + '_> {
-        Box::new(
-            self.content
-                .iter()
-                .filter_map(|content| match content {
-                    ParagraphContent::Run(run) => Some(run.iter_text()),
-                    ParagraphContent::Link(link) => Some(link.iter_text()),
-                    ParagraphContent::SDT(sdt) => Some(sdt.iter_text()),
-                    _ => None,
-                })
-                .flatten(),
-        )
+        Box::new(self.content.iter().flat_map(|content| content.iter_text()))
     }
 
     pub fn iter_text_mut(&mut self) -> impl Iterator<Item = &mut Cow<'a, str>> {
         self.content
             .iter_mut()
-            .filter_map(|content| match content {
-                ParagraphContent::Run(run) => Some(run.iter_text_mut()),
-                ParagraphContent::Link(link) => Some(link.iter_text_mut()),
-                _ => None,
-            })
-            .flatten()
+            .flat_map(|content| content.iter_text_mut())
     }
 
     pub fn replace_text<'b, I, T, S>(&mut self, dic: T) -> crate::DocxResult<()>
@@ -117,21 +117,214 @@ impl<'a> Paragraph<'a> {
         I: Borrow<(S, S)>,
     {
         for content in self.content.iter_mut() {
-            match content {
-                ParagraphContent::Run(r) => {
-                    r.replace_text(dic)?;
+            content.replace_text(dic)?;
+        }
+
+        Ok(())
+    }
+
+    /// Replaces text in the runs of the paragraph, recording each replacement as a tracked change
+    ///
+    /// The runs of hyperlinks, insertions and content controls are covered too, while deleted
+    /// text is left alone. See [`Run::replace_text_tracked`] for how the runs are split.
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        replace_inline_tracked(&mut self.content, dic, track)
+    }
+
+    /// Finds every occurrence of `pattern`, even when it is split across runs
+    ///
+    /// The text of the runs of the paragraph, including the runs of its hyperlinks,
+    /// is searched as one string. Returns the byte ranges of the matches in that string.
+    pub fn find_text(&self, pattern: &str) -> Vec<Range<usize>> {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+        let text: String = self.iter_runs().flat_map(run_texts).collect();
+        text.match_indices(pattern)
+            .map(|(pos, found)| pos..pos + found.len())
+            .collect()
+    }
+
+    /// Replaces text like [`Paragraph::replace_text`], but matches across run and hyperlink boundaries
+    ///
+    /// Each replacement takes the formatting of the run in which its match starts,
+    /// and the runs emptied by the replacement are removed. Returns the byte ranges
+    /// of the replaced matches, in the text searched by [`Paragraph::find_text`].
+    pub fn replace_text_across_runs<'b, I, T, S>(&mut self, dic: T) -> Vec<Range<usize>>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        let mut segments: Vec<&mut Text<'a>> = self
+            .iter_runs_mut()
+            .flat_map(|run| {
+                run.content.iter_mut().filter_map(|content| match content {
+                    RunContent::Text(text) => Some(text),
+                    _ => None,
+                })
+            })
+            .collect();
+        let text: String = segments.iter().map(|t| t.text.as_ref()).collect();
+
+        let mut matches = Vec::new();
+        let mut offset = 0;
+        while let Some((pos, len, new)) = find_first_match(&text[offset..], dic) {
+            let start = offset + pos;
+            matches.push((start..start + len, new));
+            offset = start + len;
+        }
+        if matches.is_empty() {
+            return Vec::new();
+        }
+
+        let mut emptied = vec![false; segments.len()];
+        let mut start = 0;
+        for (segment, emptied) in segments.iter_mut().zip(emptied.iter_mut()) {
+            let end = start + segment.text.len();
+            let mut overlapping = matches
+                .iter()
+                .filter(|(range, _)| range.start < end && range.end > start)
+                .peekable();
+            if overlapping.peek().is_some() {
+                let mut replaced = String::new();
+                let mut cursor = start;
+                for (range, new) in overlapping {
+                    if range.start >= start {
+                        replaced.push_str(&text[cursor..range.start]);
+                        replaced.push_str(new);
+                    }
+                    cursor = range.end.min(end);
                 }
-                ParagraphContent::Link(link) => {
-                    link.replace_text(dic)?;
+                replaced.push_str(&text[cursor..end]);
+                *emptied = replaced.is_empty();
+                if replaced.starts_with(char::is_whitespace)
+                    || replaced.ends_with(char::is_whitespace)
+                {
+                    segment.space = Some(TextSpace::Preserve);
                 }
-                _ => {}
+                segment.text = replaced.into();
             }
+            start = end;
         }
 
-        Ok(())
+        let mut emptied = emptied.into_iter();
+        let mut trim = |run: &mut Run<'a>| {
+            let mut trimmed = false;
+            run.content.retain(|content| match content {
+                RunContent::Text(_) => {
+                    let keep = !emptied.next().unwrap_or_default();
+                    trimmed |= !keep;
+                    keep
+                }
+                _ => true,
+            });
+            !(trimmed && run.content.is_empty())
+        };
+        self.content.retain_mut(|content| match content {
+            ParagraphContent::Run(run) => trim(run),
+            ParagraphContent::Link(link) => {
+                let had_runs = !link.content.is_empty();
+                link.content.retain_mut(|content| match content {
+                    HyperlinkContent::Run(run) => trim(run),
+                    _ => true,
+                });
+                !(had_runs && link.content.is_empty())
+            }
+            _ => true,
+        });
+
+        matches.into_iter().map(|(range, _)| range).collect()
+    }
+
+    fn iter_runs(&self) -> impl Iterator<Item = &Run<'a>> {
+        self.content.iter().flat_map(|content| {
+            let runs: Box<dyn Iterator<Item = &Run<'a>>> = match content {
+                ParagraphContent::Run(run) => Box::new(std::iter::once(run)),
+                ParagraphContent::Link(link) => {
+                    Box::new(link.content.iter().filter_map(|content| match content {
+                        HyperlinkContent::Run(run) => Some(run),
+                        _ => None,
+                    }))
+                }
+                _ => Box::new(std::iter::empty()),
+            };
+            runs
+        })
+    }
+
+    fn iter_runs_mut(&mut self) -> impl Iterator<Item = &mut Run<'a>> {
+        self.content.iter_mut().flat_map(|content| {
+            let runs: Box<dyn Iterator<Item = &mut Run<'a>>> = match content {
+                ParagraphContent::Run(run) => Box::new(std::iter::once(run)),
+                ParagraphContent::Link(link) => {
+                    Box::new(link.content.iter_mut().filter_map(|content| match content {
+                        HyperlinkContent::Run(run) => Some(run),
+                        _ => None,
+                    }))
+                }
+                _ => Box::new(std::iter::empty()),
+            };
+            runs
+        })
     }
 }
 
+/// Replaces text in paragraph content, recording each replacement as a tracked change
+pub(crate) fn replace_inline_tracked<'a, 'b, I, T, S>(
+    content: &mut Vec<ParagraphContent<'a>>,
+    dic: T,
+    track: &mut TrackChanges,
+) -> crate::DocxResult<()>
+where
+    S: AsRef<str> + 'b,
+    T: IntoIterator<Item = I> + Copy,
+    I: Borrow<(S, S)>,
+{
+    for item in std::mem::take(content) {
+        match item {
+            ParagraphContent::Run(run) => match run.replace_text_tracked(dic, track) {
+                Some(replaced) => content.extend(replaced),
+                None => content.push(run.into()),
+            },
+            ParagraphContent::Link(mut link) => {
+                link.replace_text_tracked(dic, track)?;
+                content.push(link.into());
+            }
+            ParagraphContent::SDT(mut sdt) => {
+                sdt.replace_text_tracked(dic, track)?;
+                content.push(sdt.into());
+            }
+            ParagraphContent::Insertion(mut ins) => {
+                replace_inline_tracked(&mut ins.content, dic, track)?;
+                content.push(ins.into());
+            }
+            ParagraphContent::MoveTo(mut to) => {
+                replace_inline_tracked(&mut to.content, dic, track)?;
+                content.push(to.into());
+            }
+            item => content.push(item),
+        }
+    }
+    Ok(())
+}
+
+fn run_texts<'r>(run: &'r Run<'_>) -> impl Iterator<Item = &'r str> {
+    run.content.iter().filter_map(|content| match content {
+        RunContent::Text(text) => Some(text.text.as_ref()),
+        _ => None,
+    })
+}
+
 /// A set of elements that can be contained as the content of a paragraph.
 #[derive(Debug, From, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -144,12 +337,79 @@ pub enum ParagraphContent<'a> {
     Run(Run<'a>),
     #[xml(tag = "w:hyperlink")]
     Link(Hyperlink<'a>),
+    #[xml(tag = "w:fldSimple")]
+    SimpleField(SimpleField<'a>),
     #[xml(tag = "w:bookmarkStart")]
     BookmarkStart(BookmarkStart<'a>),
     #[xml(tag = "w:bookmarkEnd")]
     BookmarkEnd(BookmarkEnd<'a>),
     #[xml(tag = "w:sdt")]
     SDT(SDT<'a>),
+    #[xml(tag = "w:ins")]
+    Insertion(Insertion<'a>),
+    #[xml(tag = "w:del")]
+    Deletion(Deletion<'a>),
+    #[xml(tag = "w:moveFrom")]
+    MoveFrom(MoveFrom<'a>),
+    #[xml(tag = "w:moveTo")]
+    MoveTo(MoveTo<'a>),
+    #[xml(tag = "w:moveFromRangeStart")]
+    MoveFromRangeStart(MoveFromRangeStart<'a>),
+    #[xml(tag = "w:moveFromRangeEnd")]
+    MoveFromRangeEnd(MoveFromRangeEnd),
+    #[xml(tag = "w:moveToRangeStart")]
+    MoveToRangeStart(MoveToRangeStart<'a>),
+    #[xml(tag = "w:moveToRangeEnd")]
+    MoveToRangeEnd(MoveToRangeEnd),
+}
+
+__into_owned! {
+    enum ParagraphContent {
+        CommentRangeStart, CommentRangeEnd, Run, Link, SimpleField, BookmarkStart, BookmarkEnd,
+        SDT, Insertion, Deletion, MoveFrom, MoveTo, MoveFromRangeStart, MoveToRangeStart;
+        MoveFromRangeEnd, MoveToRangeEnd
+    }
+}
+
+impl<'a> ParagraphContent<'a> {
+    pub fn iter_text(&self) -> Box<dyn Iterator<Item = &Cow<'a, str>> + '_> {
+        match self {
+            ParagraphContent::Run(run) => run.iter_text(),
+            ParagraphContent::Link(link) => link.iter_text(),
+            ParagraphContent::SimpleField(field) => field.iter_text(),
+            ParagraphContent::SDT(sdt) => sdt.iter_text(),
+            ParagraphContent::Insertion(ins) => ins.iter_text(),
+            ParagraphContent::MoveTo(to) => to.iter_text(),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    pub fn iter_text_mut(&mut self) -> Box<dyn Iterator<Item = &mut Cow<'a, str>> + '_> {
+        match self {
+            ParagraphContent::Run(run) => run.iter_text_mut(),
+            ParagraphContent::Link(link) => link.iter_text_mut(),
+            ParagraphContent::SimpleField(field) => field.iter_text_mut(),
+            ParagraphContent::Insertion(ins) => ins.iter_text_mut(),
+            ParagraphContent::MoveTo(to) => to.iter_text_mut(),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    pub fn replace_text<'b, I, T, S>(&mut self, dic: T) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        match self {
+            ParagraphContent::Run(r) => r.replace_text(dic),
+            ParagraphContent::Link(link) => link.replace_text(dic),
+            ParagraphContent::SimpleField(field) => field.replace_text(dic),
+            ParagraphContent::Insertion(ins) => ins.replace_text(dic),
+            ParagraphContent::MoveTo(to) => to.replace_text(dic),
+            _ => Ok(()),
+        }
+    }
 }
 
 __xml_test_suites!(
@@ -164,4 +424,6 @@ __xml_test_suites!(
     r#"<w:p><w:bookmarkStart/></w:p>"#,
     Paragraph::default().push(BookmarkEnd::default()),
     r#"<w:p><w:bookmarkEnd/></w:p>"#,
+    Paragraph::default().push(Insertion::default().push(Run::default())),
+    r#"<w:p><w:ins w:id="0" w:author=""><w:r/></w:ins></w:p>"#,
 );
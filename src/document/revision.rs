@@ -0,0 +1,1104 @@
+#![allow(unused_must_use)]
+use hard_xml::{XmlRead, XmlWrite};
+use std::borrow::{Borrow, Cow};
+use std::collections::HashSet;
+
+use crate::{
+    __into_owned, __setter, __xml_test_suites,
+    document::{
+        BodyContent, Hyperlink, HyperlinkContent, InstrText, Paragraph, ParagraphContent, Run,
+        RunContent, Table, TableCell, TableCellContent, TableRowContent, Text, VisitMut, SDT,
+    },
+    formatting::{CharacterProperty, ParagraphProperty, SectionProperty},
+};
+
+macro_rules! revision_container {
+    ($(#[$doc:meta])* $tag:literal, $name:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+        #[cfg_attr(test, derive(PartialEq))]
+        #[xml(tag = $tag)]
+        pub struct $name<'a> {
+            /// Specifies a unique identifier for the revision.
+            #[xml(attr = "w:id")]
+            pub id: isize,
+            /// Specifies the author of the revision.
+            #[xml(attr = "w:author")]
+            pub author: Cow<'a, str>,
+            /// Specifies the date and time of the revision.
+            #[xml(attr = "w:date")]
+            pub date: Option<Cow<'a, str>>,
+            /// Specifies the revised content.
+            #[xml(
+                child = "w:commentRangeStart",
+                child = "w:commentRangeEnd",
+                child = "w:r",
+                child = "w:hyperlink",
+                child = "w:fldSimple",
+                child = "w:bookmarkStart",
+                child = "w:bookmarkEnd",
+                child = "w:sdt",
+                child = "w:ins",
+                child = "w:del",
+                child = "w:moveFrom",
+                child = "w:moveTo",
+                child = "w:moveFromRangeStart",
+                child = "w:moveFromRangeEnd",
+                child = "w:moveToRangeStart",
+                child = "w:moveToRangeEnd"
+            )]
+            pub content: Vec<ParagraphContent<'a>>,
+        }
+
+        __into_owned! { $name { author, date, content; id } }
+
+        impl<'a> $name<'a> {
+            __setter!(id: isize);
+            __setter!(author: Cow<'a, str>);
+            __setter!(date: Option<Cow<'a, str>>);
+
+            #[inline(always)]
+            pub fn push<T: Into<ParagraphContent<'a>>>(mut self, content: T) -> Self {
+                self.content.push(content.into());
+                self
+            }
+
+            pub fn text(&self) -> String {
+                self.iter_text()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join("")
+            }
+
+            pub fn iter_text(&self) -> Box<dyn Iterator<Item = &Cow<'a, str>> + '_> {
+                Box::new(
+                    self.content
+                        .iter()
+                        .flat_map(|content| content.iter_text()),
+                )
+            }
+
+            pub fn iter_text_mut(&mut self) -> Box<dyn Iterator<Item = &mut Cow<'a, str>> + '_> {
+                Box::new(
+                    self.content
+                        .iter_mut()
+                        .flat_map(|content| content.iter_text_mut()),
+                )
+            }
+
+            pub fn replace_text<'b, I, T, S>(&mut self, dic: T) -> crate::DocxResult<()>
+            where
+                S: AsRef<str> + 'b,
+                T: IntoIterator<Item = I> + Copy,
+                I: Borrow<(S, S)>,
+            {
+                for content in self.content.iter_mut() {
+                    content.replace_text(dic)?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+revision_container! {
+    /// Inserted Run Content
+    ///
+    /// ```rust
+    /// use docx_rust::document::*;
+    ///
+    /// let ins = Insertion::default()
+    ///     .id(1isize)
+    ///     .author("John")
+    ///     .date("2014-06-25T10:40:00Z")
+    ///     .push(Run::default().push_text("inserted"));
+    /// ```
+    "w:ins", Insertion
+}
+
+revision_container! {
+    /// Deleted Run Content
+    ///
+    /// The runs of a deletion should use `DelText` instead of `Text`.
+    "w:del", Deletion
+}
+
+revision_container! {
+    /// Move Source Run Content
+    "w:moveFrom", MoveFrom
+}
+
+revision_container! {
+    /// Move Destination Run Content
+    "w:moveTo", MoveTo
+}
+
+/// Move Source Location Container - Start
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:moveFromRangeStart")]
+pub struct MoveFromRangeStart<'a> {
+    /// Specifies a unique identifier for the annotation.
+    #[xml(attr = "w:id")]
+    pub id: isize,
+    /// Specifies the name of the move, shared by its source and destination.
+    #[xml(attr = "w:name")]
+    pub name: Option<Cow<'a, str>>,
+    /// Specifies the author of the annotation.
+    #[xml(attr = "w:author")]
+    pub author: Option<Cow<'a, str>>,
+    /// Specifies the date and time of the annotation.
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+}
+
+__into_owned! { MoveFromRangeStart { name, author, date; id } }
+
+/// Move Source Location Container - End
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:moveFromRangeEnd")]
+pub struct MoveFromRangeEnd {
+    /// Specifies the identifier of the matching range start.
+    #[xml(attr = "w:id")]
+    pub id: isize,
+}
+
+/// Move Destination Location Container - Start
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:moveToRangeStart")]
+pub struct MoveToRangeStart<'a> {
+    /// Specifies a unique identifier for the annotation.
+    #[xml(attr = "w:id")]
+    pub id: isize,
+    /// Specifies the name of the move, shared by its source and destination.
+    #[xml(attr = "w:name")]
+    pub name: Option<Cow<'a, str>>,
+    /// Specifies the author of the annotation.
+    #[xml(attr = "w:author")]
+    pub author: Option<Cow<'a, str>>,
+    /// Specifies the date and time of the annotation.
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+}
+
+__into_owned! { MoveToRangeStart { name, author, date; id } }
+
+/// Move Destination Location Container - End
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:moveToRangeEnd")]
+pub struct MoveToRangeEnd {
+    /// Specifies the identifier of the matching range start.
+    #[xml(attr = "w:id")]
+    pub id: isize,
+}
+
+/// Inserted Paragraph Mark or Table Row
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:ins")]
+pub struct InsertionMark<'a> {
+    /// Specifies a unique identifier for the revision.
+    #[xml(attr = "w:id")]
+    pub id: isize,
+    /// Specifies the author of the revision.
+    #[xml(attr = "w:author")]
+    pub author: Cow<'a, str>,
+    /// Specifies the date and time of the revision.
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+}
+
+__into_owned! { InsertionMark { author, date; id } }
+
+/// Deleted Paragraph Mark or Table Row
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:del")]
+pub struct DeletionMark<'a> {
+    /// Specifies a unique identifier for the revision.
+    #[xml(attr = "w:id")]
+    pub id: isize,
+    /// Specifies the author of the revision.
+    #[xml(attr = "w:author")]
+    pub author: Cow<'a, str>,
+    /// Specifies the date and time of the revision.
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+}
+
+__into_owned! { DeletionMark { author, date; id } }
+
+/// Move Source Paragraph Mark
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:moveFrom")]
+pub struct MoveFromMark<'a> {
+    /// Specifies a unique identifier for the revision.
+    #[xml(attr = "w:id")]
+    pub id: isize,
+    /// Specifies the author of the revision.
+    #[xml(attr = "w:author")]
+    pub author: Cow<'a, str>,
+    /// Specifies the date and time of the revision.
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+}
+
+__into_owned! { MoveFromMark { author, date; id } }
+
+/// Move Destination Paragraph Mark
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:moveTo")]
+pub struct MoveToMark<'a> {
+    /// Specifies a unique identifier for the revision.
+    #[xml(attr = "w:id")]
+    pub id: isize,
+    /// Specifies the author of the revision.
+    #[xml(attr = "w:author")]
+    pub author: Cow<'a, str>,
+    /// Specifies the date and time of the revision.
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+}
+
+__into_owned! { MoveToMark { author, date; id } }
+
+/// Records edits as tracked changes
+///
+/// Each revision created through it gets the configured author and date,
+/// and a fresh identifier.
+///
+/// ```rust
+/// use docx_rust::document::*;
+///
+/// let mut track = TrackChanges::new("Bot")
+///     .date("2024-01-01T00:00:00Z")
+///     .next_id(100isize);
+///
+/// let mut para = Paragraph::default().push_text("Dear NAME,");
+/// para.replace_text_tracked(&[("NAME", "Jane")], &mut track).unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TrackChanges<'a> {
+    /// Specifies the author of the revisions.
+    pub author: Cow<'a, str>,
+    /// Specifies the date and time of the revisions.
+    pub date: Option<Cow<'a, str>>,
+    /// Specifies the identifier of the next revision.
+    ///
+    /// Revision ids have to be unique in a document, so for a document which already has
+    /// revisions, start after them with [`Docx::track_changes`].
+    ///
+    /// [`Docx::track_changes`]: crate::Docx::track_changes
+    pub next_id: isize,
+}
+
+impl<'a> TrackChanges<'a> {
+    __setter!(author: Cow<'a, str>);
+    __setter!(date: Option<Cow<'a, str>>);
+    __setter!(next_id: isize);
+
+    pub fn new<T: Into<Cow<'a, str>>>(author: T) -> Self {
+        TrackChanges {
+            author: author.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates an empty `w:ins` revision.
+    pub fn insertion<'b>(&mut self) -> Insertion<'b> {
+        Insertion {
+            id: self.take_id(),
+            author: self.author.to_string().into(),
+            date: self.date.as_ref().map(|date| date.to_string().into()),
+            content: Vec::new(),
+        }
+    }
+
+    /// Creates an empty `w:del` revision.
+    pub fn deletion<'b>(&mut self) -> Deletion<'b> {
+        Deletion {
+            id: self.take_id(),
+            author: self.author.to_string().into(),
+            date: self.date.as_ref().map(|date| date.to_string().into()),
+            content: Vec::new(),
+        }
+    }
+
+    fn take_id(&mut self) -> isize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+}
+
+/// Finds the highest revision id, shifting every revision id by an offset on the way
+///
+/// It covers the `w:ins`, `w:del`, `w:moveFrom` and `w:moveTo` revisions and move ranges
+/// at every level, and the revision marks of paragraphs, runs and table rows.
+#[derive(Default)]
+pub(crate) struct RevisionIds {
+    /// Added to every revision id, 0 to only look for the highest one
+    pub offset: isize,
+    pub max_id: isize,
+}
+
+impl RevisionIds {
+    fn shift(&mut self, id: &mut isize) {
+        *id += self.offset;
+        self.max_id = self.max_id.max(*id);
+    }
+
+    fn shift_marks(&mut self, property: &mut CharacterProperty) {
+        let marks = [
+            property.inserted.as_mut().map(|mark| &mut mark.id),
+            property.deleted.as_mut().map(|mark| &mut mark.id),
+            property.move_from.as_mut().map(|mark| &mut mark.id),
+            property.move_to.as_mut().map(|mark| &mut mark.id),
+            property.r_pr_change.as_mut().map(|change| &mut change.id),
+        ];
+        for id in marks.into_iter().flatten() {
+            self.shift(id);
+        }
+    }
+}
+
+impl<'a> VisitMut<'a> for RevisionIds {
+    fn visit_block(&mut self, block: &mut BodyContent<'a>) {
+        let id = match block {
+            BodyContent::Insertion(ins) => &mut ins.id,
+            BodyContent::Deletion(del) => &mut del.id,
+            BodyContent::MoveFrom(from) => &mut from.id,
+            BodyContent::MoveTo(to) => &mut to.id,
+            BodyContent::MoveFromRangeStart(start) => &mut start.id,
+            BodyContent::MoveFromRangeEnd(end) => &mut end.id,
+            BodyContent::MoveToRangeStart(start) => &mut start.id,
+            BodyContent::MoveToRangeEnd(end) => &mut end.id,
+            _ => return,
+        };
+        self.shift(id);
+    }
+
+    fn visit_inline(&mut self, content: &mut ParagraphContent<'a>) {
+        let id = match content {
+            ParagraphContent::Insertion(ins) => &mut ins.id,
+            ParagraphContent::Deletion(del) => &mut del.id,
+            ParagraphContent::MoveFrom(from) => &mut from.id,
+            ParagraphContent::MoveTo(to) => &mut to.id,
+            ParagraphContent::MoveFromRangeStart(start) => &mut start.id,
+            ParagraphContent::MoveFromRangeEnd(end) => &mut end.id,
+            ParagraphContent::MoveToRangeStart(start) => &mut start.id,
+            ParagraphContent::MoveToRangeEnd(end) => &mut end.id,
+            _ => return,
+        };
+        self.shift(id);
+    }
+
+    fn visit_paragraph(&mut self, paragraph: &mut Paragraph<'a>) {
+        if let Some(property) = &mut paragraph.property {
+            if let Some(change) = &mut property.p_pr_change {
+                self.shift(&mut change.id);
+            }
+            for mark in property.r_pr.iter_mut() {
+                self.shift_marks(mark);
+            }
+        }
+    }
+
+    fn visit_table(&mut self, table: &mut Table<'a>) {
+        for row in table.rows.iter_mut() {
+            let property = &mut row.property;
+            if let Some(mark) = &mut property.inserted {
+                self.shift(&mut mark.id);
+            }
+            if let Some(mark) = &mut property.deleted {
+                self.shift(&mut mark.id);
+            }
+        }
+    }
+
+    fn visit_cell(&mut self, cell: &mut TableCell<'a>) {
+        for content in cell.content.iter_mut() {
+            let id = match content {
+                TableCellContent::MoveFromRangeStart(start) => &mut start.id,
+                TableCellContent::MoveFromRangeEnd(end) => &mut end.id,
+                TableCellContent::MoveToRangeStart(start) => &mut start.id,
+                TableCellContent::MoveToRangeEnd(end) => &mut end.id,
+                _ => continue,
+            };
+            self.shift(id);
+        }
+    }
+
+    fn visit_hyperlink(&mut self, link: &mut Hyperlink<'a>) {
+        for content in link.content.iter_mut() {
+            let id = match content {
+                HyperlinkContent::Insertion(ins) => &mut ins.id,
+                HyperlinkContent::Deletion(del) => &mut del.id,
+                _ => continue,
+            };
+            self.shift(id);
+        }
+    }
+
+    fn visit_run(&mut self, run: &mut Run<'a>) {
+        if let Some(property) = &mut run.property {
+            self.shift_marks(property);
+        }
+    }
+}
+
+/// Selects the revisions affected by [`Docx::accept_revisions`] and [`Docx::reject_revisions`]
+///
+/// An empty filter selects every revision. Dates are compared as ISO 8601 strings,
+/// so the bounds can be given either as a date or as a full timestamp.
+///
+/// Formatting changes of tables, rows and cells (`w:tblPrChange`, `w:trPrChange` and
+/// `w:tcPrChange`) are not modelled: they are dropped when the document is read, so
+/// they can be neither accepted nor rejected.
+///
+/// ```rust
+/// use docx_rust::document::RevisionFilter;
+///
+/// let filter = RevisionFilter::default()
+///     .author("John")
+///     .since("2014-06-01")
+///     .until("2014-07-01T00:00:00Z");
+/// ```
+///
+/// [`Docx::accept_revisions`]: crate::Docx::accept_revisions
+/// [`Docx::reject_revisions`]: crate::Docx::reject_revisions
+#[derive(Debug, Default, Clone)]
+pub struct RevisionFilter<'a> {
+    /// Selects only the revisions made by this author.
+    pub author: Option<Cow<'a, str>>,
+    /// Selects only the revisions made at or after this date.
+    pub since: Option<Cow<'a, str>>,
+    /// Selects only the revisions made before this date.
+    pub until: Option<Cow<'a, str>>,
+}
+
+impl<'a> RevisionFilter<'a> {
+    __setter!(author: Option<Cow<'a, str>>);
+    __setter!(since: Option<Cow<'a, str>>);
+    __setter!(until: Option<Cow<'a, str>>);
+
+    /// Returns whether a revision with the given author and date is selected.
+    ///
+    /// A revision without a date is never selected by a date bound.
+    pub fn matches(&self, author: &str, date: Option<&str>) -> bool {
+        if matches!(&self.author, Some(expected) if expected != author) {
+            return false;
+        }
+        if let Some(since) = &self.since {
+            if date.is_none_or(|date| date < since.as_ref()) {
+                return false;
+            }
+        }
+        if let Some(until) = &self.until {
+            if date.is_none_or(|date| date >= until.as_ref()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Accepts or rejects the revisions selected by a filter, rewriting the content in place.
+pub(crate) struct RevisionResolver<'f> {
+    filter: &'f RevisionFilter<'f>,
+    accept: bool,
+    /// Identifiers of the move ranges whose start marker was removed.
+    move_ranges: HashSet<isize>,
+}
+
+impl<'f> RevisionResolver<'f> {
+    pub fn new(filter: &'f RevisionFilter<'f>, accept: bool) -> Self {
+        RevisionResolver {
+            filter,
+            accept,
+            move_ranges: HashSet::new(),
+        }
+    }
+
+    fn matches(&self, author: &str, date: &Option<Cow<'_, str>>) -> bool {
+        self.filter.matches(author, date.as_deref())
+    }
+
+    pub fn resolve_blocks<'a>(&mut self, content: &mut Vec<BodyContent<'a>>) {
+        let mut items = Vec::with_capacity(content.len());
+        for item in content.drain(..) {
+            match item {
+                BodyContent::Paragraph(mut para) => {
+                    let removed = self.resolve_paragraph(&mut para);
+                    items.push((BodyContent::Paragraph(para), removed));
+                }
+                BodyContent::Table(mut table) => {
+                    if self.resolve_table(&mut table) {
+                        items.push((table.into(), false));
+                    }
+                }
+                BodyContent::Sdt(mut sdt) => {
+                    self.resolve_sdt(&mut sdt);
+                    items.push((sdt.into(), false));
+                }
+                BodyContent::SectionProperty(mut sect) => {
+                    self.resolve_section_property(&mut sect);
+                    items.push((sect.into(), false));
+                }
+                BodyContent::TableCell(mut cell) => {
+                    self.resolve_cell(&mut cell);
+                    items.push((cell.into(), false));
+                }
+                BodyContent::Run(mut run) => {
+                    self.resolve_run(&mut run);
+                    items.push((run.into(), false));
+                }
+                BodyContent::Insertion(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        items.push((rev.into(), false));
+                    } else if self.accept {
+                        items.extend(into_blocks(rev.content).map(|item| (item, false)));
+                    }
+                }
+                BodyContent::MoveTo(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        items.push((rev.into(), false));
+                    } else if self.accept {
+                        items.extend(into_blocks(rev.content).map(|item| (item, false)));
+                    }
+                }
+                BodyContent::Deletion(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        items.push((rev.into(), false));
+                    } else if !self.accept {
+                        restore_deleted_text(&mut rev.content);
+                        items.extend(into_blocks(rev.content).map(|item| (item, false)));
+                    }
+                }
+                BodyContent::MoveFrom(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        items.push((rev.into(), false));
+                    } else if !self.accept {
+                        restore_deleted_text(&mut rev.content);
+                        items.extend(into_blocks(rev.content).map(|item| (item, false)));
+                    }
+                }
+                BodyContent::MoveFromRangeStart(start) => {
+                    if self.remove_range_start(start.id, &start.author, &start.date) {
+                        items.push((start.into(), false));
+                    }
+                }
+                BodyContent::MoveToRangeStart(start) => {
+                    if self.remove_range_start(start.id, &start.author, &start.date) {
+                        items.push((start.into(), false));
+                    }
+                }
+                BodyContent::MoveFromRangeEnd(end) => {
+                    if !self.move_ranges.contains(&end.id) {
+                        items.push((end.into(), false));
+                    }
+                }
+                BodyContent::MoveToRangeEnd(end) => {
+                    if !self.move_ranges.contains(&end.id) {
+                        items.push((end.into(), false));
+                    }
+                }
+            }
+        }
+        *content = join_paragraphs(items, |item| match item {
+            BodyContent::Paragraph(para) => Some(para),
+            _ => None,
+        });
+    }
+
+    /// Returns `false` if the range start is selected, remembering its identifier
+    /// so that the matching range end is removed as well.
+    fn remove_range_start(
+        &mut self,
+        id: isize,
+        author: &Option<Cow<'_, str>>,
+        date: &Option<Cow<'_, str>>,
+    ) -> bool {
+        if self.matches(author.as_deref().unwrap_or_default(), date) {
+            self.move_ranges.insert(id);
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Returns `true` if the paragraph mark is removed, in which case the paragraph
+    /// has to be joined with the one that follows it.
+    fn resolve_paragraph(&mut self, para: &mut Paragraph<'_>) -> bool {
+        let mut removed = false;
+        if let Some(prop) = &mut para.property {
+            self.resolve_paragraph_property(prop);
+            for r_pr in prop.r_pr.iter_mut() {
+                removed |= self.resolve_paragraph_mark(r_pr);
+                self.resolve_character_property(r_pr);
+            }
+        }
+        self.resolve_inline(&mut para.content);
+        removed
+    }
+
+    fn resolve_paragraph_mark(&self, prop: &mut CharacterProperty<'_>) -> bool {
+        let mut removed = false;
+        if let Some(mark) = prop.inserted.take() {
+            if self.matches(&mark.author, &mark.date) {
+                removed |= !self.accept;
+            } else {
+                prop.inserted = Some(mark);
+            }
+        }
+        if let Some(mark) = prop.move_to.take() {
+            if self.matches(&mark.author, &mark.date) {
+                removed |= !self.accept;
+            } else {
+                prop.move_to = Some(mark);
+            }
+        }
+        if let Some(mark) = prop.deleted.take() {
+            if self.matches(&mark.author, &mark.date) {
+                removed |= self.accept;
+            } else {
+                prop.deleted = Some(mark);
+            }
+        }
+        if let Some(mark) = prop.move_from.take() {
+            if self.matches(&mark.author, &mark.date) {
+                removed |= self.accept;
+            } else {
+                prop.move_from = Some(mark);
+            }
+        }
+        removed
+    }
+
+    fn resolve_paragraph_property(&mut self, prop: &mut ParagraphProperty<'_>) {
+        if let Some(sect) = &mut prop.section_property {
+            self.resolve_section_property(sect);
+        }
+        if let Some(change) = prop.p_pr_change.take() {
+            if !self.matches(&change.author, &change.date) {
+                prop.p_pr_change = Some(change);
+            } else if !self.accept {
+                *prop = ParagraphProperty {
+                    r_pr: std::mem::take(&mut prop.r_pr),
+                    section_property: prop.section_property.take(),
+                    ..change.previous_property.unwrap_or_default().into()
+                };
+            }
+        }
+    }
+
+    fn resolve_character_property(&self, prop: &mut CharacterProperty<'_>) {
+        if let Some(change) = prop.r_pr_change.take() {
+            if !self.matches(&change.author, &change.date) {
+                prop.r_pr_change = Some(change);
+            } else if !self.accept {
+                *prop = CharacterProperty {
+                    inserted: prop.inserted.take(),
+                    deleted: prop.deleted.take(),
+                    move_from: prop.move_from.take(),
+                    move_to: prop.move_to.take(),
+                    ..change.previous_property.unwrap_or_default().into()
+                };
+            }
+        }
+    }
+
+    fn resolve_section_property(&self, prop: &mut SectionProperty<'_>) {
+        if let Some(change) = prop.revision.take() {
+            if !self.matches(&change.author, &change.date) {
+                prop.revision = Some(change);
+            } else if !self.accept {
+                *prop = SectionProperty {
+                    header_footer_references: std::mem::take(&mut prop.header_footer_references),
+                    ..change.section_property.unwrap_or_default().into()
+                };
+            }
+        }
+    }
+
+    fn resolve_inline<'a>(&mut self, content: &mut Vec<ParagraphContent<'a>>) {
+        let items = std::mem::take(content);
+        for item in items {
+            match item {
+                ParagraphContent::Run(mut run) => {
+                    self.resolve_run(&mut run);
+                    content.push(run.into());
+                }
+                ParagraphContent::Link(mut link) => {
+                    self.resolve_link(&mut link);
+                    content.push(link.into());
+                }
+                ParagraphContent::SimpleField(mut field) => {
+                    for run in field.content.iter_mut() {
+                        self.resolve_run(run);
+                    }
+                    content.push(field.into());
+                }
+                ParagraphContent::SDT(mut sdt) => {
+                    self.resolve_sdt(&mut sdt);
+                    content.push(sdt.into());
+                }
+                ParagraphContent::Insertion(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        content.push(rev.into());
+                    } else if self.accept {
+                        content.append(&mut rev.content);
+                    }
+                }
+                ParagraphContent::MoveTo(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        content.push(rev.into());
+                    } else if self.accept {
+                        content.append(&mut rev.content);
+                    }
+                }
+                ParagraphContent::Deletion(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        content.push(rev.into());
+                    } else if !self.accept {
+                        restore_deleted_text(&mut rev.content);
+                        content.append(&mut rev.content);
+                    }
+                }
+                ParagraphContent::MoveFrom(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        content.push(rev.into());
+                    } else if !self.accept {
+                        restore_deleted_text(&mut rev.content);
+                        content.append(&mut rev.content);
+                    }
+                }
+                ParagraphContent::MoveFromRangeStart(start) => {
+                    if self.remove_range_start(start.id, &start.author, &start.date) {
+                        content.push(start.into());
+                    }
+                }
+                ParagraphContent::MoveToRangeStart(start) => {
+                    if self.remove_range_start(start.id, &start.author, &start.date) {
+                        content.push(start.into());
+                    }
+                }
+                ParagraphContent::MoveFromRangeEnd(end) => {
+                    if !self.move_ranges.contains(&end.id) {
+                        content.push(end.into());
+                    }
+                }
+                ParagraphContent::MoveToRangeEnd(end) => {
+                    if !self.move_ranges.contains(&end.id) {
+                        content.push(end.into());
+                    }
+                }
+                item => content.push(item),
+            }
+        }
+    }
+
+    fn resolve_link(&mut self, link: &mut Hyperlink<'_>) {
+        for item in std::mem::take(&mut link.content) {
+            match item {
+                HyperlinkContent::Run(mut run) => {
+                    self.resolve_run(&mut run);
+                    link.content.push(run.into());
+                }
+                HyperlinkContent::Insertion(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        link.content.push(rev.into());
+                    } else if self.accept {
+                        link.content.extend(
+                            rev.content
+                                .into_iter()
+                                .filter_map(HyperlinkContent::from_inline),
+                        );
+                    }
+                }
+                HyperlinkContent::Deletion(mut rev) => {
+                    self.resolve_inline(&mut rev.content);
+                    if !self.matches(&rev.author, &rev.date) {
+                        link.content.push(rev.into());
+                    } else if !self.accept {
+                        restore_deleted_text(&mut rev.content);
+                        link.content.extend(
+                            rev.content
+                                .into_iter()
+                                .filter_map(HyperlinkContent::from_inline),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn resolve_run(&self, run: &mut Run<'_>) {
+        if let Some(prop) = &mut run.property {
+            self.resolve_character_property(prop);
+        }
+    }
+
+    fn resolve_sdt(&mut self, sdt: &mut SDT<'_>) {
+        if let Some(content) = &mut sdt.content {
+            self.resolve_blocks(&mut content.content);
+        }
+    }
+
+    /// Returns `false` if every row of the table was removed, in which case the table has
+    /// to be removed as well.
+    fn resolve_table(&mut self, table: &mut Table<'_>) -> bool {
+        table.rows.retain_mut(|row| {
+            if let Some(mark) = row.property.inserted.take() {
+                if !self.matches(&mark.author, &mark.date) {
+                    row.property.inserted = Some(mark);
+                } else if !self.accept {
+                    return false;
+                }
+            }
+            if let Some(mark) = row.property.deleted.take() {
+                if !self.matches(&mark.author, &mark.date) {
+                    row.property.deleted = Some(mark);
+                } else if self.accept {
+                    return false;
+                }
+            }
+            true
+        });
+        for row in table.rows.iter_mut() {
+            for cell in row.cells.iter_mut() {
+                match cell {
+                    TableRowContent::TableCell(cell) => self.resolve_cell(cell),
+                    TableRowContent::SDT(sdt) => self.resolve_sdt(sdt),
+                }
+            }
+        }
+        !table.rows.is_empty()
+    }
+
+    fn resolve_cell(&mut self, cell: &mut TableCell<'_>) {
+        let mut items = Vec::with_capacity(cell.content.len());
+        for item in cell.content.drain(..) {
+            match item {
+                TableCellContent::Paragraph(mut para) => {
+                    let removed = self.resolve_paragraph(&mut para);
+                    items.push((TableCellContent::Paragraph(para), removed));
+                }
+                TableCellContent::Table(mut table) => {
+                    if self.resolve_table(&mut table) {
+                        items.push((table.into(), false));
+                    }
+                }
+                TableCellContent::SDT(mut sdt) => {
+                    self.resolve_sdt(&mut sdt);
+                    items.push((sdt.into(), false));
+                }
+                TableCellContent::MoveFromRangeStart(start) => {
+                    if self.remove_range_start(start.id, &start.author, &start.date) {
+                        items.push((start.into(), false));
+                    }
+                }
+                TableCellContent::MoveToRangeStart(start) => {
+                    if self.remove_range_start(start.id, &start.author, &start.date) {
+                        items.push((start.into(), false));
+                    }
+                }
+                TableCellContent::MoveFromRangeEnd(end) => {
+                    if !self.move_ranges.contains(&end.id) {
+                        items.push((end.into(), false));
+                    }
+                }
+                TableCellContent::MoveToRangeEnd(end) => {
+                    if !self.move_ranges.contains(&end.id) {
+                        items.push((end.into(), false));
+                    }
+                }
+                item => items.push((item, false)),
+            }
+        }
+        cell.content = join_paragraphs(items, |item| match item {
+            TableCellContent::Paragraph(para) => Some(para),
+            _ => None,
+        });
+    }
+}
+
+/// Joins every paragraph whose mark was removed with the paragraph that follows it.
+///
+/// The joined paragraph keeps the properties of the following paragraph, as its mark survives.
+fn join_paragraphs<'a, T>(
+    items: Vec<(T, bool)>,
+    as_paragraph: impl Fn(&mut T) -> Option<&mut Paragraph<'a>>,
+) -> Vec<T> {
+    let mut content = Vec::with_capacity(items.len());
+    let mut pending: Option<T> = None;
+    for (mut item, removed) in items {
+        match as_paragraph(&mut item) {
+            Some(para) => {
+                if let Some(prev) = pending.take().as_mut().and_then(&as_paragraph) {
+                    prev.content.append(&mut para.content);
+                    para.content = std::mem::take(&mut prev.content);
+                    // don't lose a section break carried by the removed mark
+                    if let Some(sect) = prev.property.take().and_then(|prop| prop.section_property)
+                    {
+                        para.property
+                            .get_or_insert_with(Default::default)
+                            .section_property
+                            .get_or_insert(sect);
+                    }
+                }
+                if removed {
+                    pending = Some(item);
+                    continue;
+                }
+            }
+            None => content.extend(pending.take()),
+        }
+        content.push(item);
+    }
+    content.extend(pending);
+    content
+}
+
+/// Converts the run content of a block-level revision into block content.
+///
+/// Hyperlinks, fields, bookmarks and comment ranges can't stand at the block level, so
+/// content holding any of them is kept together in a paragraph.
+fn into_blocks<'a>(content: Vec<ParagraphContent<'a>>) -> impl Iterator<Item = BodyContent<'a>> {
+    let inline = content.iter().any(|item| {
+        matches!(
+            item,
+            ParagraphContent::Link(_)
+                | ParagraphContent::SimpleField(_)
+                | ParagraphContent::BookmarkStart(_)
+                | ParagraphContent::BookmarkEnd(_)
+                | ParagraphContent::CommentRangeStart(_)
+                | ParagraphContent::CommentRangeEnd(_)
+        )
+    });
+    if inline {
+        let para = Paragraph {
+            content,
+            ..Default::default()
+        };
+        return vec![para.into()].into_iter();
+    }
+    content
+        .into_iter()
+        .map(|item| match item {
+            ParagraphContent::Run(run) => run.into(),
+            ParagraphContent::SDT(sdt) => sdt.into(),
+            ParagraphContent::Insertion(rev) => rev.into(),
+            ParagraphContent::Deletion(rev) => rev.into(),
+            ParagraphContent::MoveFrom(rev) => rev.into(),
+            ParagraphContent::MoveTo(rev) => rev.into(),
+            ParagraphContent::MoveFromRangeStart(start) => start.into(),
+            ParagraphContent::MoveFromRangeEnd(end) => end.into(),
+            ParagraphContent::MoveToRangeStart(start) => start.into(),
+            ParagraphContent::MoveToRangeEnd(end) => end.into(),
+            item @ (ParagraphContent::Link(_)
+            | ParagraphContent::SimpleField(_)
+            | ParagraphContent::BookmarkStart(_)
+            | ParagraphContent::BookmarkEnd(_)
+            | ParagraphContent::CommentRangeStart(_)
+            | ParagraphContent::CommentRangeEnd(_)) => Paragraph::default().push(item).into(),
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Turns the deleted text and field codes of restored content back into literal ones.
+///
+/// Insertions and moves nested in the restored content were deleted along with it, so they
+/// are restored too, while the deletions left in place by the filter stay deleted.
+fn restore_deleted_text(content: &mut [ParagraphContent<'_>]) {
+    for item in content.iter_mut() {
+        match item {
+            ParagraphContent::Run(run) => restore_deleted_run(run),
+            ParagraphContent::Link(link) => {
+                for content in link.content.iter_mut() {
+                    match content {
+                        HyperlinkContent::Run(run) => restore_deleted_run(run),
+                        HyperlinkContent::Insertion(rev) => restore_deleted_text(&mut rev.content),
+                        HyperlinkContent::Deletion(_) => {}
+                    }
+                }
+            }
+            ParagraphContent::SimpleField(field) => {
+                field.content.iter_mut().for_each(restore_deleted_run);
+            }
+            ParagraphContent::SDT(sdt) => restore_deleted_sdt(sdt),
+            ParagraphContent::Insertion(rev) => restore_deleted_text(&mut rev.content),
+            ParagraphContent::MoveTo(rev) => restore_deleted_text(&mut rev.content),
+            _ => {}
+        }
+    }
+}
+
+fn restore_deleted_sdt(sdt: &mut SDT<'_>) {
+    for block in sdt.content.iter_mut().flat_map(|c| c.content.iter_mut()) {
+        match block {
+            BodyContent::Paragraph(para) => restore_deleted_text(&mut para.content),
+            BodyContent::Run(run) => restore_deleted_run(run),
+            BodyContent::Sdt(sdt) => restore_deleted_sdt(sdt),
+            BodyContent::Insertion(rev) => restore_deleted_text(&mut rev.content),
+            BodyContent::MoveTo(rev) => restore_deleted_text(&mut rev.content),
+            _ => {}
+        }
+    }
+}
+
+fn restore_deleted_run(run: &mut Run<'_>) {
+    for item in run.content.iter_mut() {
+        match item {
+            RunContent::DelText(del) => {
+                *item = RunContent::Text(Text {
+                    space: del.space.take(),
+                    text: std::mem::take(&mut del.text),
+                });
+            }
+            RunContent::DelInstrText(del) => {
+                *item = RunContent::InstrText(InstrText {
+                    space: del.space.take(),
+                    text: std::mem::take(&mut del.text),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+use crate::document::DelText;
+
+__xml_test_suites!(
+    Insertion,
+    Insertion::default(),
+    r#"<w:ins w:id="0" w:author=""/>"#,
+    Insertion::default()
+        .id(1isize)
+        .author("John")
+        .date("2014-06-25T10:40:00Z")
+        .push(Run::default().push_text("text")),
+    r#"<w:ins w:id="1" w:author="John" w:date="2014-06-25T10:40:00Z"><w:r><w:t>text</w:t></w:r></w:ins>"#,
+    Insertion::default().author("John").push(
+        Deletion::default()
+            .author("Jane")
+            .push(Run::default().push(DelText::from("text")))
+    ),
+    r#"<w:ins w:id="0" w:author="John"><w:del w:id="0" w:author="Jane"><w:r><w:delText>text</w:delText></w:r></w:del></w:ins>"#,
+);
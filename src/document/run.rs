@@ -4,13 +4,13 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::{Borrow, Cow};
 
 use crate::{
-    __setter, __xml_test_suites,
+    __define_enum, __define_struct, __into_owned, __setter, __xml_test_suites,
     document::{
         drawing::Drawing, field_char::FieldChar, instrtext::InstrText, r#break::Break,
         r#break::LastRenderedPageBreak, tab::Tab, text::Text,
     },
     formatting::CharacterProperty,
-    DocxResult, __define_enum, __define_struct,
+    DocxResult,
 };
 
 use super::{
@@ -18,7 +18,7 @@ use super::{
     instrtext::DelInstrText,
     sym::Sym,
     AnnotationRef, CarriageReturn, CommentReference, DelText, EndnoteRef, EndnoteReference,
-    FootnoteRef, FootnoteReference,
+    FootnoteRef, FootnoteReference, ParagraphContent, TextSpace, TrackChanges,
 };
 
 /// Run
@@ -87,6 +87,8 @@ pub struct Run<'a> {
     pub content: Vec<RunContent<'a>>,
 }
 
+__into_owned! { Run { rsid_r, rsid_r_default, property, content } }
+
 impl<'a> Run<'a> {
     __setter!(property: Option<CharacterProperty<'a>>);
 
@@ -175,6 +177,105 @@ impl<'a> Run<'a> {
 
         Ok(())
     }
+
+    /// Replaces text like [`Run::replace_text`], but records each replacement as a tracked change
+    ///
+    /// Returns `None` if nothing matches. Otherwise returns the content that takes the place
+    /// of this run: the unchanged parts as runs with the same properties, and every match as a
+    /// `w:del` of the old text followed by a `w:ins` of the new text.
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> Option<Vec<ParagraphContent<'a>>>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        let with_content = |content: Vec<RunContent<'a>>| Run {
+            rsid_r: self.rsid_r.clone(),
+            rsid_r_default: self.rsid_r_default.clone(),
+            property: self.property.clone(),
+            content,
+        };
+
+        let mut result = Vec::new();
+        let mut current = Vec::new();
+        let mut matched = false;
+
+        for c in self.content.iter() {
+            let RunContent::Text(t) = c else {
+                current.push(c.clone());
+                continue;
+            };
+            let mut rest: &str = &t.text;
+            while let Some((pos, old, new)) = find_first_match(rest, dic) {
+                matched = true;
+                if pos > 0 {
+                    current.push(RunContent::Text(text_with_space(&rest[..pos])));
+                }
+                if !current.is_empty() {
+                    result.push(with_content(std::mem::take(&mut current)).into());
+                }
+                let del = DelText::from((rest[pos..pos + old].to_string(), TextSpace::Preserve));
+                result.push(track.deletion().push(with_content(vec![del.into()])).into());
+                if !new.is_empty() {
+                    let text = text_with_space(&new);
+                    result.push(
+                        track
+                            .insertion()
+                            .push(with_content(vec![text.into()]))
+                            .into(),
+                    );
+                }
+                rest = &rest[pos + old..];
+            }
+            if !rest.is_empty() {
+                current.push(RunContent::Text(text_with_space(rest)));
+            }
+        }
+
+        if !matched {
+            return None;
+        }
+        if !current.is_empty() {
+            result.push(with_content(current).into());
+        }
+        Some(result)
+    }
+}
+
+/// Finds the earliest occurrence of any of the patterns, returning its position,
+/// the length of the matched text and the replacement.
+pub(crate) fn find_first_match<I, T, S>(text: &str, dic: T) -> Option<(usize, usize, String)>
+where
+    S: AsRef<str>,
+    T: IntoIterator<Item = I>,
+    I: Borrow<(S, S)>,
+{
+    let mut found: Option<(usize, usize, String)> = None;
+    for p in dic {
+        let (old, new) = p.borrow();
+        let old = old.as_ref();
+        if old.is_empty() {
+            continue;
+        }
+        if let Some(pos) = text.find(old) {
+            if found.as_ref().is_none_or(|(first, _, _)| pos < *first) {
+                found = Some((pos, old.len(), new.as_ref().to_string()));
+            }
+        }
+    }
+    found
+}
+
+fn text_with_space<'a>(text: &str) -> Text<'a> {
+    if text.starts_with(char::is_whitespace) || text.ends_with(char::is_whitespace) {
+        Text::from((text.to_string(), TextSpace::Preserve))
+    } else {
+        Text::from(text.to_string())
+    }
 }
 
 /// A set of elements that can be contained as the content of a run.
@@ -247,6 +348,16 @@ pub enum RunContent<'a> {
     LastRenderedPageBreak(LastRenderedPageBreak),
 }
 
+__into_owned! {
+    enum RunContent {
+        Text, DelText, InstrText, DelInstrText, Sym, FootnoteReference, EndnoteReference,
+        CommentReference, Drawing;
+        Break, NoBreakHyphen, SoftHyphen, DayShort, MonthShort, YearShort, DayLong, MonthLong,
+        YearLong, AnnotationRef, FootnoteRef, EndnoteRef, Separator, ContinuationSeparator, PgNum,
+        CarriageReturn, Tab, FieldChar, PTab, LastRenderedPageBreak
+    }
+}
+
 __define_struct! {
     ("w:ptab", PTab) {
         "w:alignment", alignment,	PTabAlignment	//Positional Tab Stop Alignment
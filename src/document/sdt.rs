@@ -1,11 +1,11 @@
 #![allow(unused_must_use)]
-use std::borrow::Cow;
+use std::borrow::{Borrow, Cow};
 
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__setter, __xml_test_suites};
+use crate::{__into_owned, __setter, __xml_test_suites};
 
-use super::BodyContent;
+use super::{body::replace_blocks_tracked, BodyContent, TrackChanges};
 
 /// SDT
 ///
@@ -31,6 +31,8 @@ pub struct SDT<'a> {
     pub content: Option<SDTContent<'a>>,
 }
 
+__into_owned! { SDT { property, content; end_property } }
+
 impl<'a> SDT<'a> {
     __setter!(property: Option<SDTProperty<'a>>);
     __setter!(end_property: Option<SDTEndProperty>);
@@ -46,6 +48,45 @@ impl<'a> SDT<'a> {
         )
     }
 
+    pub fn iter_text_mut(&mut self) -> Box<dyn Iterator<Item = &mut Cow<'a, str>> + '_> {
+        Box::new(
+            self.content
+                .as_mut()
+                .map(|content| content.iter_text_mut())
+                .into_iter()
+                .flatten(),
+        )
+    }
+
+    pub fn replace_text<'b, I, T, S>(&mut self, dic: T) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        if let Some(content) = &mut self.content {
+            content.replace_text(dic)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces text like [`SDT::replace_text`], but records each replacement as a tracked change
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        if let Some(content) = &mut self.content {
+            replace_blocks_tracked(&mut content.content, dic, track)?;
+        }
+        Ok(())
+    }
+
     pub fn text(&self) -> String {
         self.iter_text()
             .map(|c| c.to_string())
@@ -66,6 +107,8 @@ pub struct SDTProperty<'a> {
     pub doc_part_obj: Option<DocPartObj<'a>>,
 }
 
+__into_owned! { SDTProperty { doc_part_obj; id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:id")]
@@ -84,6 +127,8 @@ pub struct DocPartObj<'a> {
     pub doc_part_unique: Option<DocPartUnique>,
 }
 
+__into_owned! { DocPartObj { doc_part_gallery; doc_part_unique } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:docPartGallery")]
@@ -92,6 +137,8 @@ pub struct DocPartGallery<'a> {
     pub name: Option<Cow<'a, str>>,
 }
 
+__into_owned! { DocPartGallery { name } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:docPartUnique")]
@@ -112,11 +159,21 @@ pub struct SDTContent<'a> {
         child = "w:tbl",
         child = "w:sectPr",
         child = "w:sdt",
-        child = "w:r"
+        child = "w:r",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:moveFromRangeStart",
+        child = "w:moveFromRangeEnd",
+        child = "w:moveToRangeStart",
+        child = "w:moveToRangeEnd"
     )]
     pub content: Vec<BodyContent<'a>>,
 }
 
+__into_owned! { SDTContent { content } }
+
 impl<'a> SDTContent<'a> {
     pub fn text(&self) -> String {
         self.iter_text()
@@ -131,15 +188,59 @@ impl<'a> SDTContent<'a> {
                 .iter()
                 .filter_map(|content| match content {
                     BodyContent::Paragraph(para) => Some(para.iter_text()),
-                    BodyContent::Table(_) => None,
+                    BodyContent::Table(table) => Some(Box::new(table.iter_text())),
                     BodyContent::SectionProperty(_) => None,
                     BodyContent::Sdt(sdt) => Some(sdt.iter_text()),
-                    BodyContent::TableCell(_) => None,
+                    BodyContent::TableCell(tc) => Some(tc.iter_text()),
                     BodyContent::Run(run) => Some(run.iter_text()),
+                    BodyContent::Insertion(ins) => Some(ins.iter_text()),
+                    BodyContent::MoveTo(to) => Some(to.iter_text()),
+                    _ => None,
+                })
+                .flatten(),
+        )
+    }
+
+    pub fn iter_text_mut(&mut self) -> Box<dyn Iterator<Item = &mut Cow<'a, str>> + '_> {
+        Box::new(
+            self.content
+                .iter_mut()
+                .filter_map(|content| -> Option<Box<dyn Iterator<Item = _>>> {
+                    match content {
+                        BodyContent::Paragraph(para) => Some(Box::new(para.iter_text_mut())),
+                        BodyContent::Table(table) => Some(Box::new(table.iter_text_mut())),
+                        BodyContent::Sdt(sdt) => Some(sdt.iter_text_mut()),
+                        BodyContent::TableCell(tc) => Some(tc.iter_text_mut()),
+                        BodyContent::Run(run) => Some(run.iter_text_mut()),
+                        BodyContent::Insertion(ins) => Some(ins.iter_text_mut()),
+                        BodyContent::MoveTo(to) => Some(to.iter_text_mut()),
+                        _ => None,
+                    }
                 })
                 .flatten(),
         )
     }
+
+    pub fn replace_text<'b, I, T, S>(&mut self, dic: T) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        for content in self.content.iter_mut() {
+            match content {
+                BodyContent::Paragraph(para) => para.replace_text(dic)?,
+                BodyContent::Table(table) => table.replace_text(dic)?,
+                BodyContent::Sdt(sdt) => sdt.replace_text(dic)?,
+                BodyContent::TableCell(tc) => tc.replace_text(dic)?,
+                BodyContent::Run(run) => run.replace_text(dic)?,
+                BodyContent::Insertion(ins) => ins.replace_text(dic)?,
+                BodyContent::MoveTo(to) => to.replace_text(dic)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
 }
 
 __xml_test_suites!(SDT, SDT::default(), "<w:sdt/>",);
@@ -2,6 +2,8 @@ use std::borrow::Cow;
 
 use hard_xml::{XmlRead, XmlWrite};
 
+use crate::__into_owned;
+
 /// Symbol Character
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -12,3 +14,5 @@ pub struct Sym<'a> {
     #[xml(attr = "w:char")]
     pub char: Option<Cow<'a, str>>,
 }
+
+__into_owned! { Sym { font, char } }
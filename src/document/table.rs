@@ -1,10 +1,11 @@
 #![allow(unused_must_use)]
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
 
 use crate::{
-    __setter, __xml_test_suites,
-    document::{TableGrid, TableRow},
+    __into_owned, __setter, __xml_test_suites,
+    document::{TableCell, TableGrid, TableRow, TrackChanges},
     formatting::TableProperty,
 };
 
@@ -30,6 +31,8 @@ pub struct Table<'a> {
     pub rows: Vec<TableRow<'a>>,
 }
 
+__into_owned! { Table { property, rows; grids } }
+
 impl<'a> Table<'a> {
     __setter!(property: TableProperty<'a>);
 
@@ -59,6 +62,96 @@ impl<'a> Table<'a> {
         }
         Ok(())
     }
+
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        for row in self.rows.iter_mut() {
+            row.replace_text_tracked(dic, track)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the logical grid of the table
+    ///
+    /// Each row lists the cells which start in it, with the spans of merged cells resolved:
+    /// `w:gridSpan` and `w:hMerge` widen a cell, and the cells which continue a `w:vMerge`
+    /// extend the cell above them instead of appearing on their own. The cells of a row start
+    /// after the grid columns its `w:gridBefore` skips.
+    pub fn logical_grid(&self) -> Vec<Vec<GridCell<'_, 'a>>> {
+        let mut grid: Vec<Vec<GridCell>> = Vec::with_capacity(self.rows.len());
+        // vertically merged regions by grid column, as (row, index in row) of their first cell
+        let mut open: HashMap<usize, (usize, usize)> = HashMap::new();
+
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let mut cells: Vec<GridCell> = Vec::new();
+            let mut still_open = HashMap::new();
+            let mut column = row.property.columns_before();
+
+            for cell in row.iter_cells() {
+                let prop = &cell.property;
+                let span = prop.column_span();
+
+                let continues_h_merge = prop.h_merge.as_ref().is_some_and(|m| !m.is_restart());
+                if let (true, Some(last)) = (continues_h_merge, cells.last_mut()) {
+                    last.column_span += span;
+                    column += span;
+                    continue;
+                }
+
+                let v_merge = prop.v_merge.as_ref();
+                if v_merge.is_some_and(|m| !m.is_restart()) {
+                    if let Some(&(first_row, index)) = open.get(&column) {
+                        grid[first_row][index].row_span = row_index - first_row + 1;
+                        still_open.insert(column, (first_row, index));
+                        column += span;
+                        continue;
+                    }
+                }
+
+                if v_merge.is_some() {
+                    still_open.insert(column, (row_index, cells.len()));
+                }
+                cells.push(GridCell {
+                    cell,
+                    row: row_index,
+                    column,
+                    row_span: 1,
+                    column_span: span,
+                });
+                column += span;
+            }
+
+            grid.push(cells);
+            open = still_open;
+        }
+
+        grid
+    }
+}
+
+/// A cell of the logical grid of a table
+///
+/// See [`Table::logical_grid`].
+#[derive(Debug, Clone)]
+pub struct GridCell<'t, 'a> {
+    /// The cell at the top left of the merged region.
+    pub cell: &'t TableCell<'a>,
+    /// Index of the first row covered by the cell.
+    pub row: usize,
+    /// Index of the first grid column covered by the cell.
+    pub column: usize,
+    /// Number of rows covered by the cell.
+    pub row_span: usize,
+    /// Number of grid columns covered by the cell.
+    pub column_span: usize,
 }
 
 __xml_test_suites!(
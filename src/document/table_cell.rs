@@ -4,7 +4,14 @@ use std::borrow::{Borrow, Cow};
 use derive_more::From;
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__setter, __xml_test_suites, document::Paragraph, formatting::TableCellProperty};
+use crate::{
+    __into_owned, __setter, __xml_test_suites,
+    document::{
+        BookmarkEnd, BookmarkStart, CommentRangeEnd, CommentRangeStart, MoveFromRangeEnd,
+        MoveFromRangeStart, MoveToRangeEnd, MoveToRangeStart, Paragraph, Table, TrackChanges, SDT,
+    },
+    formatting::TableCellProperty,
+};
 
 /// Table Cell
 ///
@@ -22,13 +29,27 @@ use crate::{__setter, __xml_test_suites, document::Paragraph, formatting::TableC
 #[xml(tag = "w:tc")]
 pub struct TableCell<'a> {
     #[xml(default, child = "w:tcPr")]
-    pub property: TableCellProperty,
-    #[xml(child = "w:p")]
+    pub property: TableCellProperty<'a>,
+    #[xml(
+        child = "w:p",
+        child = "w:tbl",
+        child = "w:sdt",
+        child = "w:bookmarkStart",
+        child = "w:bookmarkEnd",
+        child = "w:commentRangeStart",
+        child = "w:commentRangeEnd",
+        child = "w:moveFromRangeStart",
+        child = "w:moveFromRangeEnd",
+        child = "w:moveToRangeStart",
+        child = "w:moveToRangeEnd"
+    )]
     pub content: Vec<TableCellContent<'a>>,
 }
 
+__into_owned! { TableCell { property, content } }
+
 impl<'a> TableCell<'a> {
-    __setter!(property: TableCellProperty);
+    __setter!(property: TableCellProperty<'a>);
 
     pub fn paragraph<T: Into<Paragraph<'a>>>(par: T) -> Self {
         TableCell {
@@ -37,22 +58,41 @@ impl<'a> TableCell<'a> {
         }
     }
 
-    pub fn iter_text(&self) -> impl Iterator<Item = &Cow<'a, str>> {
-        self.content
-            .iter()
-            .filter_map(|content| match content {
-                TableCellContent::Paragraph(p) => Some(p.iter_text()),
-            })
-            .flatten()
+    pub fn push<T: Into<TableCellContent<'a>>>(mut self, content: T) -> Self {
+        self.content.push(content.into());
+        self
+    }
+
+    pub fn iter_text(&self) -> Box<dyn Iterator<Item = &Cow<'a, str>> + '_> {
+        Box::new(
+            self.content
+                .iter()
+                .filter_map(|content| -> Option<Box<dyn Iterator<Item = _>>> {
+                    match content {
+                        TableCellContent::Paragraph(p) => Some(p.iter_text()),
+                        TableCellContent::Table(t) => Some(Box::new(t.iter_text())),
+                        TableCellContent::SDT(sdt) => Some(sdt.iter_text()),
+                        _ => None,
+                    }
+                })
+                .flatten(),
+        )
     }
 
-    pub fn iter_text_mut(&mut self) -> impl Iterator<Item = &mut Cow<'a, str>> {
-        self.content
-            .iter_mut()
-            .filter_map(|content| match content {
-                TableCellContent::Paragraph(p) => Some(p.iter_text_mut()),
-            })
-            .flatten()
+    pub fn iter_text_mut(&mut self) -> Box<dyn Iterator<Item = &mut Cow<'a, str>> + '_> {
+        Box::new(
+            self.content
+                .iter_mut()
+                .filter_map(|content| -> Option<Box<dyn Iterator<Item = _>>> {
+                    match content {
+                        TableCellContent::Paragraph(p) => Some(Box::new(p.iter_text_mut())),
+                        TableCellContent::Table(t) => Some(Box::new(t.iter_text_mut())),
+                        TableCellContent::SDT(sdt) => Some(sdt.iter_text_mut()),
+                        _ => None,
+                    }
+                })
+                .flatten(),
+        )
     }
 
     pub fn replace_text<'b, I, T, S>(&mut self, dic: T) -> crate::DocxResult<()>
@@ -64,6 +104,30 @@ impl<'a> TableCell<'a> {
         for content in self.content.iter_mut() {
             match content {
                 TableCellContent::Paragraph(p) => p.replace_text(dic)?,
+                TableCellContent::Table(t) => t.replace_text(dic)?,
+                TableCellContent::SDT(sdt) => sdt.replace_text(dic)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        for content in self.content.iter_mut() {
+            match content {
+                TableCellContent::Paragraph(p) => p.replace_text_tracked(dic, track)?,
+                TableCellContent::Table(t) => t.replace_text_tracked(dic, track)?,
+                TableCellContent::SDT(sdt) => sdt.replace_text_tracked(dic, track)?,
+                _ => {}
             }
         }
         Ok(())
@@ -81,15 +145,44 @@ impl<'a, T: Into<TableCellContent<'a>>> From<T> for TableCell<'a> {
 
 #[derive(Debug, From, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
 pub enum TableCellContent<'a> {
     #[xml(tag = "w:p")]
     Paragraph(Paragraph<'a>),
-    // #[xml(tag = "w:tbl")]
-    // Table(Table<'a>),
+    #[xml(tag = "w:tbl")]
+    Table(Table<'a>),
+    #[xml(tag = "w:sdt")]
+    SDT(SDT<'a>),
+    #[xml(tag = "w:bookmarkStart")]
+    BookmarkStart(BookmarkStart<'a>),
+    #[xml(tag = "w:bookmarkEnd")]
+    BookmarkEnd(BookmarkEnd<'a>),
+    #[xml(tag = "w:commentRangeStart")]
+    CommentRangeStart(CommentRangeStart<'a>),
+    #[xml(tag = "w:commentRangeEnd")]
+    CommentRangeEnd(CommentRangeEnd<'a>),
+    #[xml(tag = "w:moveFromRangeStart")]
+    MoveFromRangeStart(MoveFromRangeStart<'a>),
+    #[xml(tag = "w:moveFromRangeEnd")]
+    MoveFromRangeEnd(MoveFromRangeEnd),
+    #[xml(tag = "w:moveToRangeStart")]
+    MoveToRangeStart(MoveToRangeStart<'a>),
+    #[xml(tag = "w:moveToRangeEnd")]
+    MoveToRangeEnd(MoveToRangeEnd),
+}
+
+__into_owned! {
+    enum TableCellContent {
+        Paragraph, Table, SDT, BookmarkStart, BookmarkEnd, CommentRangeStart, CommentRangeEnd,
+        MoveFromRangeStart, MoveToRangeStart;
+        MoveFromRangeEnd, MoveToRangeEnd
+    }
 }
 
 __xml_test_suites!(
     TableCell,
     TableCell::paragraph(Paragraph::default()),
     r#"<w:tc><w:tcPr><w:vAlign w:val="top"/></w:tcPr><w:p/></w:tc>"#,
+    TableCell::from(Table::default()).push(Paragraph::default()),
+    r#"<w:tc><w:tcPr><w:vAlign w:val="top"/></w:tcPr><w:tbl><w:tblPr/><w:tblGrid/></w:tbl><w:p/></w:tc>"#,
 );
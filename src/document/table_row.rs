@@ -5,7 +5,11 @@ use super::SDT;
 
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__setter, __xml_test_suites, document::TableCell, formatting::TableRowProperty};
+use crate::{
+    __into_owned, __setter, __xml_test_suites,
+    document::{BodyContent, TableCell, TrackChanges},
+    formatting::TableRowProperty,
+};
 
 /// Table Row
 ///
@@ -26,12 +30,15 @@ use crate::{__setter, __xml_test_suites, document::TableCell, formatting::TableR
 #[xml(tag = "w:tr")]
 pub struct TableRow<'a> {
     #[xml(default, child = "w:trPr")]
-    pub property: TableRowProperty,
+    pub property: TableRowProperty<'a>,
     #[xml(child = "w:tc", child = "w:sdt")]
     pub cells: Vec<TableRowContent<'a>>,
 }
 
+__into_owned! { TableRow { property, cells } }
+
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
+#[allow(clippy::large_enum_variant)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum TableRowContent<'a> {
     #[xml(tag = "w:tc")]
@@ -40,6 +47,8 @@ pub enum TableRowContent<'a> {
     SDT(SDT<'a>),
 }
 
+__into_owned! { enum TableRowContent { TableCell, SDT } }
+
 impl<'a> From<TableCell<'a>> for TableRowContent<'a> {
     fn from(value: TableCell<'a>) -> Self {
         TableRowContent::TableCell(value)
@@ -54,13 +63,32 @@ impl<'a> From<crate::document::Paragraph<'a>> for TableRowContent<'a> {
 }
 
 impl<'a> TableRow<'a> {
-    __setter!(property: TableRowProperty);
+    __setter!(property: TableRowProperty<'a>);
 
     pub fn push_cell<T: Into<TableRowContent<'a>>>(mut self, cell: T) -> Self {
         self.cells.push(cell.into());
         self
     }
 
+    /// Iterates over the cells of the row, including the cells wrapped in content controls.
+    pub fn iter_cells(&self) -> impl Iterator<Item = &TableCell<'a>> {
+        self.cells.iter().flat_map(|content| {
+            let cells: Box<dyn Iterator<Item = &TableCell<'a>>> = match content {
+                TableRowContent::TableCell(tc) => Box::new(std::iter::once(tc)),
+                TableRowContent::SDT(sdt) => Box::new(
+                    sdt.content
+                        .iter()
+                        .flat_map(|content| content.content.iter())
+                        .filter_map(|content| match content {
+                            BodyContent::TableCell(tc) => Some(tc),
+                            _ => None,
+                        }),
+                ),
+            };
+            cells
+        })
+    }
+
     pub fn iter_text(&self) -> impl Iterator<Item = &Cow<'a, str>> {
         self.cells
             .iter()
@@ -96,6 +124,25 @@ impl<'a> TableRow<'a> {
         }
         Ok(())
     }
+
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> crate::DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        for cell in self.cells.iter_mut() {
+            match cell {
+                TableRowContent::TableCell(c) => c.replace_text_tracked(dic, track)?,
+                TableRowContent::SDT(sdt) => sdt.replace_text_tracked(dic, track)?,
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
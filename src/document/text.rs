@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__string_enum, __xml_test_suites};
+use crate::{__into_owned, __string_enum, __xml_test_suites};
 
 /// Literal Text
 ///
@@ -26,6 +26,8 @@ pub struct Text<'a> {
     pub text: Cow<'a, str>,
 }
 
+__into_owned! { Text { text; space } }
+
 impl From<String> for Text<'_> {
     fn from(val: String) -> Self {
         Text {
@@ -74,6 +76,8 @@ pub struct DelText<'a> {
     pub text: Cow<'a, str>,
 }
 
+__into_owned! { DelText { text; space } }
+
 impl From<String> for DelText<'_> {
     fn from(val: String) -> Self {
         DelText {
@@ -9,7 +9,10 @@ use std::borrow::Cow;
 use std::io::Write;
 
 use crate::schema::{SCHEMA_DRAWINGML, SCHEMA_MAIN, SCHEMA_WORDML_14};
-use crate::{__define_struct, __define_struct_vec, __string_enum, __xml_test_suites, write_attr};
+use crate::{
+    __define_struct, __define_struct_vec, __into_owned, __string_enum, __xml_test_suites,
+    write_attr,
+};
 
 /// The root element of the main document part.
 #[derive(Debug, Default, XmlRead, Clone)]
@@ -30,6 +33,8 @@ pub struct Theme<'a> {
     pub ext_lst: Option<ExtLst>,
 }
 
+__into_owned! { Theme { name, elements, cust_clr_lst; defaults, extra_clr_scheme_lst, ext_lst } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:custClrLst")]
@@ -38,6 +43,8 @@ pub struct CustClrLst<'a> {
     pub contents: Vec<CustClr<'a>>,
 }
 
+__into_owned! { CustClrLst { contents } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:dk1")]
@@ -56,6 +63,8 @@ pub struct Dk1<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Dk1 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:lt1")]
@@ -74,6 +83,8 @@ pub struct Lt1<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Lt1 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:dk2")]
@@ -92,6 +103,8 @@ pub struct Dk2<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Dk2 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:lt2")]
@@ -110,6 +123,8 @@ pub struct Lt2<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Lt2 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:accent1")]
@@ -128,6 +143,8 @@ pub struct Accent1<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Accent1 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:accent2")]
@@ -146,6 +163,8 @@ pub struct Accent2<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Accent2 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:accent3")]
@@ -164,6 +183,8 @@ pub struct Accent3<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Accent3 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:accent4")]
@@ -182,6 +203,8 @@ pub struct Accent4<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Accent4 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:accent5")]
@@ -200,6 +223,8 @@ pub struct Accent5<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Accent5 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:accent6")]
@@ -218,6 +243,8 @@ pub struct Accent6<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { Accent6 { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:hlink")]
@@ -236,6 +263,8 @@ pub struct HLink<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { HLink { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:folHlink")]
@@ -254,6 +283,8 @@ pub struct FolHlink<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { FolHlink { name; custom_color } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:custClr")]
@@ -272,6 +303,8 @@ pub struct CustClr<'a> {
     pub custom_color: Vec<CustClrChoice>,
 }
 
+__into_owned! { CustClr { name; custom_color } }
+
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum CustClrChoice {
@@ -824,6 +857,8 @@ pub struct ThemeElements<'a> {
     pub ext_lst: Option<ExtLst>,
 }
 
+__into_owned! { ThemeElements { clr_scheme, font_scheme, fmt_scheme; ext_lst } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:clrScheme")]
@@ -872,6 +907,14 @@ pub struct ClrScheme<'a> {
     pub ext_lst: Option<ExtLst>,
 }
 
+__into_owned! {
+    ClrScheme {
+        name, dk1, lt1, dk2, lt2, accent1, accent2, accent3, accent4, accent5, accent6, hlink,
+        fol_hlink;
+        ext_lst
+    }
+}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:fontScheme")]
@@ -886,6 +929,8 @@ pub struct FontScheme<'a> {
     pub ext_lst: Option<ExtLst>,
 }
 
+__into_owned! { FontScheme { name, major_font, minor_font; ext_lst } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:majorFont")]
@@ -902,6 +947,8 @@ pub struct MajorFont<'a> {
     pub ext_lst: Option<ExtLst>,
 }
 
+__into_owned! { MajorFont { latin, ea, cs, fonts; ext_lst } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:minorFont")]
@@ -918,6 +965,8 @@ pub struct MinorFont<'a> {
     pub ext_lst: Option<ExtLst>,
 }
 
+__into_owned! { MinorFont { latin, ea, cs, fonts; ext_lst } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:ea")]
@@ -932,6 +981,8 @@ pub struct EA<'a> {
     pub charset: Option<i8>,
 }
 
+__into_owned! { EA { typeface, panose; pitch_family, charset } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:cs")]
@@ -946,6 +997,8 @@ pub struct CS<'a> {
     pub charset: Option<i8>,
 }
 
+__into_owned! { CS { typeface, panose; pitch_family, charset } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:latin")]
@@ -960,6 +1013,8 @@ pub struct Latin<'a> {
     pub charset: Option<i8>,
 }
 
+__into_owned! { Latin { typeface, panose; pitch_family, charset } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:font")]
@@ -970,6 +1025,8 @@ pub struct Font<'a> {
     pub typeface: Cow<'a, str>,
 }
 
+__into_owned! { Font { script, typeface } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "a:fmtScheme")]
@@ -986,6 +1043,13 @@ pub struct FmtScheme<'a> {
     pub bg_fill_style_lst: BgFillStyleLst,
 }
 
+__into_owned! {
+    FmtScheme {
+        name;
+        fill_style_lst, in_style_lst, effect_style_lst, bg_fill_style_lst
+    }
+}
+
 __define_struct_vec! {
     ("a:fillStyleLst", FillStyleLst, FillStyleLstChoice) {} {
         "a:noFill", NoFill    //No Fill
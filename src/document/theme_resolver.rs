@@ -0,0 +1,340 @@
+//! Resolution of theme colors and theme fonts
+//!
+//! Colors and fonts in the document may name a slot of the theme instead of a concrete value.
+//! The slots for text and background colors go through the color scheme mapping of the
+//! settings first, which lets a document swap its dark and light colors.
+
+use crate::{
+    document::{CustClrChoice, SchemeClr, SchemeClrType, Theme},
+    formatting::{Color, Fonts, ThemeColor, ThemeFont},
+    settings::{ClrSchemeMapping, ColorSchemeIndex},
+};
+
+/// Turns references to the theme into concrete colors and typefaces
+///
+/// ```rust
+/// use docx_rust::document::{CustClrChoice, SrgbClr, Theme, ThemeResolver};
+/// use docx_rust::formatting::{Color, ThemeColor, ThemeFont};
+///
+/// let mut theme = Theme::default();
+/// let accent1 = SrgbClr {
+///     value: Some("4472C4".into()),
+///     alpha: None,
+/// };
+/// let scheme = &mut theme.elements.clr_scheme;
+/// scheme.accent1.custom_color.push(CustClrChoice::SrgbClr(accent1));
+/// theme.elements.font_scheme.major_font.latin.typeface = Some("Calibri Light".into());
+/// let resolver = ThemeResolver::new(&theme, None);
+///
+/// let color = Color::from("000000").theme_color(ThemeColor::Accent1);
+/// assert_eq!(resolver.color(&color).as_deref(), Some("4472C4"));
+///
+/// let darker = color.theme_shade("BF");
+/// assert_eq!(resolver.color(&darker).as_deref(), Some("2F5496"));
+///
+/// assert_eq!(resolver.font(&ThemeFont::MajorAscii), Some("Calibri Light"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ThemeResolver<'t, 'a> {
+    theme: &'t Theme<'a>,
+    mapping: Option<&'t ClrSchemeMapping>,
+}
+
+impl<'t, 'a> ThemeResolver<'t, 'a> {
+    pub fn new(theme: &'t Theme<'a>, mapping: Option<&'t ClrSchemeMapping>) -> Self {
+        ThemeResolver { theme, mapping }
+    }
+
+    /// Returns the color as six hex digits, preferring its theme color
+    ///
+    /// Returns `None` for `auto`, whose color depends on the background.
+    pub fn color(&self, color: &Color) -> Option<String> {
+        let themed = color.theme_color.as_ref().and_then(|theme_color| {
+            let mut rgb = self.theme_color(theme_color)?;
+            if let Some(tint) = color.theme_tint.as_ref().and_then(|t| hex_byte(t)) {
+                rgb = tint_rgb(rgb, tint as f64 / 255.0);
+            }
+            if let Some(shade) = color.theme_shade.as_ref().and_then(|s| hex_byte(s)) {
+                rgb = shade_rgb(rgb, shade as f64 / 255.0);
+            }
+            Some(rgb)
+        });
+        match themed {
+            Some(rgb) => Some(to_hex(rgb)),
+            None if color.value.eq_ignore_ascii_case("auto") => None,
+            None => Some(color.value.to_string()),
+        }
+    }
+
+    /// Returns a color of the theme, applying its modulations, tint and shade
+    pub fn scheme_color(&self, color: &SchemeClr) -> Option<String> {
+        let index = match color.val {
+            SchemeClrType::Bg1 => self.mapped(|m| &m.background1, ColorSchemeIndex::Light1),
+            SchemeClrType::Tx1 => self.mapped(|m| &m.text1, ColorSchemeIndex::Dark1),
+            SchemeClrType::Bg2 => self.mapped(|m| &m.background2, ColorSchemeIndex::Light2),
+            SchemeClrType::Tx2 => self.mapped(|m| &m.text2, ColorSchemeIndex::Dark2),
+            SchemeClrType::Dk1 => ColorSchemeIndex::Dark1,
+            SchemeClrType::Lt1 => ColorSchemeIndex::Light1,
+            SchemeClrType::Dk2 => ColorSchemeIndex::Dark2,
+            SchemeClrType::Lt2 => ColorSchemeIndex::Light2,
+            SchemeClrType::Accent1 => ColorSchemeIndex::Accent1,
+            SchemeClrType::Accent2 => ColorSchemeIndex::Accent2,
+            SchemeClrType::Accent3 => ColorSchemeIndex::Accent3,
+            SchemeClrType::Accent4 => ColorSchemeIndex::Accent4,
+            SchemeClrType::Accent5 => ColorSchemeIndex::Accent5,
+            SchemeClrType::Accent6 => ColorSchemeIndex::Accent6,
+            SchemeClrType::Hlink => ColorSchemeIndex::Hyperlink,
+            SchemeClrType::FolHlink => ColorSchemeIndex::FollowedHyperlink,
+            // the placeholder color is only known where the style is used
+            SchemeClrType::PhClr => return None,
+        };
+        let mut rgb = self.scheme_slot(&index)?;
+        if let Some(tint) = &color.tint {
+            rgb = tint_rgb(rgb, tint.val as f64 / 100_000.0);
+        }
+        if let Some(shade) = &color.shade {
+            rgb = shade_rgb(rgb, shade.val as f64 / 100_000.0);
+        }
+        if color.sat_mod.is_some() || color.lum_mod.is_some() {
+            let (h, mut s, mut l) = rgb_to_hsl(rgb);
+            if let Some(sat_mod) = &color.sat_mod {
+                s = (s * sat_mod.val as f64 / 100_000.0).clamp(0.0, 1.0);
+            }
+            if let Some(lum_mod) = &color.lum_mod {
+                l = (l * lum_mod.val as f64 / 100_000.0).clamp(0.0, 1.0);
+            }
+            rgb = hsl_to_rgb(h, s, l);
+        }
+        Some(to_hex(rgb))
+    }
+
+    /// Returns the typeface of a theme font slot
+    pub fn font(&self, font: &ThemeFont) -> Option<&'t str> {
+        let scheme = &self.theme.elements.font_scheme;
+        let (major, minor) = (&scheme.major_font, &scheme.minor_font);
+        let typeface = match font {
+            ThemeFont::MajorAscii | ThemeFont::MajorHansi => &major.latin.typeface,
+            ThemeFont::MajorEastAsia => &major.ea.typeface,
+            ThemeFont::MajorBidi => &major.cs.typeface,
+            ThemeFont::MinorAscii | ThemeFont::MinorHansi => &minor.latin.typeface,
+            ThemeFont::MinorEastAsia => &minor.ea.typeface,
+            ThemeFont::MinorBidi => &minor.cs.typeface,
+        };
+        typeface.as_deref().filter(|typeface| !typeface.is_empty())
+    }
+
+    /// Replaces the theme font slots of `fonts` with the typefaces they stand for
+    ///
+    /// Slots the theme leaves empty are kept, so the result still says where the font comes
+    /// from.
+    pub fn fonts(&self, fonts: &Fonts) -> Fonts {
+        let mut resolved = fonts.clone();
+        macro_rules! pair {
+            ($($name:ident, $theme:ident);*) => {$(
+                if let Some(typeface) = fonts.$theme.as_ref().and_then(|slot| self.font(slot)) {
+                    resolved.$name = Some(typeface.to_string());
+                    resolved.$theme = None;
+                }
+            )*};
+        }
+        pair!(
+            ascii, ascii_theme;
+            east_asia, east_asia_theme;
+            h_ansi, h_ansi_theme
+        );
+        let custom_theme = fonts
+            .custom_theme
+            .as_ref()
+            .and_then(|slot| slot.parse().ok());
+        if let Some(typeface) = custom_theme.and_then(|slot| self.font(&slot)) {
+            resolved.custom = Some(typeface.to_string());
+            resolved.custom_theme = None;
+        }
+        resolved
+    }
+
+    fn mapped<F>(&self, field: F, default: ColorSchemeIndex) -> ColorSchemeIndex
+    where
+        F: Fn(&ClrSchemeMapping) -> &Option<ColorSchemeIndex>,
+    {
+        self.mapping
+            .and_then(|mapping| field(mapping).clone())
+            .unwrap_or(default)
+    }
+
+    fn theme_color(&self, color: &ThemeColor) -> Option<(u8, u8, u8)> {
+        let index = match color {
+            ThemeColor::Dark1 => ColorSchemeIndex::Dark1,
+            ThemeColor::Light1 => ColorSchemeIndex::Light1,
+            ThemeColor::Dark2 => ColorSchemeIndex::Dark2,
+            ThemeColor::Light2 => ColorSchemeIndex::Light2,
+            ThemeColor::Background1 => self.mapped(|m| &m.background1, ColorSchemeIndex::Light1),
+            ThemeColor::Text1 => self.mapped(|m| &m.text1, ColorSchemeIndex::Dark1),
+            ThemeColor::Background2 => self.mapped(|m| &m.background2, ColorSchemeIndex::Light2),
+            ThemeColor::Text2 => self.mapped(|m| &m.text2, ColorSchemeIndex::Dark2),
+            ThemeColor::Accent1 => self.mapped(|m| &m.accent1, ColorSchemeIndex::Accent1),
+            ThemeColor::Accent2 => self.mapped(|m| &m.accent2, ColorSchemeIndex::Accent2),
+            ThemeColor::Accent3 => self.mapped(|m| &m.accent3, ColorSchemeIndex::Accent3),
+            ThemeColor::Accent4 => self.mapped(|m| &m.accent4, ColorSchemeIndex::Accent4),
+            ThemeColor::Accent5 => self.mapped(|m| &m.accent5, ColorSchemeIndex::Accent5),
+            ThemeColor::Accent6 => self.mapped(|m| &m.accent6, ColorSchemeIndex::Accent6),
+            ThemeColor::Hyperlink => self.mapped(|m| &m.hyperlink, ColorSchemeIndex::Hyperlink),
+            ThemeColor::FollowedHyperlink => self.mapped(
+                |m| &m.followed_hyperlink,
+                ColorSchemeIndex::FollowedHyperlink,
+            ),
+            ThemeColor::None => return None,
+        };
+        self.scheme_slot(&index)
+    }
+
+    fn scheme_slot(&self, index: &ColorSchemeIndex) -> Option<(u8, u8, u8)> {
+        let scheme = &self.theme.elements.clr_scheme;
+        let choices = match index {
+            ColorSchemeIndex::Dark1 => &scheme.dk1.custom_color,
+            ColorSchemeIndex::Light1 => &scheme.lt1.custom_color,
+            ColorSchemeIndex::Dark2 => &scheme.dk2.custom_color,
+            ColorSchemeIndex::Light2 => &scheme.lt2.custom_color,
+            ColorSchemeIndex::Accent1 => &scheme.accent1.custom_color,
+            ColorSchemeIndex::Accent2 => &scheme.accent2.custom_color,
+            ColorSchemeIndex::Accent3 => &scheme.accent3.custom_color,
+            ColorSchemeIndex::Accent4 => &scheme.accent4.custom_color,
+            ColorSchemeIndex::Accent5 => &scheme.accent5.custom_color,
+            ColorSchemeIndex::Accent6 => &scheme.accent6.custom_color,
+            ColorSchemeIndex::Hyperlink => &scheme.hlink.custom_color,
+            ColorSchemeIndex::FollowedHyperlink => &scheme.fol_hlink.custom_color,
+        };
+        choices.iter().find_map(|choice| match choice {
+            CustClrChoice::SrgbClr(srgb) => parse_rgb(srgb.value.as_deref()?),
+            CustClrChoice::SysClr(sys) => parse_rgb(sys.last_color.as_deref()?),
+            CustClrChoice::HslClr(hsl) => Some(hsl_to_rgb(
+                hsl.hue? as f64 / 60_000.0 / 360.0,
+                hsl.sat? as f64 / 100_000.0,
+                hsl.lum? as f64 / 100_000.0,
+            )),
+            _ => None,
+        })
+    }
+}
+
+fn hex_byte(value: &str) -> Option<u8> {
+    u8::from_str_radix(value, 16).ok()
+}
+
+fn parse_rgb(value: &str) -> Option<(u8, u8, u8)> {
+    if value.len() != 6 {
+        return None;
+    }
+    Some((
+        hex_byte(value.get(0..2)?)?,
+        hex_byte(value.get(2..4)?)?,
+        hex_byte(value.get(4..6)?)?,
+    ))
+}
+
+fn to_hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("{:02X}{:02X}{:02X}", r, g, b)
+}
+
+/// Moves the luminance towards white, keeping `tint` of it
+fn tint_rgb(rgb: (u8, u8, u8), tint: f64) -> (u8, u8, u8) {
+    let (h, s, l) = rgb_to_hsl(rgb);
+    hsl_to_rgb(h, s, (l * tint + (1.0 - tint)).clamp(0.0, 1.0))
+}
+
+/// Moves the luminance towards black, keeping `shade` of it
+fn shade_rgb(rgb: (u8, u8, u8), shade: f64) -> (u8, u8, u8) {
+    let (h, s, l) = rgb_to_hsl(rgb);
+    hsl_to_rgb(h, s, (l * shade).clamp(0.0, 1.0))
+}
+
+/// Converts to hue, saturation and luminance, all between 0 and 1
+fn rgb_to_hsl((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    if max == min {
+        return (0.0, 0.0, l);
+    }
+    let d = max - min;
+    let s = if l > 0.5 {
+        d / (2.0 - max - min)
+    } else {
+        d / (max + min)
+    };
+    let h = if max == r {
+        (g - b) / d + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h / 6.0, s, l)
+}
+
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (u8, u8, u8) {
+    let channel = |v: f64| (v * 255.0).round().clamp(0.0, 255.0) as u8;
+    if s == 0.0 {
+        return (channel(l), channel(l), channel(l));
+    }
+    let q = if l < 0.5 {
+        l * (1.0 + s)
+    } else {
+        l + s - l * s
+    };
+    let p = 2.0 * l - q;
+    let hue = |mut t: f64| {
+        if t < 0.0 {
+            t += 1.0;
+        }
+        if t > 1.0 {
+            t -= 1.0;
+        }
+        if t < 1.0 / 6.0 {
+            p + (q - p) * 6.0 * t
+        } else if t < 0.5 {
+            q
+        } else if t < 2.0 / 3.0 {
+            p + (q - p) * (2.0 / 3.0 - t) * 6.0
+        } else {
+            p
+        }
+    };
+    (
+        channel(hue(h + 1.0 / 3.0)),
+        channel(hue(h)),
+        channel(hue(h - 1.0 / 3.0)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsl_round_trip() {
+        for rgb in [
+            (0x44, 0x72, 0xC4),
+            (0, 0, 0),
+            (255, 255, 255),
+            (0xED, 0x7D, 0x31),
+        ] {
+            let (h, s, l) = rgb_to_hsl(rgb);
+            assert_eq!(hsl_to_rgb(h, s, l), rgb);
+        }
+    }
+
+    #[test]
+    fn tint_and_shade() {
+        // "Blue, Accent 1, Lighter 40%" and "Darker 25%" as shown by Word
+        assert_eq!(
+            to_hex(tint_rgb((0x44, 0x72, 0xC4), 0x99 as f64 / 255.0)),
+            "8FAADC"
+        );
+        assert_eq!(
+            to_hex(shade_rgb((0x44, 0x72, 0xC4), 0xBF as f64 / 255.0)),
+            "2F5496"
+        );
+    }
+}
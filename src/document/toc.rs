@@ -0,0 +1,629 @@
+//! Table of contents
+//!
+//! A table of contents is a `TOC` field in a content control whose doc part gallery is
+//! "Table of Contents". Each entry is a hyperlink to a `_Toc` bookmark around its heading,
+//! followed by a `PAGEREF` field which Word fills in with the page number.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::document::instrtext::{InstrText, TextSpace};
+use crate::document::{
+    BodyContent, BookmarkEnd, BookmarkStart, CharType, DocPartGallery, DocPartObj, DocPartUnique,
+    FieldChar, Hyperlink, HyperlinkContent, Paragraph, ParagraphContent, Run, RunContent,
+    SDTContent, SDTProperty, Tab, TableCell, TableCellContent, Text, VisitMut, SDT,
+};
+use crate::formatting::{
+    CustomTabStop, CustomTabStopSet, CustomTabStopSetChoice, OutlineLvl, ParagraphProperty,
+    TabLeaderCharacter, TabStopType,
+};
+use crate::styles::{Style, Styles};
+
+pub(crate) const TOC_GALLERY: &str = "Table of Contents";
+const TOC_HEADING_STYLE: &str = "TOCHeading";
+/// Outline level of paragraphs which are body text, and never listed
+const BODY_TEXT_LEVEL: isize = 9;
+
+/// A heading listed in the table of contents
+#[derive(Debug, Clone)]
+pub(crate) struct TocEntry {
+    /// From 1 to 9
+    pub level: usize,
+    pub text: String,
+    pub bookmark: String,
+}
+
+/// Returns whether a block is the content control of a table of contents
+pub(crate) fn is_toc(content: &BodyContent) -> bool {
+    match content {
+        BodyContent::Sdt(sdt) => sdt
+            .property
+            .as_ref()
+            .and_then(|prop| prop.doc_part_obj.as_ref())
+            .and_then(|obj| obj.doc_part_gallery.as_ref())
+            .and_then(|gallery| gallery.name.as_deref())
+            .is_some_and(|name| name == TOC_GALLERY),
+        _ => false,
+    }
+}
+
+/// Returns the heading level of every paragraph style which has one
+///
+/// The level is taken from the outline level of the style or of the styles it is based on,
+/// or else from names like "heading 1".
+pub(crate) fn heading_levels(styles: &Styles) -> HashMap<String, usize> {
+    let by_id: HashMap<&str, &Style> = styles
+        .styles
+        .iter()
+        .map(|style| (&*style.style_id, style))
+        .collect();
+
+    fn level_of(by_id: &HashMap<&str, &Style>, id: &str, depth: usize) -> Option<usize> {
+        let style = by_id.get(id)?;
+        if let Some(lvl) = style
+            .paragraph
+            .as_ref()
+            .and_then(|prop| prop.outline_lvl.as_ref())
+        {
+            return outline_level(lvl);
+        }
+        let name = style.name.as_ref().map(|name| &*name.value).unwrap_or(id);
+        if let Some(level) = heading_number(name).or_else(|| heading_number(id)) {
+            return Some(level);
+        }
+        match &style.base {
+            // guards against styles which are based on each other
+            Some(base) if depth < 16 => level_of(by_id, &base.value, depth + 1),
+            _ => None,
+        }
+    }
+
+    by_id
+        .keys()
+        .filter_map(|id| Some((id.to_string(), level_of(&by_id, id, 0)?)))
+        .collect()
+}
+
+fn outline_level(lvl: &OutlineLvl) -> Option<usize> {
+    (0..BODY_TEXT_LEVEL)
+        .contains(&lvl.value)
+        .then_some(lvl.value as usize + 1)
+}
+
+/// Parses names like "heading 1" or "Heading1"
+fn heading_number(name: &str) -> Option<usize> {
+    let rest = name
+        .get(..7)?
+        .eq_ignore_ascii_case("heading")
+        .then(|| &name[7..])?;
+    match rest.trim_start().parse() {
+        Ok(level @ 1..=9) => Some(level),
+        _ => None,
+    }
+}
+
+/// Collects the names and the highest numeric id of the bookmarks of a part
+#[derive(Default)]
+pub(crate) struct Bookmarks {
+    pub names: HashSet<String>,
+    pub max_id: usize,
+}
+
+impl Bookmarks {
+    fn insert(&mut self, start: &BookmarkStart) {
+        if let Some(name) = &start.name {
+            self.names.insert(name.to_string());
+        }
+        if let Some(id) = start.id.as_ref().and_then(|id| id.parse().ok()) {
+            self.max_id = self.max_id.max(id);
+        }
+    }
+}
+
+impl<'a> VisitMut<'a> for Bookmarks {
+    fn visit_inline(&mut self, content: &mut ParagraphContent<'a>) {
+        if let ParagraphContent::BookmarkStart(start) = content {
+            self.insert(start);
+        }
+    }
+
+    fn visit_cell(&mut self, cell: &mut TableCell<'a>) {
+        for content in cell.content.iter() {
+            if let TableCellContent::BookmarkStart(start) = content {
+                self.insert(start);
+            }
+        }
+    }
+}
+
+/// Finds the headings of a part, adding a `_Toc` bookmark around those which have none
+pub(crate) struct TocHeadings<'s> {
+    levels: &'s HashMap<String, usize>,
+    max_level: usize,
+    bookmarks: Bookmarks,
+    /// Bookmarks which are preferred if a heading has several, like the ones a table links to
+    anchors: HashSet<String>,
+    pub entries: Vec<TocEntry>,
+}
+
+impl<'s> TocHeadings<'s> {
+    pub fn new(levels: &'s HashMap<String, usize>, max_level: usize, bookmarks: Bookmarks) -> Self {
+        TocHeadings {
+            levels,
+            max_level,
+            bookmarks,
+            anchors: HashSet::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn prefer_anchors(mut self, anchors: HashSet<String>) -> Self {
+        self.anchors = anchors;
+        self
+    }
+
+    fn paragraph_level(&self, paragraph: &Paragraph) -> Option<usize> {
+        let prop = paragraph.property.as_ref()?;
+        match &prop.outline_lvl {
+            Some(lvl) => outline_level(lvl),
+            None => self.levels.get(&*prop.style_id.as_ref()?.value).copied(),
+        }
+    }
+
+    fn new_bookmark_name(&mut self) -> String {
+        // like the ones Word uses: `_Toc` and nine digits
+        let name = (100_000_001..)
+            .map(|n: u64| format!("_Toc{}", n))
+            .find(|name| !self.bookmarks.names.contains(name))
+            .unwrap();
+        self.bookmarks.names.insert(name.clone());
+        name
+    }
+}
+
+impl<'a, 's> VisitMut<'a> for TocHeadings<'s> {
+    fn visit_paragraph(&mut self, paragraph: &mut Paragraph<'a>) {
+        let level = match self.paragraph_level(paragraph) {
+            Some(level) if level <= self.max_level => level,
+            _ => return,
+        };
+        let text = paragraph.text();
+        if text.trim().is_empty() {
+            return;
+        }
+
+        let names: Vec<_> = paragraph
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                ParagraphContent::BookmarkStart(BookmarkStart {
+                    name: Some(name), ..
+                }) if name.starts_with("_Toc") => Some(name.to_string()),
+                _ => None,
+            })
+            .collect();
+        let bookmark = names
+            .iter()
+            .find(|name| self.anchors.contains(*name))
+            .or(names.first())
+            .cloned();
+        let bookmark = match bookmark {
+            Some(name) => name,
+            None => {
+                let name = self.new_bookmark_name();
+                self.bookmarks.max_id += 1;
+                let id = self.bookmarks.max_id.to_string();
+                paragraph.content.insert(
+                    0,
+                    BookmarkStart::default()
+                        .id(id.clone())
+                        .name(name.clone())
+                        .into(),
+                );
+                paragraph.content.push(BookmarkEnd::default().id(id).into());
+                name
+            }
+        };
+
+        self.entries.push(TocEntry {
+            level,
+            text,
+            bookmark,
+        });
+    }
+}
+
+/// Builds the content control of a table of contents listing `entries`
+///
+/// `tab_pos` is the position of the right aligned page numbers, in twentieths of a point.
+pub(crate) fn toc_sdt<'a>(
+    entries: &[TocEntry],
+    max_level: usize,
+    title: Option<&str>,
+    tab_pos: isize,
+) -> SDT<'a> {
+    let mut content = Vec::new();
+    if let Some(title) = title {
+        content.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().style_id(TOC_HEADING_STYLE))
+                .push_text(title.to_string())
+                .into(),
+        );
+    }
+
+    let field_start = [
+        Run::default().push(FieldChar::from(CharType::Begin)),
+        Run::default().push(InstrText::from((
+            format!(" TOC \\o \"1-{}\" \\h \\z \\u ", max_level),
+            TextSpace::Preserve,
+        ))),
+        Run::default().push(FieldChar::from(CharType::Separate)),
+    ];
+    let mut paragraphs: Vec<_> = entries
+        .iter()
+        .map(|entry| toc_entry_paragraph(entry, tab_pos))
+        .collect();
+    if paragraphs.is_empty() {
+        paragraphs.push(Paragraph::default());
+    }
+    // the field starts in the first entry and ends in a paragraph of its own
+    paragraphs[0]
+        .content
+        .splice(0..0, field_start.into_iter().map(Into::into));
+    paragraphs.push(Paragraph::default().push(Run::default().push(FieldChar::from(CharType::End))));
+    content.extend(paragraphs.into_iter().map(Into::into));
+
+    SDT::default()
+        .property(SDTProperty {
+            id: None,
+            doc_part_obj: Some(DocPartObj {
+                doc_part_gallery: Some(DocPartGallery {
+                    name: Some(TOC_GALLERY.into()),
+                }),
+                doc_part_unique: Some(DocPartUnique {}),
+            }),
+        })
+        .content(SDTContent { content })
+}
+
+/// Builds the paragraph of an entry: its text, a tab and the page number
+pub(crate) fn toc_entry_paragraph<'a>(entry: &TocEntry, tab_pos: isize) -> Paragraph<'a> {
+    let link = Hyperlink::default()
+        .anchor(entry.bookmark.clone())
+        .push_run(Run::default().push_text(entry.text.clone()))
+        .push_run(Run::default().push(Tab))
+        .push_run(Run::default().push(FieldChar::from(CharType::Begin)))
+        .push_run(Run::default().push(InstrText::from((
+            format!(" PAGEREF {} \\h ", entry.bookmark),
+            TextSpace::Preserve,
+        ))))
+        .push_run(Run::default().push(FieldChar::from(CharType::Separate)))
+        .push_run(Run::default().push(FieldChar::from(CharType::End)));
+
+    let tabs = CustomTabStopSet {
+        content: vec![CustomTabStopSetChoice::CustomTabStop(
+            CustomTabStop::default()
+                .tab_stop_type(TabStopType::Right)
+                .leader(TabLeaderCharacter::Dot)
+                .pos(tab_pos),
+        )],
+    };
+    let mut property = ParagraphProperty::default().style_id(format!("TOC{}", entry.level));
+    property.tabs = Some(tabs);
+
+    Paragraph::default().property(property).push(link)
+}
+
+/// Returns the number of levels listed by a `TOC` field, `3` if it doesn't say
+///
+/// The levels are given by the `\o` switch of the field code, like `TOC \o "1-3" \h`.
+pub(crate) fn toc_field_levels(sdt: &SDT) -> usize {
+    let instr: String = sdt
+        .content
+        .iter()
+        .flat_map(|content| content.content.iter())
+        .filter_map(|content| match content {
+            BodyContent::Paragraph(para) => Some(para),
+            _ => None,
+        })
+        .flat_map(|para| para.content.iter())
+        .filter_map(|content| match content {
+            ParagraphContent::Run(run) => Some(run),
+            _ => None,
+        })
+        .flat_map(|run| run.content.iter())
+        .filter_map(|content| match content {
+            RunContent::InstrText(instr) => Some(&*instr.text),
+            _ => None,
+        })
+        .collect();
+    instr
+        .split_once("\\o")
+        .and_then(|(_, rest)| rest.split('"').nth(1))
+        .and_then(|range| range.split_once('-'))
+        .and_then(|(_, last)| last.trim().parse().ok())
+        .filter(|levels| (1..=9).contains(levels))
+        .unwrap_or(3)
+}
+
+/// Returns the bookmarks the entries of a table of contents point to
+pub(crate) fn toc_anchors(sdt: &SDT) -> HashSet<String> {
+    sdt.content
+        .iter()
+        .flat_map(|content| content.content.iter())
+        .filter_map(|content| match content {
+            BodyContent::Paragraph(para) => entry_link(para),
+            _ => None,
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Rewrites the entries of an existing table of contents to list `entries`
+///
+/// An entry whose hyperlink points to the bookmark of a heading is kept, with the text of its
+/// hyperlink replaced; entries of new headings are copied from an existing entry, preferably
+/// one of the same level. The runs keep their formatting, and the blocks which are not
+/// entries, like the title, are left where they are, even between entries.
+pub(crate) fn refresh_toc_sdt(
+    sdt: &mut SDT,
+    entries: &[TocEntry],
+    styles: &Styles,
+    max_level: usize,
+    tab_pos: isize,
+) {
+    let blocks = &mut sdt.content.get_or_insert_with(Default::default).content;
+    let is_entry = |content: &BodyContent| match content {
+        BodyContent::Paragraph(para) => entry_link(para).is_some(),
+        _ => false,
+    };
+    let (first, last) = match (
+        blocks.iter().position(is_entry),
+        blocks.iter().rposition(is_entry),
+    ) {
+        (Some(first), Some(last)) => (first, last),
+        // without an entry to copy, the field is built again after the title
+        _ => {
+            let start = blocks
+                .iter()
+                .position(|content| matches!(content, BodyContent::Paragraph(para) if has_field_code(para)))
+                .unwrap_or(blocks.len());
+            blocks.truncate(start);
+            if let Some(SDTContent { content }) = toc_sdt(entries, max_level, None, tab_pos).content
+            {
+                blocks.extend(content);
+            }
+            return;
+        }
+    };
+
+    // the entries are replaced one by one, the blocks between them stay where they are
+    let slots: Vec<usize> = (first..=last).filter(|&i| is_entry(&blocks[i])).collect();
+    let mut old: Vec<Paragraph> = slots
+        .iter()
+        .filter_map(|&slot| {
+            match std::mem::replace(&mut blocks[slot], Paragraph::default().into()) {
+                BodyContent::Paragraph(para) => Some(para),
+                _ => None,
+            }
+        })
+        .collect();
+    // the start of the `TOC` field, in front of the hyperlink of the first entry
+    let link_index = old[0]
+        .content
+        .iter()
+        .position(|content| matches!(content, ParagraphContent::Link(_)))
+        .unwrap_or_default();
+    let field_start: Vec<_> = old[0].content.drain(..link_index).collect();
+    // and its end, if it follows the hyperlink of the last entry
+    let last_entry = old.last_mut().unwrap();
+    let end_index = last_entry
+        .content
+        .iter()
+        .rposition(|content| matches!(content, ParagraphContent::Link(_)))
+        .map_or(last_entry.content.len(), |index| index + 1);
+    let field_end: Vec<_> = last_entry.content.drain(end_index..).collect();
+
+    let level_of = |para: &Paragraph| {
+        let id = &para.property.as_ref()?.style_id.as_ref()?.value;
+        let style = styles.styles.iter().find(|style| style.style_id == *id);
+        let name = style
+            .and_then(|style| style.name.as_ref())
+            .map(|name| &*name.value);
+        name.and_then(toc_style_number)
+            .or_else(|| toc_style_number(id))
+    };
+    let mut templates: HashMap<usize, Paragraph> = HashMap::new();
+    for para in &old {
+        if let Some(level) = level_of(para) {
+            templates.entry(level).or_insert_with(|| para.clone());
+        }
+    }
+    let any_template = old.first().cloned();
+
+    let mut paragraphs: Vec<Paragraph> = entries
+        .iter()
+        .map(|entry| {
+            let existing = old
+                .iter()
+                .position(|para| entry_link(para) == Some(&entry.bookmark));
+            let mut para = match existing {
+                Some(index) => {
+                    let mut para = old.remove(index);
+                    if level_of(&para) != Some(entry.level) {
+                        para.property = level_property(&templates, &any_template, entry.level);
+                    }
+                    para
+                }
+                None => match templates.get(&entry.level).or(any_template.as_ref()) {
+                    Some(template) => {
+                        let mut para = template.clone();
+                        para.property = level_property(&templates, &any_template, entry.level);
+                        retarget_entry(&mut para, &entry.bookmark);
+                        para
+                    }
+                    None => toc_entry_paragraph(entry, tab_pos),
+                },
+            };
+            set_entry_text(&mut para, &entry.text);
+            para
+        })
+        .collect();
+
+    if paragraphs.is_empty() {
+        paragraphs.push(Paragraph::default());
+    }
+    paragraphs[0].content.splice(0..0, field_start);
+    if let Some(para) = paragraphs.last_mut() {
+        para.content.extend(field_end);
+    }
+
+    let count = paragraphs.len();
+    let mut paragraphs = paragraphs.into_iter().map(BodyContent::from);
+    for (&slot, para) in slots.iter().zip(paragraphs.by_ref()) {
+        blocks[slot] = para;
+    }
+    // the entries left over follow the last one, the slots left over are removed
+    blocks.splice(last + 1..last + 1, paragraphs);
+    for &slot in slots.iter().skip(count).rev() {
+        blocks.remove(slot);
+    }
+}
+
+/// Returns the bookmark the hyperlink of an entry points to
+fn entry_link<'p>(para: &'p Paragraph) -> Option<&'p str> {
+    para.content.iter().find_map(|content| match content {
+        ParagraphContent::Link(link) => link.anchor.as_deref(),
+        _ => None,
+    })
+}
+
+fn has_field_code(para: &Paragraph) -> bool {
+    para.content.iter().any(|content| match content {
+        ParagraphContent::Run(run) => run
+            .content
+            .iter()
+            .any(|content| matches!(content, RunContent::InstrText(_))),
+        _ => false,
+    })
+}
+
+/// Parses names like "toc 1" or "TOC1"
+fn toc_style_number(name: &str) -> Option<usize> {
+    let rest = name
+        .get(..3)?
+        .eq_ignore_ascii_case("toc")
+        .then(|| &name[3..])?;
+    match rest.trim_start().parse() {
+        Ok(level @ 1..=9) => Some(level),
+        _ => None,
+    }
+}
+
+/// Returns the properties of the entries of `level`, falling back to the `TOC1` to `TOC9` styles
+fn level_property<'a>(
+    templates: &HashMap<usize, Paragraph<'a>>,
+    any_template: &Option<Paragraph<'a>>,
+    level: usize,
+) -> Option<ParagraphProperty<'a>> {
+    if let Some(template) = templates.get(&level) {
+        return template.property.clone();
+    }
+    let mut property = any_template
+        .as_ref()
+        .and_then(|para| para.property.clone())
+        .unwrap_or_default();
+    property.style_id = Some(format!("TOC{}", level).into());
+    Some(property)
+}
+
+/// Points a copied entry at `bookmark`, dropping the page number it showed
+fn retarget_entry(para: &mut Paragraph, bookmark: &str) {
+    for content in para.content.iter_mut() {
+        if let ParagraphContent::Link(link) = content {
+            link.anchor = Some(bookmark.to_string().into());
+            let mut in_page_number = false;
+            for content in link.content.iter_mut() {
+                let HyperlinkContent::Run(run) = content else {
+                    continue;
+                };
+                for content in run.content.iter_mut() {
+                    match content {
+                        RunContent::InstrText(instr) if instr.text.contains("PAGEREF") => {
+                            instr.text = format!(" PAGEREF {} \\h ", bookmark).into();
+                            instr.space = Some(TextSpace::Preserve);
+                            in_page_number = true;
+                        }
+                        RunContent::Text(text) if in_page_number => text.text = "".into(),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Replaces the text of an entry, which is in the runs of its hyperlink before the tab
+///
+/// The first of these runs gets the new text, the others are removed.
+fn set_entry_text(para: &mut Paragraph, text: &str) {
+    let Some(link) = para.content.iter_mut().find_map(|content| match content {
+        ParagraphContent::Link(link) => Some(link),
+        _ => None,
+    }) else {
+        return;
+    };
+    let runs_end = link
+        .content
+        .iter()
+        .position(|content| match content {
+            HyperlinkContent::Run(run) => run.content.iter().any(|content| {
+                matches!(
+                    content,
+                    RunContent::Tab(_) | RunContent::FieldChar(_) | RunContent::PTab(_)
+                )
+            }),
+            _ => false,
+        })
+        .unwrap_or(link.content.len());
+
+    let text = Text::from((text.to_string(), crate::document::TextSpace::Preserve));
+    let mut runs = link.content.drain(..runs_end);
+    let mut run = match runs.next() {
+        Some(HyperlinkContent::Run(run)) => run,
+        _ => Run::default(),
+    };
+    drop(runs);
+    let position = run
+        .content
+        .iter()
+        .position(|content| matches!(content, RunContent::Text(_)))
+        .unwrap_or(run.content.len());
+    run.content
+        .retain(|content| !matches!(content, RunContent::Text(_)));
+    run.content
+        .insert(position.min(run.content.len()), RunContent::Text(text));
+    link.content.insert(0, HyperlinkContent::Run(run));
+}
+
+/// Adds the styles of the title and the entries, unless they are defined already
+pub(crate) fn add_toc_styles(styles: &mut Styles, max_level: usize) {
+    styles.add_builtin(TOC_HEADING_STYLE);
+    for level in 1..=max_level {
+        styles.add_builtin(&format!("TOC{}", level));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::heading_number;
+
+    #[test]
+    fn heading_names() {
+        assert_eq!(Some(1), heading_number("heading 1"));
+        assert_eq!(Some(3), heading_number("Heading3"));
+        assert_eq!(None, heading_number("Heading"));
+        assert_eq!(None, heading_number("heading 10"));
+        assert_eq!(None, heading_number("Title"));
+    }
+}
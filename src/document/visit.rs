@@ -0,0 +1,232 @@
+//! Traversal of the block and inline content tree
+//!
+//! Operations which have to reach every paragraph or run of a part, no matter how deeply it
+//! is nested in tables, content controls or revisions, implement [`VisitMut`] and hand it
+//! to [`walk_blocks_mut`], or implement [`Visit`] and hand it to [`walk_blocks`] if they only
+//! read the content. Both walks reach the same runs in the same order.
+
+use crate::document::{
+    BodyContent, Hyperlink, HyperlinkContent, Paragraph, ParagraphContent, Run, SimpleField, Table,
+    TableCell, TableCellContent, TableRowContent, SDT,
+};
+
+pub(crate) trait Visit<'a> {
+    /// Called for every block, like a paragraph or a body-level revision, before it is walked.
+    fn visit_block(&mut self, _block: &BodyContent<'a>) {}
+
+    /// Called for every item of a paragraph or of a revision, before it is walked.
+    fn visit_inline(&mut self, _content: &ParagraphContent<'a>) {}
+
+    /// Called before the content of the paragraph is walked.
+    fn visit_paragraph(&mut self, _paragraph: &Paragraph<'a>) {}
+
+    /// Called before the rows of the table are walked.
+    fn visit_table(&mut self, _table: &Table<'a>) {}
+
+    /// Called before the content of the cell is walked.
+    fn visit_cell(&mut self, _cell: &TableCell<'a>) {}
+
+    /// Called before the runs of the field result are walked.
+    fn visit_simple_field(&mut self, _field: &SimpleField<'a>) {}
+
+    fn visit_run(&mut self, _run: &Run<'a>) {}
+}
+
+pub(crate) trait VisitMut<'a> {
+    /// Called for every block, like a paragraph or a body-level revision, before it is walked.
+    fn visit_block(&mut self, _block: &mut BodyContent<'a>) {}
+
+    /// Called for every item of a paragraph or of a revision, before it is walked.
+    fn visit_inline(&mut self, _content: &mut ParagraphContent<'a>) {}
+
+    /// Called before the content of the paragraph is walked.
+    fn visit_paragraph(&mut self, _paragraph: &mut Paragraph<'a>) {}
+
+    /// Called before the rows of the table are walked.
+    fn visit_table(&mut self, _table: &mut Table<'a>) {}
+
+    /// Called before the content of the cell is walked.
+    fn visit_cell(&mut self, _cell: &mut TableCell<'a>) {}
+
+    /// Called before the runs of the hyperlink are walked.
+    fn visit_hyperlink(&mut self, _link: &mut Hyperlink<'a>) {}
+
+    /// Called before the runs of the field result are walked.
+    fn visit_simple_field(&mut self, _field: &mut SimpleField<'a>) {}
+
+    fn visit_run(&mut self, _run: &mut Run<'a>) {}
+}
+
+pub(crate) fn walk_blocks<'a, V: Visit<'a>>(visitor: &mut V, blocks: &[BodyContent<'a>]) {
+    for block in blocks {
+        visitor.visit_block(block);
+        match block {
+            BodyContent::Paragraph(para) => walk_paragraph(visitor, para),
+            BodyContent::Table(table) => walk_table(visitor, table),
+            BodyContent::Sdt(sdt) => walk_sdt(visitor, sdt),
+            BodyContent::TableCell(cell) => walk_cell(visitor, cell),
+            BodyContent::Run(run) => visitor.visit_run(run),
+            BodyContent::Insertion(ins) => walk_inline(visitor, &ins.content),
+            BodyContent::Deletion(del) => walk_inline(visitor, &del.content),
+            BodyContent::MoveFrom(from) => walk_inline(visitor, &from.content),
+            BodyContent::MoveTo(to) => walk_inline(visitor, &to.content),
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn walk_paragraph<'a, V: Visit<'a>>(visitor: &mut V, para: &Paragraph<'a>) {
+    visitor.visit_paragraph(para);
+    walk_inline(visitor, &para.content);
+}
+
+fn walk_inline<'a, V: Visit<'a>>(visitor: &mut V, content: &[ParagraphContent<'a>]) {
+    for item in content {
+        visitor.visit_inline(item);
+        match item {
+            ParagraphContent::Run(run) => visitor.visit_run(run),
+            ParagraphContent::Link(link) => {
+                for content in link.content.iter() {
+                    match content {
+                        HyperlinkContent::Run(run) => visitor.visit_run(run),
+                        HyperlinkContent::Insertion(ins) => walk_inline(visitor, &ins.content),
+                        HyperlinkContent::Deletion(del) => walk_inline(visitor, &del.content),
+                    }
+                }
+            }
+            ParagraphContent::SimpleField(field) => {
+                visitor.visit_simple_field(field);
+                for run in field.content.iter() {
+                    visitor.visit_run(run);
+                }
+            }
+            ParagraphContent::SDT(sdt) => walk_sdt(visitor, sdt),
+            ParagraphContent::Insertion(ins) => walk_inline(visitor, &ins.content),
+            ParagraphContent::Deletion(del) => walk_inline(visitor, &del.content),
+            ParagraphContent::MoveFrom(from) => walk_inline(visitor, &from.content),
+            ParagraphContent::MoveTo(to) => walk_inline(visitor, &to.content),
+            _ => {}
+        }
+    }
+}
+
+fn walk_sdt<'a, V: Visit<'a>>(visitor: &mut V, sdt: &SDT<'a>) {
+    if let Some(content) = &sdt.content {
+        walk_blocks(visitor, &content.content);
+    }
+}
+
+fn walk_table<'a, V: Visit<'a>>(visitor: &mut V, table: &Table<'a>) {
+    visitor.visit_table(table);
+    for row in table.rows.iter() {
+        for cell in row.cells.iter() {
+            match cell {
+                TableRowContent::TableCell(cell) => walk_cell(visitor, cell),
+                TableRowContent::SDT(sdt) => walk_sdt(visitor, sdt),
+            }
+        }
+    }
+}
+
+fn walk_cell<'a, V: Visit<'a>>(visitor: &mut V, cell: &TableCell<'a>) {
+    visitor.visit_cell(cell);
+    for content in cell.content.iter() {
+        match content {
+            TableCellContent::Paragraph(para) => walk_paragraph(visitor, para),
+            TableCellContent::Table(table) => walk_table(visitor, table),
+            TableCellContent::SDT(sdt) => walk_sdt(visitor, sdt),
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn walk_blocks_mut<'a, V: VisitMut<'a>>(
+    visitor: &mut V,
+    blocks: &mut [BodyContent<'a>],
+) {
+    for block in blocks {
+        visitor.visit_block(block);
+        match block {
+            BodyContent::Paragraph(para) => walk_paragraph_mut(visitor, para),
+            BodyContent::Table(table) => walk_table_mut(visitor, table),
+            BodyContent::Sdt(sdt) => walk_sdt_mut(visitor, sdt),
+            BodyContent::TableCell(cell) => walk_cell_mut(visitor, cell),
+            BodyContent::Run(run) => visitor.visit_run(run),
+            BodyContent::Insertion(ins) => walk_inline_mut(visitor, &mut ins.content),
+            BodyContent::Deletion(del) => walk_inline_mut(visitor, &mut del.content),
+            BodyContent::MoveFrom(from) => walk_inline_mut(visitor, &mut from.content),
+            BodyContent::MoveTo(to) => walk_inline_mut(visitor, &mut to.content),
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn walk_paragraph_mut<'a, V: VisitMut<'a>>(visitor: &mut V, para: &mut Paragraph<'a>) {
+    visitor.visit_paragraph(para);
+    walk_inline_mut(visitor, &mut para.content);
+}
+
+fn walk_inline_mut<'a, V: VisitMut<'a>>(visitor: &mut V, content: &mut [ParagraphContent<'a>]) {
+    for item in content {
+        visitor.visit_inline(item);
+        match item {
+            ParagraphContent::Run(run) => visitor.visit_run(run),
+            ParagraphContent::Link(link) => {
+                visitor.visit_hyperlink(link);
+                for content in link.content.iter_mut() {
+                    match content {
+                        HyperlinkContent::Run(run) => visitor.visit_run(run),
+                        HyperlinkContent::Insertion(ins) => {
+                            walk_inline_mut(visitor, &mut ins.content)
+                        }
+                        HyperlinkContent::Deletion(del) => {
+                            walk_inline_mut(visitor, &mut del.content)
+                        }
+                    }
+                }
+            }
+            ParagraphContent::SimpleField(field) => {
+                visitor.visit_simple_field(field);
+                for run in field.content.iter_mut() {
+                    visitor.visit_run(run);
+                }
+            }
+            ParagraphContent::SDT(sdt) => walk_sdt_mut(visitor, sdt),
+            ParagraphContent::Insertion(ins) => walk_inline_mut(visitor, &mut ins.content),
+            ParagraphContent::Deletion(del) => walk_inline_mut(visitor, &mut del.content),
+            ParagraphContent::MoveFrom(from) => walk_inline_mut(visitor, &mut from.content),
+            ParagraphContent::MoveTo(to) => walk_inline_mut(visitor, &mut to.content),
+            _ => {}
+        }
+    }
+}
+
+fn walk_sdt_mut<'a, V: VisitMut<'a>>(visitor: &mut V, sdt: &mut SDT<'a>) {
+    if let Some(content) = &mut sdt.content {
+        walk_blocks_mut(visitor, &mut content.content);
+    }
+}
+
+fn walk_table_mut<'a, V: VisitMut<'a>>(visitor: &mut V, table: &mut Table<'a>) {
+    visitor.visit_table(table);
+    for row in table.rows.iter_mut() {
+        for cell in row.cells.iter_mut() {
+            match cell {
+                TableRowContent::TableCell(cell) => walk_cell_mut(visitor, cell),
+                TableRowContent::SDT(sdt) => walk_sdt_mut(visitor, sdt),
+            }
+        }
+    }
+}
+
+fn walk_cell_mut<'a, V: VisitMut<'a>>(visitor: &mut V, cell: &mut TableCell<'a>) {
+    visitor.visit_cell(cell);
+    for content in cell.content.iter_mut() {
+        match content {
+            TableCellContent::Paragraph(para) => walk_paragraph_mut(visitor, para),
+            TableCellContent::Table(table) => walk_table_mut(visitor, table),
+            TableCellContent::SDT(sdt) => walk_sdt_mut(visitor, sdt),
+            _ => {}
+        }
+    }
+}
@@ -3,7 +3,7 @@ use async_zip::{Compression, ZipEntryBuilder};
 #[cfg(feature = "async")]
 use futures_io::{AsyncBufRead, AsyncWrite};
 use hard_xml::{XmlRead, XmlWrite, XmlWriter};
-use std::borrow::Cow;
+use std::borrow::{Borrow, Cow};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Read, Seek, Write};
@@ -11,28 +11,53 @@ use std::path::Path;
 use zip::write::SimpleFileOptions;
 use zip::{result::ZipError, CompressionMethod, ZipArchive, ZipWriter};
 
-use crate::document::{Comments, EndNotes, FootNotes, Footer, Header, Numbering, Theme};
-use crate::media::MediaType;
+use crate::document::{
+    add_toc_styles, document_properties, heading_levels, is_merge_field, is_toc, refresh_toc_sdt,
+    toc_anchors, toc_field_levels, toc_sdt, walk_blocks, walk_blocks_mut, walk_paragraph,
+    walk_paragraph_mut, BodyContent, BookmarkTexts, Bookmarks, Comments, Drawing, EndNotes, Field,
+    FieldContext, FieldScanner, FieldUpdater, FootNotes, Footer, Header, HeaderFooterReferenceType,
+    Hyperlink, IdRemapper, Merger, Numbering, Paragraph, Record, RevisionFilter, RevisionIds,
+    RevisionResolver, Run, RunContent, Theme, ThemeResolver, TocHeadings, TrackChanges, Visit,
+    VisitMut,
+};
+use crate::error::DocxError;
+use crate::media::{
+    get_media_content_type, get_media_type, get_media_type_relation_type, ImageFormat, ImageInfo,
+    MediaType,
+};
 use crate::schema::{
-    SCHEMA_COMMENTS, SCHEMA_ENDNOTES, SCHEMA_FOOTNOTES, SCHEMA_HEADER, SCHEMA_NUMBERING,
-    SCHEMA_SETTINGS, SCHEMA_THEME, SCHEMA_WEB_SETTINGS,
+    SCHEMA_COMMENTS, SCHEMA_ENDNOTES, SCHEMA_FOOTER, SCHEMA_FOOTNOTES, SCHEMA_HEADER,
+    SCHEMA_HYPERLINK, SCHEMA_NUMBERING, SCHEMA_SETTINGS, SCHEMA_THEME, SCHEMA_WEB_SETTINGS,
 };
-use crate::settings::Settings;
+use crate::settings::{EvenAndOddHeaders, Settings, UpdateFields};
 use crate::web_settings::WebSettings;
 use crate::{
     app::App,
-    content_type::ContentTypes,
+    content_type::{
+        ContentTypes, CONTENT_TYPE_COMMENTS, CONTENT_TYPE_CORE, CONTENT_TYPE_CUSTOM,
+        CONTENT_TYPE_DOCUMENT, CONTENT_TYPE_ENDNOTES, CONTENT_TYPE_EXTENDED,
+        CONTENT_TYPE_FONT_TABLE, CONTENT_TYPE_FOOTER, CONTENT_TYPE_FOOTNOTES, CONTENT_TYPE_HEADER,
+        CONTENT_TYPE_NUMBERING, CONTENT_TYPE_SETTINGS, CONTENT_TYPE_STYLES, CONTENT_TYPE_THEME,
+        CONTENT_TYPE_WEB_SETTINGS,
+    },
     core::Core,
+    custom::CustomProperties,
     document::Document,
     error::DocxResult,
     font_table::FontTable,
-    rels::Relationships,
+    formatting::{CharacterProperty, ParagraphProperty, SectionProperty},
+    owned::IntoOwned,
+    rels::{Relationship, Relationships, TargetMode},
     schema::{
-        SCHEMA_CORE, SCHEMA_FONT_TABLE, SCHEMA_OFFICE_DOCUMENT, SCHEMA_REL_EXTENDED, SCHEMA_STYLES,
+        SCHEMA_CORE, SCHEMA_CUSTOM, SCHEMA_FONT_TABLE, SCHEMA_OFFICE_DOCUMENT, SCHEMA_REL_EXTENDED,
+        SCHEMA_STYLES,
     },
-    styles::Styles,
+    styles::{locate_cell, overlay, StyleRemapper, StyleType, Styles},
 };
 
+/// The id of the character style of hyperlinks
+const HYPERLINK_STYLE: &str = "Hyperlink";
+
 /// A WordprocessingML package
 #[derive(Debug, Default, Clone)]
 pub struct Docx<'a> {
@@ -40,6 +65,8 @@ pub struct Docx<'a> {
     pub app: Option<App<'a>>,
     /// Specifies core properties part
     pub core: Option<Core<'a>>,
+    /// Specifies custom properties part
+    pub custom_properties: Option<CustomProperties<'a>>,
     /// Specifies the content type of relationship parts and the main document part.
     pub content_types: ContentTypes<'a>,
     /// Specifies the main document part.
@@ -56,7 +83,7 @@ pub struct Docx<'a> {
     pub headers: HashMap<String, Header<'a>>,
     pub footers: HashMap<String, Footer<'a>>,
     pub themes: HashMap<String, Theme<'a>>,
-    pub media: HashMap<String, (MediaType, &'a Vec<u8>)>,
+    pub media: HashMap<String, (MediaType, Cow<'a, [u8]>)>,
     pub footnotes: Option<FootNotes<'a>>,
     pub endnotes: Option<EndNotes<'a>>,
     pub settings: Option<Settings<'a>>,
@@ -64,15 +91,50 @@ pub struct Docx<'a> {
     pub comments: Option<Comments<'a>>,
     pub numbering: Option<Numbering<'a>>,
     pub custom_xml: HashMap<String, Cow<'a, [u8]>>,
+    /// Specifies the parts which are not modeled by this crate
+    ///
+    /// They are kept as raw bytes, keyed by their path in the package, and written back unchanged.
+    pub opaque_parts: HashMap<String, Cow<'a, [u8]>>,
 }
 
 impl<'a> Docx<'a> {
-    pub fn write<W: Write + Seek>(&'a mut self, writer: W) -> DocxResult<W> {
+    /// Creates an empty document with the styles of a new blank Word document
+    ///
+    /// See [`Styles::builtin`] for the styles it defines.
+    ///
+    /// ```rust
+    /// use docx_rust::document::Paragraph;
+    /// use docx_rust::formatting::ParagraphProperty;
+    /// use docx_rust::Docx;
+    ///
+    /// let mut docx = Docx::with_default_styles();
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .property(ParagraphProperty::default().style_id("Heading1"))
+    ///         .push_text("Introduction"),
+    /// );
+    /// assert!(docx.styles.get("Heading1").is_some());
+    /// ```
+    pub fn with_default_styles() -> Self {
+        Docx {
+            styles: Styles::builtin(),
+            ..Default::default()
+        }
+    }
+
+    /// Writes the package into `writer`
+    ///
+    /// The output is reproducible: parts are written in a fixed order, with headers, footers,
+    /// themes, media and other parts sorted by name, relationships added on the way get the same
+    /// ids every time, and every entry carries the timestamp 1980-01-01 00:00.
+    pub fn write<W: Write + Seek>(&mut self, writer: W) -> DocxResult<W> {
         let mut writer = XmlWriter::new(ZipWriter::new(writer));
 
+        // like Word, every entry gets the same timestamp, so the output only depends on the content
         let opt = SimpleFileOptions::default()
             .compression_method(CompressionMethod::Deflated)
-            .unix_permissions(0o755);
+            .unix_permissions(0o755)
+            .last_modified_time(zip::DateTime::default());
 
         // ==== Add Relationships ====
 
@@ -84,6 +146,10 @@ impl<'a> Docx<'a> {
             self.rels.add_rel(SCHEMA_CORE, "docProps/core.xml");
         }
 
+        if self.custom_properties.is_some() {
+            self.rels.add_rel(SCHEMA_CUSTOM, "docProps/custom.xml");
+        }
+
         self.rels
             .add_rel(SCHEMA_OFFICE_DOCUMENT, "word/document.xml");
 
@@ -133,31 +199,33 @@ impl<'a> Docx<'a> {
                 .add_rel(SCHEMA_NUMBERING, "numbering.xml");
         }
 
-        for hd in &self.headers {
+        for hd in sorted(&self.headers) {
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(SCHEMA_HEADER, hd.0);
+                .add_rel(SCHEMA_HEADER, hd.0.clone());
         }
 
-        for ft in &self.footers {
+        for ft in sorted(&self.footers) {
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(SCHEMA_HEADER, ft.0);
+                .add_rel(SCHEMA_FOOTER, ft.0.clone());
         }
 
-        for theme in &self.themes {
+        for theme in sorted(&self.themes) {
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(SCHEMA_THEME, theme.0);
+                .add_rel(SCHEMA_THEME, theme.0.clone());
         }
 
-        for media in &self.media {
+        for media in sorted(&self.media) {
             let rel = crate::media::get_media_type_relation_type(&media.1 .0);
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(rel, media.0);
+                .add_rel(rel, media.0.clone());
         }
 
+        self.reconcile_content_types();
+
         // ==== Write Zip Item ====
 
         macro_rules! write_xml {
@@ -184,6 +252,7 @@ impl<'a> Docx<'a> {
             self.content_types        => "[Content_Types].xml"
             Some(self.app)            => "docProps/app.xml"
             Some(self.core)           => "docProps/core.xml"
+            Some(self.custom_properties) => "docProps/custom.xml"
             self.rels                 => "_rels/.rels"
             self.document             => "word/document.xml"
             self.styles               => "word/styles.xml"
@@ -198,7 +267,7 @@ impl<'a> Docx<'a> {
             Some(self.settings_rels)  => "word/_rels/settings.xml.rels"
         );
 
-        for hd in self.headers.iter() {
+        for hd in sorted(&self.headers) {
             let file_path = format!("word/{}", hd.0);
             let content = hd.1;
             write_xml!(
@@ -206,7 +275,7 @@ impl<'a> Docx<'a> {
             );
         }
 
-        for hd in self.footers.iter() {
+        for hd in sorted(&self.footers) {
             let file_path = format!("word/{}", hd.0);
             let content = hd.1;
             write_xml!(
@@ -214,7 +283,7 @@ impl<'a> Docx<'a> {
             );
         }
 
-        for theme in self.themes.iter() {
+        for theme in sorted(&self.themes) {
             let file_path = format!("word/{}", theme.0);
             let content = theme.1;
             write_xml!(
@@ -222,13 +291,18 @@ impl<'a> Docx<'a> {
             );
         }
 
-        for media in self.media.iter() {
+        for media in sorted(&self.media) {
             let file_path = format!("word/{}", media.0);
             writer.inner.start_file(file_path, opt)?;
-            writer.inner.write_all(media.1 .1)?;
+            writer.inner.write_all(&media.1 .1)?;
         }
 
-        for (file_path, content) in &self.custom_xml {
+        for (file_path, content) in sorted(&self.custom_xml) {
+            writer.inner.start_file(file_path.clone(), opt)?;
+            writer.inner.write_all(content)?;
+        }
+
+        for (file_path, content) in sorted(&self.opaque_parts) {
             writer.inner.start_file(file_path.clone(), opt)?;
             writer.inner.write_all(content)?;
         }
@@ -236,18 +310,1554 @@ impl<'a> Docx<'a> {
         Ok(writer.inner.finish()?)
     }
 
-    pub fn write_file<P: AsRef<Path>>(&'a mut self, path: P) -> DocxResult<File> {
+    pub fn write_file<P: AsRef<Path>>(&mut self, path: P) -> DocxResult<File> {
         if let Some(p) = path.as_ref().parent() {
             std::fs::create_dir_all(p)?;
         }
         let file = File::create(path)?;
         self.write(file)
     }
+
+    /// Converts into a `Docx` which owns all of its data
+    ///
+    /// The returned `Docx` no longer borrows from the [`DocxFile`] it was parsed from.
+    pub fn into_owned(self) -> Docx<'static> {
+        let Docx {
+            app,
+            core,
+            custom_properties,
+            content_types,
+            document,
+            font_table,
+            styles,
+            rels,
+            document_rels,
+            settings_rels,
+            headers,
+            footers,
+            themes,
+            media,
+            footnotes,
+            endnotes,
+            settings,
+            web_settings,
+            comments,
+            numbering,
+            custom_xml,
+            opaque_parts,
+        } = self;
+
+        Docx {
+            app: IntoOwned::into_owned(app),
+            core: IntoOwned::into_owned(core),
+            custom_properties: IntoOwned::into_owned(custom_properties),
+            content_types: IntoOwned::into_owned(content_types),
+            document: IntoOwned::into_owned(document),
+            font_table: IntoOwned::into_owned(font_table),
+            styles: IntoOwned::into_owned(styles),
+            rels: IntoOwned::into_owned(rels),
+            document_rels: IntoOwned::into_owned(document_rels),
+            settings_rels: IntoOwned::into_owned(settings_rels),
+            headers: IntoOwned::into_owned(headers),
+            footers: IntoOwned::into_owned(footers),
+            themes: IntoOwned::into_owned(themes),
+            media: media
+                .into_iter()
+                .map(|(name, (ty, content))| (name, (ty, IntoOwned::into_owned(content))))
+                .collect(),
+            footnotes: IntoOwned::into_owned(footnotes),
+            endnotes: IntoOwned::into_owned(endnotes),
+            settings: IntoOwned::into_owned(settings),
+            web_settings,
+            comments: IntoOwned::into_owned(comments),
+            numbering: IntoOwned::into_owned(numbering),
+            custom_xml: IntoOwned::into_owned(custom_xml),
+            opaque_parts: IntoOwned::into_owned(opaque_parts),
+        }
+    }
+
+    /// Accepts every tracked change in the document
+    ///
+    /// The body, headers, footers, footnotes and endnotes are rewritten to their final state.
+    pub fn accept_all_revisions(&mut self) {
+        self.accept_revisions(&RevisionFilter::default());
+    }
+
+    /// Rejects every tracked change in the document
+    ///
+    /// The body, headers, footers, footnotes and endnotes are rewritten to their original state.
+    pub fn reject_all_revisions(&mut self) {
+        self.reject_revisions(&RevisionFilter::default());
+    }
+
+    /// Accepts the tracked changes selected by `filter`, leaving the others untouched.
+    pub fn accept_revisions(&mut self, filter: &RevisionFilter) {
+        self.resolve_revisions(RevisionResolver::new(filter, true));
+    }
+
+    /// Rejects the tracked changes selected by `filter`, leaving the others untouched.
+    pub fn reject_revisions(&mut self, filter: &RevisionFilter) {
+        self.resolve_revisions(RevisionResolver::new(filter, false));
+    }
+
+    /// Creates a [`TrackChanges`] for this document, numbering new revisions after the ones
+    /// the document already has
+    ///
+    /// Revision ids have to be unique in the document, and [`TrackChanges::new`] starts at 0.
+    ///
+    /// ```rust
+    /// use docx_rust::document::{Insertion, Paragraph, Run};
+    /// use docx_rust::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// let insertion = Insertion::default()
+    ///     .id(4isize)
+    ///     .push(Run::default().push_text("NAME"));
+    /// docx.document.push(Paragraph::default().push(insertion));
+    ///
+    /// let mut track = docx.track_changes("Bot");
+    /// assert_eq!(track.next_id, 5);
+    /// docx.replace_text_tracked([("NAME", "Jane")], &mut track).unwrap();
+    /// ```
+    pub fn track_changes<'b, T: Into<Cow<'b, str>>>(&mut self, author: T) -> TrackChanges<'b> {
+        TrackChanges::new(author).next_id(self.next_revision_id())
+    }
+
+    /// Replaces text in the body, headers and footers, recording each replacement as a
+    /// tracked change
+    ///
+    /// Works like [`Body::replace_text_tracked`]. Headers and footers are handled in the
+    /// order of their names, after the body.
+    ///
+    /// [`Body::replace_text_tracked`]: crate::document::Body::replace_text_tracked
+    pub fn replace_text_tracked<'b, I, T, S>(
+        &mut self,
+        dic: T,
+        track: &mut TrackChanges,
+    ) -> DocxResult<()>
+    where
+        S: AsRef<str> + 'b,
+        T: IntoIterator<Item = I> + Copy,
+        I: Borrow<(S, S)>,
+    {
+        self.document.body.replace_text_tracked(dic, track)?;
+        for (_, header) in sorted_mut(&mut self.headers) {
+            header.replace_text_tracked(dic, track)?;
+        }
+        for (_, footer) in sorted_mut(&mut self.footers) {
+            footer.replace_text_tracked(dic, track)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_revisions(&mut self, mut resolver: RevisionResolver) {
+        resolver.resolve_blocks(&mut self.document.body.content);
+        for header in self.headers.values_mut() {
+            resolver.resolve_blocks(&mut header.content);
+        }
+        for footer in self.footers.values_mut() {
+            resolver.resolve_blocks(&mut footer.content);
+        }
+        if let Some(footnotes) = &mut self.footnotes {
+            for note in footnotes.content.iter_mut() {
+                resolver.resolve_blocks(&mut note.content);
+            }
+        }
+        if let Some(endnotes) = &mut self.endnotes {
+            for note in endnotes.content.iter_mut() {
+                resolver.resolve_blocks(&mut note.content);
+            }
+        }
+    }
+
+    /// Adds an image to the package and returns a run displaying it inline
+    ///
+    /// The format and size are read from `bytes`, which has to be a PNG, JPEG, GIF, BMP, TIFF,
+    /// EMF or WMF image; use [`Docx::add_svg_image`] for SVG images. `width` and `height` are
+    /// in EMUs. If only one of them is given, the other one keeps the aspect ratio; if neither
+    /// is given, the image is shown at the size derived from its resolution.
+    ///
+    /// The media part, its relationship from the main document and the content type of its
+    /// extension are registered as well. The run still has to be pushed into a paragraph.
+    ///
+    /// ```rust
+    /// use docx_rust::document::Paragraph;
+    /// use docx_rust::Docx;
+    ///
+    /// let gif = b"GIF89a\x20\x00\x10\x00\x00\x00\x00".to_vec();
+    ///
+    /// let mut docx = Docx::default();
+    /// let run = docx.add_image(gif, Some(914400), None).unwrap();
+    /// docx.document.push(Paragraph::default().push(run));
+    /// ```
+    pub fn add_image<B: Into<Cow<'a, [u8]>>>(
+        &mut self,
+        bytes: B,
+        width: Option<u64>,
+        height: Option<u64>,
+    ) -> DocxResult<Run<'a>> {
+        let bytes = bytes.into();
+        let info = ImageInfo::from_bytes(&bytes)
+            .filter(|info| info.format != ImageFormat::Svg)
+            .ok_or(DocxError::UnsupportedImage)?;
+
+        let (cx, cy) = fit_extent(&info, width, height);
+        let rel_id = self.add_media(info.format.extension(), bytes)?;
+        let id = self.next_drawing_id();
+
+        Ok(Run::default().push(Drawing::picture(rel_id, id, cx, cy)))
+    }
+
+    /// Adds an SVG image with its raster fallback and returns a run displaying it inline
+    ///
+    /// The picture refers to `fallback`, which has to be an image accepted by
+    /// [`Docx::add_image`], and carries the SVG image in an `asvg:svgBlip` extension.
+    /// If the SVG image specifies no size, the size of the fallback is used instead.
+    pub fn add_svg_image<S, F>(
+        &mut self,
+        svg: S,
+        fallback: F,
+        width: Option<u64>,
+        height: Option<u64>,
+    ) -> DocxResult<Run<'a>>
+    where
+        S: Into<Cow<'a, [u8]>>,
+        F: Into<Cow<'a, [u8]>>,
+    {
+        let (svg, fallback) = (svg.into(), fallback.into());
+        let fallback_info = ImageInfo::from_bytes(&fallback)
+            .filter(|info| info.format != ImageFormat::Svg)
+            .ok_or(DocxError::UnsupportedImage)?;
+        let info = ImageInfo::from_bytes(&svg)
+            .filter(|info| info.format == ImageFormat::Svg)
+            .unwrap_or_else(|| fallback_info.clone());
+
+        let (cx, cy) = fit_extent(&info, width, height);
+        let rel_id = self.add_media(fallback_info.format.extension(), fallback)?;
+        let svg_rel_id = self.add_media(ImageFormat::Svg.extension(), svg)?;
+        let id = self.next_drawing_id();
+
+        Ok(Run::default().push(Drawing::svg_picture(rel_id, svg_rel_id, id, cx, cy)))
+    }
+
+    /// Adds a media file to the package and returns the id of its relationship
+    ///
+    /// The kind of media and its content type are derived from `ext`, the extension of the file,
+    /// e.g. `"png"`, `"mp4"` or `"mp3"`. The file is named after its kind,
+    /// like `media/video1.mp4`.
+    pub fn add_media<B: Into<Cow<'a, [u8]>>>(&mut self, ext: &str, bytes: B) -> DocxResult<String> {
+        let ext = ext.to_ascii_lowercase();
+        let ty = get_media_content_type(&format!(".{}", ext))
+            .ok_or_else(|| DocxError::UnsupportedMedia(ext.clone()))?;
+        let media_type = get_media_type(&format!(".{}", ext))
+            .ok_or_else(|| DocxError::UnsupportedMedia(ext.clone()))?;
+
+        let name = self.next_media_name(&media_type, &ext);
+
+        self.content_types.add_default(ext, ty);
+        let rel_id = self
+            .document_rels
+            .get_or_insert(Relationships::default())
+            .add_rel(get_media_type_relation_type(&media_type), name.clone());
+        self.media.insert(name, (media_type, bytes.into()));
+
+        Ok(rel_id)
+    }
+
+    /// Returns a name for a media file, named after its kind, which is not in use
+    fn next_media_name(&self, media_type: &MediaType, ext: &str) -> String {
+        let kind = match media_type {
+            MediaType::Image => "image",
+            MediaType::Video => "video",
+            MediaType::Audio => "audio",
+        };
+        (1..)
+            .map(|i| format!("media/{}{}.{}", kind, i, ext))
+            .find(|name| {
+                !self.media.contains_key(name)
+                    && !self.opaque_parts.contains_key(&format!("word/{}", name))
+            })
+            .unwrap()
+    }
+
+    /// Returns a hyperlink to `url`, made of `runs`
+    ///
+    /// Every link gets an external relationship of its own, even if the URL is used already.
+    /// Runs without a character style get the "Hyperlink" style, which is added to the styles
+    /// part if it is missing. The hyperlink still has to be pushed into a paragraph.
+    ///
+    /// ```rust
+    /// use docx_rust::document::{Paragraph, Run};
+    /// use docx_rust::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// let link = docx.add_external_link(
+    ///     "https://www.rust-lang.org/",
+    ///     [Run::default().push_text("Rust")],
+    /// );
+    /// assert_eq!(Some("https://www.rust-lang.org/"), docx.hyperlink_url(&link));
+    /// docx.document.push(Paragraph::default().push(link));
+    /// ```
+    pub fn add_external_link<U, I>(&mut self, url: U, runs: I) -> Hyperlink<'a>
+    where
+        U: Into<Cow<'a, str>>,
+        I: IntoIterator<Item = Run<'a>>,
+    {
+        let rel_id = self
+            .document_rels
+            .get_or_insert(Relationships::default())
+            .add_external_rel(SCHEMA_HYPERLINK, url);
+
+        self.styles.add_builtin(HYPERLINK_STYLE);
+
+        runs.into_iter()
+            .map(|mut run| {
+                let property = run.property.get_or_insert_with(Default::default);
+                if property.style_id.is_none() {
+                    property.style_id = Some(HYPERLINK_STYLE.into());
+                }
+                run
+            })
+            .fold(Hyperlink::default().id(rel_id), Hyperlink::push_run)
+    }
+
+    /// Returns the URL an external hyperlink in the main document points to
+    pub fn hyperlink_url(&self, link: &Hyperlink) -> Option<&str> {
+        self.document_rels
+            .as_ref()?
+            .get_external_target(link.id.as_deref()?)
+    }
+
+    /// Inserts a table of contents at the start of the body, or in place of the existing one
+    ///
+    /// Headings of level 1 to `levels` are listed, recognized by their outline level or by
+    /// their style, like "heading 1". Each gets a `_Toc` bookmark, unless it has one already,
+    /// and an entry linking to it, styled with `TOC1` to `TOC9`; these styles and `TOCHeading`
+    /// for `title` are added if they are missing.
+    ///
+    /// Page numbers can only be computed by a layout engine, so `w:updateFields` is set in the
+    /// settings, making Word fill them in when it opens the document.
+    pub fn insert_toc(&mut self, levels: usize, title: Option<&str>) {
+        let levels = levels.clamp(1, 9);
+
+        let mut bookmarks = Bookmarks::default();
+        walk_blocks_mut(&mut bookmarks, &mut self.document.body.content);
+        let style_levels = heading_levels(&self.styles);
+        let mut headings = TocHeadings::new(&style_levels, levels, bookmarks);
+        walk_blocks_mut(&mut headings, &mut self.document.body.content);
+
+        let sdt = toc_sdt(&headings.entries, levels, title, self.text_width());
+        let body = &mut self.document.body.content;
+        match body.iter().position(is_toc) {
+            Some(index) => body[index] = sdt.into(),
+            None => body.insert(0, sdt.into()),
+        }
+
+        add_toc_styles(&mut self.styles, levels);
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .update_fields = Some(UpdateFields { val: Some(true) });
+    }
+
+    /// Brings the entries of the existing table of contents in line with the headings
+    ///
+    /// Entries are matched to headings by the `_Toc` bookmark their hyperlink points to. The
+    /// text of every entry is replaced by the current text of its heading, entries of removed
+    /// headings are dropped and entries of new headings are added, copying the formatting of
+    /// the existing ones. Page numbers are left as they are.
+    ///
+    /// Returns `false` if the body has no table of contents.
+    pub fn refresh_toc(&mut self) -> bool {
+        let Some(index) = self.document.body.content.iter().position(is_toc) else {
+            return false;
+        };
+        let BodyContent::Sdt(mut sdt) = self.document.body.content.remove(index) else {
+            unreachable!()
+        };
+        let levels = toc_field_levels(&sdt);
+        let anchors = toc_anchors(&sdt);
+
+        let mut bookmarks = Bookmarks::default();
+        walk_blocks_mut(&mut bookmarks, &mut self.document.body.content);
+        let style_levels = heading_levels(&self.styles);
+        let mut headings =
+            TocHeadings::new(&style_levels, levels, bookmarks).prefer_anchors(anchors);
+        walk_blocks_mut(&mut headings, &mut self.document.body.content);
+
+        refresh_toc_sdt(
+            &mut sdt,
+            &headings.entries,
+            &self.styles,
+            levels,
+            self.text_width(),
+        );
+        self.document.body.content.insert(index, sdt.into());
+        true
+    }
+
+    /// Returns the fields of the document
+    ///
+    /// Both simple fields and complex fields are returned, in the order they start in, from
+    /// the body, then the headers, footers, footnotes, endnotes and comments. Fields nested
+    /// in another field are returned as well; in the instruction of the outer field they are
+    /// replaced by their cached result.
+    ///
+    /// ```rust
+    /// use docx_rust::document::{FieldKind, Paragraph, Run, SimpleField};
+    /// use docx_rust::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// let page = SimpleField::default()
+    ///     .instr("PAGE")
+    ///     .push(Run::default().push_text("1"));
+    /// docx.document.push(Paragraph::default().push(page));
+    ///
+    /// let fields = docx.fields();
+    /// assert_eq!(fields[0].kind, FieldKind::Page);
+    /// assert_eq!(fields[0].result_text(), "1");
+    /// ```
+    pub fn fields(&self) -> Vec<Field<'a>> {
+        let mut scanner = FieldScanner::default();
+        self.walk_parts(&mut scanner);
+        scanner.into_fields()
+    }
+
+    /// Replaces the cached results of fields
+    ///
+    /// `replace` is called with every field returned by [`Docx::fields`], but nested fields
+    /// come before the field they are nested in, whose instruction then holds their new
+    /// result. It returns the new result text, or `None` to keep the cached result.
+    ///
+    /// The new text takes the place of the first text of the old result, keeping its
+    /// formatting, and the rest of the old result is removed. Returns the number of replaced
+    /// results.
+    pub fn replace_field_results<F>(&mut self, replace: F) -> usize
+    where
+        F: FnMut(&Field<'a>) -> Option<String>,
+    {
+        let mut scanner = FieldScanner::default();
+        self.walk_parts(&mut scanner);
+        let mut writer = scanner.into_writer(replace);
+        self.walk_parts_mut(&mut writer);
+        writer.replaced
+    }
+
+    /// Replaces fields by their cached results
+    ///
+    /// The fields selected by `predicate` lose their instruction, so their results are left
+    /// as plain content; fields nested in their instruction are removed with it. Returns the
+    /// number of unlinked fields.
+    pub fn unlink_fields<P>(&mut self, predicate: P) -> usize
+    where
+        P: FnMut(&Field<'a>) -> bool,
+    {
+        let mut scanner = FieldScanner::default();
+        self.walk_parts(&mut scanner);
+        let mut unlinker = scanner.into_unlinker(predicate);
+        self.walk_parts_mut(&mut unlinker);
+        unlinker.unlinked()
+    }
+
+    /// Updates the results of the fields whose values are known without laying out the pages
+    ///
+    /// These are:
+    ///
+    /// - `DATE` and `TIME`, showing [`FieldContext::now`],
+    /// - `DOCPROPERTY`, `AUTHOR`, `TITLE`, `SUBJECT`, `KEYWORDS`, `COMMENTS` and `LASTSAVEDBY`,
+    ///   showing the core, application and custom properties,
+    /// - `REF`, showing the text of a bookmark of the body, and `PAGEREF`, showing its page,
+    /// - `SEQ`, numbering the items of each sequence, like figure captions, in the order of the
+    ///   parts returned by [`Docx::fields`],
+    /// - `NUMPAGES`, showing [`FieldContext::pages`].
+    ///
+    /// Pages are estimated from the page and section breaks of the body if they are not given.
+    /// The format switches of the fields are applied as described by [`Field::format`]. Other
+    /// fields, and fields referring to missing properties or bookmarks, are left as they are.
+    /// Returns the number of updated fields.
+    ///
+    /// ```rust
+    /// use docx_rust::document::{DateTime, FieldContext, Paragraph, SimpleField};
+    /// use docx_rust::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// let date = SimpleField::default().instr(r#"DATE \@ "d MMMM yyyy""#);
+    /// docx.document.push(Paragraph::default().push_text("Printed on ").push(date));
+    ///
+    /// let now = DateTime::parse("2024-03-05").unwrap();
+    /// docx.update_fields(&FieldContext::default().now(now));
+    /// assert_eq!(docx.document.body.text(), "Printed on 5 March 2024");
+    /// ```
+    pub fn update_fields(&mut self, context: &FieldContext) -> usize {
+        let mut bookmarks = BookmarkTexts::default();
+        walk_blocks(&mut bookmarks, &self.document.body.content);
+        let properties = document_properties(
+            self.core.as_ref(),
+            self.app.as_ref(),
+            self.custom_properties.as_ref(),
+        );
+        let mut updater = FieldUpdater::new(context, properties, bookmarks);
+        self.replace_field_results(|field| updater.result(field))
+    }
+
+    /// Merges the document with every record of a mail merge data source
+    ///
+    /// Each record maps the column names used by `MERGEFIELD` fields to values; any iterator
+    /// of pairs does, like a `HashMap`, a `BTreeMap` or a vector of tuples. Column names are
+    /// matched ignoring case.
+    ///
+    /// Returns one document per record, where `MERGEFIELD`, `MERGEREC`, `IF`, `NEXT` and
+    /// `NEXTIF` fields are replaced by their results and the mail merge settings are removed.
+    /// The format switches of the fields, like `\* Upper` or `\@ "dd.MM.yyyy"`, are applied
+    /// as described by [`Field::format`]. A `NEXT` field, or a `NEXTIF` field whose condition
+    /// holds, makes the fields after it use the next record, so the document uses more than one.
+    ///
+    /// ```rust
+    /// use docx_rust::document::{Paragraph, SimpleField};
+    /// use docx_rust::Docx;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut docx = Docx::default();
+    /// let name = SimpleField::default().instr(r#"MERGEFIELD Name \* Upper"#);
+    /// docx.document.push(Paragraph::default().push_text("Dear ").push(name));
+    ///
+    /// let records = vec![
+    ///     HashMap::from([("Name", "Alice")]),
+    ///     HashMap::from([("Name", "Bob")]),
+    /// ];
+    /// let letters = docx.mail_merge(records);
+    /// assert_eq!(letters[1].document.body.text(), "Dear BOB");
+    /// ```
+    pub fn mail_merge<I, R, K, V>(&self, records: I) -> Vec<Docx<'a>>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let records: Vec<Record> = records
+            .into_iter()
+            .map(|record| {
+                record
+                    .into_iter()
+                    .map(|(key, value)| (key.as_ref().to_string(), value.as_ref().to_string()))
+                    .collect()
+            })
+            .collect();
+
+        let mut documents = Vec::new();
+        let mut next = 0;
+        while next < records.len() {
+            let mut merger = Merger::new(&records, next);
+            let mut docx = self.clone();
+            docx.replace_field_results(|field| merger.result(field));
+            docx.unlink_fields(is_merge_field);
+            if let Some(settings) = &mut docx.settings {
+                settings.mail_merge = None;
+            }
+            documents.push(docx);
+            next = merger.current + 1;
+        }
+        documents
+    }
+
+    /// Merges the document with every record of a mail merge data source into one document
+    ///
+    /// Works like [`Docx::mail_merge`], but the bodies of the merged documents follow each
+    /// other, each starting a new section with the section properties of the document. The
+    /// other parts, like headers, footers and notes, are the ones merged with the first record.
+    /// The bookmarks, drawings and revisions of the following bodies get new ids, and
+    /// bookmarks are renamed like in [`Docx::append_document`], so they stay unique.
+    ///
+    /// Returns `None` if there are no records.
+    pub fn mail_merge_combined<I, R, K, V>(&self, records: I) -> Option<Docx<'a>>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut documents = self.mail_merge(records).into_iter();
+        let mut combined = documents.next()?;
+        for mut docx in documents {
+            let mut remapper = IdRemapper::default();
+            combined.reconcile_bookmarks(&mut docx, &mut remapper);
+            remapper.drawing_offset = combined.next_drawing_id() - 1;
+            remapper.revisions.offset = combined.next_revision_id();
+            walk_blocks_mut(&mut remapper, &mut docx.document.body.content);
+
+            let body = &mut combined.document.body;
+            end_last_section(&mut body.content);
+            body.content.extend(docx.document.body.content);
+        }
+        Some(combined)
+    }
+
+    /// Appends the content of another document to this one
+    ///
+    /// The body of `other` follows the body of this document in a new section, and keeps its
+    /// own sections, with their headers and footers. What the content of `other` refers to is
+    /// carried over, with new ids wherever they would clash with the ones of this document:
+    ///
+    /// - styles defined differently in both documents are renamed, except for the default
+    ///   styles, which are replaced by the ones of this document,
+    /// - numbering definitions get new `w:numId` and `w:abstractNumId`,
+    /// - media files which differ from the ones of this document with the same name are
+    ///   renamed, and images, hyperlinks, headers and footers get new relationships, in the
+    ///   main document part as well as in notes and comments,
+    /// - footnotes, endnotes, comments, bookmarks, drawings and revisions get new ids, and
+    ///   bookmarks whose name is taken are renamed, along with the hyperlinks and fields
+    ///   referring to them.
+    ///
+    /// The document defaults, settings, theme and font table of `other` are dropped.
+    ///
+    /// ```rust
+    /// use docx_rust::document::Paragraph;
+    /// use docx_rust::formatting::ParagraphProperty;
+    /// use docx_rust::styles::{Style, StyleType};
+    /// use docx_rust::Docx;
+    ///
+    /// let mut cover = Docx::default();
+    /// cover.styles.push(Style::new(StyleType::Paragraph, "Big"));
+    /// cover.document.push(Paragraph::default().push_text("Cover"));
+    ///
+    /// let mut body = Docx::default();
+    /// body.styles.push(Style::new(StyleType::Paragraph, "Big").name("Big"));
+    /// body.document.push(
+    ///     Paragraph::default()
+    ///         .property(ParagraphProperty::default().style_id("Big"))
+    ///         .push_text("Body"),
+    /// );
+    ///
+    /// cover.append_document(body);
+    /// assert_eq!(cover.document.body.text(), "Cover\r\nBody");
+    /// assert_eq!(cover.styles.get("Big1").unwrap().name.as_ref().unwrap().value, "Big 1");
+    /// ```
+    pub fn append_document(&mut self, mut other: Docx<'a>) {
+        self.reconcile_styles(&mut other);
+
+        let mut remapper = IdRemapper::default();
+        self.reconcile_numbering(&mut other, &mut remapper);
+        for style in other.styles.styles.iter_mut() {
+            if let Some(property) = &mut style.paragraph {
+                remapper.remap_paragraph_property(property);
+            }
+        }
+        self.reconcile_notes(&mut other, &mut remapper);
+        self.reconcile_bookmarks(&mut other, &mut remapper);
+        remapper.drawing_offset = self.next_drawing_id() - 1;
+        remapper.revisions.offset = self.next_revision_id();
+        remapper.relationships = self.move_related_parts(&mut other, &mut remapper);
+
+        let body = &mut other.document.body.content;
+        walk_blocks_mut(&mut remapper, body);
+        for content in body.iter_mut() {
+            if let BodyContent::SectionProperty(section) = content {
+                remapper.remap_section(section);
+            }
+        }
+
+        let even_and_odd = other.settings.as_ref().map(|s| &s.even_and_odd_headers);
+        if matches!(even_and_odd, Some(Some(_))) {
+            self.enable_header_footer_kind(&HeaderFooterReferenceType::Even);
+        }
+        for style in other.styles.styles {
+            if self.styles.get(&style.style_id).is_none() {
+                self.styles.styles.push(style);
+            }
+        }
+        match (&mut self.numbering, other.numbering) {
+            (Some(numbering), Some(other)) => {
+                numbering
+                    .abstract_numberings
+                    .extend(other.abstract_numberings);
+                numbering.numberings.extend(other.numberings);
+            }
+            (numbering, other) => *numbering = numbering.take().or(other),
+        }
+        match (&mut self.footnotes, other.footnotes) {
+            (Some(notes), Some(other)) => notes.content.extend(other.content),
+            (notes, other) => *notes = notes.take().or(other),
+        }
+        match (&mut self.endnotes, other.endnotes) {
+            (Some(notes), Some(other)) => notes.content.extend(other.content),
+            (notes, other) => *notes = notes.take().or(other),
+        }
+        match (&mut self.comments, other.comments) {
+            (Some(comments), Some(other)) => comments.comments.extend(other.comments),
+            (comments, other) => *comments = comments.take().or(other),
+        }
+
+        let body = &mut self.document.body.content;
+        if !body.is_empty() {
+            end_last_section(body);
+        }
+        body.extend(other.document.body.content);
+    }
+
+    /// Gives the bookmarks of `other` ids following the ones of this document, and renames
+    /// those whose name is taken
+    fn reconcile_bookmarks(&mut self, other: &mut Docx<'a>, remapper: &mut IdRemapper) {
+        let mut own_bookmarks = Bookmarks::default();
+        self.walk_parts_mut(&mut own_bookmarks);
+        let mut bookmarks = Bookmarks::default();
+        other.walk_parts_mut(&mut bookmarks);
+        remapper.bookmark_offset = own_bookmarks.max_id + 1;
+        let mut names: Vec<_> = bookmarks.names.iter().collect();
+        names.sort_unstable();
+        for name in names {
+            if own_bookmarks.names.contains(name) {
+                let new_name = (1..)
+                    .map(|i| format!("{}_{}", name, i))
+                    .find(|n| !own_bookmarks.names.contains(n) && !bookmarks.names.contains(n))
+                    .unwrap();
+                own_bookmarks.names.insert(new_name.clone());
+                remapper.bookmarks.insert(name.clone(), new_name);
+            }
+        }
+    }
+
+    /// Renames the styles of `other` which are defined differently in this document
+    fn reconcile_styles(&self, other: &mut Docx<'a>) {
+        let mut defaults = Vec::new();
+        let mut conflicts = Vec::new();
+        for style in other.styles.styles.iter() {
+            if style.default == Some(true) {
+                let own = style.ty.clone().and_then(|ty| self.styles.default_of(ty));
+                if let Some(own) = own {
+                    if own.style_id != style.style_id {
+                        defaults.push((style.style_id.to_string(), own.style_id.to_string()));
+                    }
+                    continue;
+                }
+            }
+            if let Some(own) = self.styles.get(&style.style_id) {
+                if own.to_string().ok() != style.to_string().ok() {
+                    conflicts.push(style.style_id.to_string());
+                }
+            }
+        }
+
+        for old_id in conflicts {
+            let (n, new_id) = (1..)
+                .map(|n| (n, format!("{}{}", old_id, n)))
+                .find(|(_, id)| self.styles.get(id).is_none() && other.styles.get(id).is_none())
+                .unwrap();
+            other.remap_style(&old_id, &new_id);
+            let renamed = other
+                .styles
+                .styles
+                .iter_mut()
+                .find(|s| s.style_id == new_id);
+            if let Some(name) = renamed.and_then(|style| style.name.as_mut()) {
+                name.value = format!("{} {}", name.value, n).into();
+            }
+        }
+        for (old_id, new_id) in defaults {
+            other.remap_style(&old_id, &new_id);
+        }
+    }
+
+    /// Gives the numbering definitions of `other` ids following the ones of this document
+    fn reconcile_numbering(&self, other: &mut Docx<'a>, remapper: &mut IdRemapper) {
+        let Some(numbering) = &mut other.numbering else {
+            return;
+        };
+        let own = self.numbering.as_ref();
+        let abstract_offset = own
+            .and_then(|own| {
+                let ids = own.abstract_numberings.iter();
+                ids.filter_map(|n| n.abstract_num_id).max()
+            })
+            .map_or(0, |id| id + 1);
+        let num_offset = own
+            .and_then(|own| own.numberings.iter().filter_map(|n| n.num_id).max())
+            .unwrap_or(0);
+
+        for abstract_num in numbering.abstract_numberings.iter_mut() {
+            if let Some(id) = &mut abstract_num.abstract_num_id {
+                *id += abstract_offset;
+            }
+        }
+        for num in numbering.numberings.iter_mut() {
+            if let Some(id) = num
+                .abstract_num_id
+                .as_mut()
+                .and_then(|id| id.value.as_mut())
+            {
+                *id += abstract_offset;
+            }
+            if let Some(id) = &mut num.num_id {
+                remapper.numberings.insert(*id, *id + num_offset);
+                *id += num_offset;
+            }
+        }
+    }
+
+    /// Gives the notes and comments of `other` ids following the ones of this document
+    ///
+    /// The separators of `other` are dropped if this document has notes of the same kind.
+    fn reconcile_notes(&self, other: &mut Docx<'a>, remapper: &mut IdRemapper) {
+        if let (Some(own), Some(notes)) = (&self.footnotes, &mut other.footnotes) {
+            let first = own.content.iter().filter_map(|n| n.id).max().unwrap_or(0) + 1;
+            notes.content.retain(|note| note.ty.is_none());
+            let ids = notes.content.iter_mut().filter_map(|n| n.id.as_mut());
+            for (id, new_id) in ids.zip(first..) {
+                remapper
+                    .footnotes
+                    .insert(id.to_string(), new_id.to_string());
+                *id = new_id;
+            }
+        }
+        if let (Some(own), Some(notes)) = (&self.endnotes, &mut other.endnotes) {
+            let first = own.content.iter().filter_map(|n| n.id).max().unwrap_or(0) + 1;
+            notes.content.retain(|note| note.ty.is_none());
+            let ids = notes.content.iter_mut().filter_map(|n| n.id.as_mut());
+            for (id, new_id) in ids.zip(first..) {
+                remapper.endnotes.insert(id.to_string(), new_id.to_string());
+                *id = new_id;
+            }
+        }
+        if let (Some(own), Some(comments)) = (&self.comments, &mut other.comments) {
+            let first = own.comments.iter().filter_map(|c| c.id).max().unwrap_or(-1) + 1;
+            let ids = comments.comments.iter_mut().filter_map(|c| c.id.as_mut());
+            for (id, new_id) in ids.zip(first..) {
+                remapper.comments.insert(id.to_string(), new_id.to_string());
+                *id = new_id;
+            }
+        }
+    }
+
+    /// Moves the media, headers, footers and unmodeled parts the main document part of `other`
+    /// relates to, and returns the new ids of its relationships
+    ///
+    /// The content of the headers, footers, notes and comments is remapped on the way, the
+    /// relationships of notes and comments being merged into the ones of this document.
+    fn move_related_parts(
+        &mut self,
+        other: &mut Docx<'a>,
+        remapper: &mut IdRemapper,
+    ) -> HashMap<String, String> {
+        let mut media: Vec<_> = other.media.drain().collect();
+        media.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        let mut media_names = HashMap::new();
+        for (name, (media_type, bytes)) in media {
+            let taken = self.opaque_parts.contains_key(&format!("word/{}", name));
+            let new_name = match self.media.get(&name) {
+                Some((_, own)) if *own == bytes => name.clone(),
+                None if !taken => name.clone(),
+                _ => {
+                    let ext = name.rsplit_once('.').map_or("", |(_, ext)| ext);
+                    self.next_media_name(&media_type, ext)
+                }
+            };
+            self.media.insert(new_name.clone(), (media_type, bytes));
+            media_names.insert(name, new_name);
+        }
+
+        let mut relationships = HashMap::new();
+        let rels = other.document_rels.take().unwrap_or_default();
+        for rel in rels.relationships {
+            let target = if rel.target_mode == Some(TargetMode::External) {
+                rel.target
+            } else if let Some(name) = media_names.get(&*rel.target) {
+                name.clone().into()
+            } else if let Some(mut header) = other.headers.remove(&*rel.target) {
+                walk_blocks_mut(remapper, &mut header.content);
+                let name = self.next_header_footer_name("header");
+                self.move_part_rels(other, &rel.target, &name, &media_names);
+                self.content_types
+                    .add_override(format!("/word/{}", name), CONTENT_TYPE_HEADER);
+                self.headers.insert(name.clone(), header);
+                name.into()
+            } else if let Some(mut footer) = other.footers.remove(&*rel.target) {
+                walk_blocks_mut(remapper, &mut footer.content);
+                let name = self.next_header_footer_name("footer");
+                self.move_part_rels(other, &rel.target, &name, &media_names);
+                self.content_types
+                    .add_override(format!("/word/{}", name), CONTENT_TYPE_FOOTER);
+                self.footers.insert(name.clone(), footer);
+                name.into()
+            } else if let Some(bytes) = other.opaque_parts.remove(&format!("word/{}", rel.target)) {
+                let name = (0..)
+                    .map(|i| match (i, rel.target.rsplit_once('.')) {
+                        (0, _) => rel.target.to_string(),
+                        (i, Some((stem, ext))) => format!("{}_{}.{}", stem, i, ext),
+                        (i, None) => format!("{}_{}", rel.target, i),
+                    })
+                    .find(|name| !self.opaque_parts.contains_key(&format!("word/{}", name)))
+                    .unwrap();
+                let part = format!("/word/{}", rel.target);
+                if let Some(ty) = other.content_types.content_type(&part) {
+                    self.content_types
+                        .add_override(format!("/word/{}", name), ty.to_string());
+                }
+                self.move_part_rels(other, &rel.target, &name, &media_names);
+                self.opaque_parts.insert(format!("word/{}", name), bytes);
+                name.into()
+            } else {
+                // the parts every document has, like styles or settings
+                continue;
+            };
+
+            let own = self.document_rels.get_or_insert_with(Default::default);
+            let id = own.next_id();
+            own.relationships.push(Relationship {
+                id: id.clone().into(),
+                target,
+                ty: rel.ty,
+                target_mode: rel.target_mode,
+            });
+            relationships.insert(rel.id.to_string(), id);
+        }
+
+        remapper.relationships = self.merge_part_rels(other, "footnotes.xml", &media_names);
+        if let Some(footnotes) = &mut other.footnotes {
+            for note in footnotes.content.iter_mut() {
+                walk_blocks_mut(remapper, &mut note.content);
+            }
+        }
+        remapper.relationships = self.merge_part_rels(other, "endnotes.xml", &media_names);
+        if let Some(endnotes) = &mut other.endnotes {
+            for note in endnotes.content.iter_mut() {
+                walk_blocks_mut(remapper, &mut note.content);
+            }
+        }
+        remapper.relationships = self.merge_part_rels(other, "comments.xml", &media_names);
+        if let Some(comments) = &mut other.comments {
+            for comment in comments.comments.iter_mut() {
+                walk_paragraph_mut(remapper, &mut comment.content);
+            }
+        }
+        relationships
+    }
+
+    /// Adds the relationships of the part `word/{file}` of `other` to the ones of the same
+    /// part of this document, and returns their new ids
+    fn merge_part_rels(
+        &mut self,
+        other: &mut Docx<'a>,
+        file: &str,
+        media_names: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        let name = format!("word/_rels/{}.rels", file);
+        let parse = |bytes: &[u8]| {
+            std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|xml| Relationships::from_str(xml).ok())
+                .map(IntoOwned::into_owned)
+        };
+        let mut ids = HashMap::new();
+        let Some(rels) = other.opaque_parts.remove(&name).and_then(|b| parse(&b)) else {
+            return ids;
+        };
+        let mut own = self
+            .opaque_parts
+            .get(&name)
+            .and_then(|bytes| parse(bytes))
+            .unwrap_or_default();
+        for rel in rels.relationships {
+            let target = match media_names.get(&*rel.target) {
+                Some(name) if rel.target_mode != Some(TargetMode::External) => name.clone().into(),
+                _ => rel.target,
+            };
+            let id = own.next_id();
+            own.relationships.push(Relationship {
+                id: id.clone().into(),
+                target,
+                ty: rel.ty,
+                target_mode: rel.target_mode,
+            });
+            ids.insert(rel.id.to_string(), id);
+        }
+        if let Ok(xml) = own.to_string() {
+            self.opaque_parts.insert(name, Cow::Owned(xml.into_bytes()));
+        }
+        ids
+    }
+
+    /// Moves the relationships of a part of `other` which is renamed to `name`, pointing them
+    /// at the new names of the media files
+    fn move_part_rels(
+        &mut self,
+        other: &mut Docx<'a>,
+        target: &str,
+        name: &str,
+        media_names: &HashMap<String, String>,
+    ) {
+        let rels_name = |target: &str| match target.rsplit_once('/') {
+            Some((dir, file)) => format!("word/{}/_rels/{}.rels", dir, file),
+            None => format!("word/_rels/{}.rels", target),
+        };
+        let Some(bytes) = other.opaque_parts.remove(&rels_name(target)) else {
+            return;
+        };
+        let rewritten = std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|xml| Relationships::from_str(xml).ok())
+            .and_then(|mut rels| {
+                for rel in rels.relationships.iter_mut() {
+                    if let Some(new_name) = media_names.get(&*rel.target) {
+                        rel.target = new_name.clone().into();
+                    }
+                }
+                rels.to_string().ok()
+            });
+        let bytes = match rewritten {
+            Some(xml) => Cow::Owned(xml.into_bytes()),
+            None => bytes,
+        };
+        self.opaque_parts.insert(rels_name(name), bytes);
+    }
+
+    /// Returns the formatting a run of the paragraph is displayed with
+    ///
+    /// The run properties are collected from the document defaults, the table style if the
+    /// paragraph sits in a table, the paragraph style, the numbering level, the character style
+    /// and the run itself, later ones winning. Paragraphs and runs without a style get the
+    /// default style of their type. Bold, italics and the other toggle properties flip between
+    /// the table, paragraph and character styles the way Word does.
+    ///
+    /// The table is found by looking for the paragraph itself in the document, so it has to be
+    /// borrowed from this `Docx` for table styles to apply.
+    ///
+    /// ```rust
+    /// use docx_rust::document::{Paragraph, Run};
+    /// use docx_rust::formatting::{CharacterProperty, ParagraphProperty};
+    /// use docx_rust::styles::{Style, StyleType};
+    /// use docx_rust::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// docx.styles.push(
+    ///     Style::new(StyleType::Paragraph, "Title")
+    ///         .character(CharacterProperty::default().bold(true).size(56isize)),
+    /// );
+    /// docx.styles.push(
+    ///     Style::new(StyleType::Character, "Strong")
+    ///         .character(CharacterProperty::default().bold(true)),
+    /// );
+    ///
+    /// let para = Paragraph::default().property(ParagraphProperty::default().style_id("Title"));
+    /// let run = Run::default()
+    ///     .property(CharacterProperty::default().style_id("Strong"))
+    ///     .push_text("Plain");
+    ///
+    /// let property = docx.effective_run_properties(&para, &run);
+    /// assert_eq!(property.size.unwrap().value, 56);
+    /// assert_eq!(property.bold.unwrap().value, Some(false));
+    /// ```
+    pub fn effective_run_properties(
+        &self,
+        para: &Paragraph<'a>,
+        run: &Run<'a>,
+    ) -> CharacterProperty<'a> {
+        let styles = &self.styles;
+        let mut property = styles
+            .default
+            .as_ref()
+            .and_then(|default| default.character.inner.clone())
+            .unwrap_or_default();
+
+        let mut parts = vec![&self.document.body.content[..]];
+        parts.extend(self.headers.values().map(|header| &header.content[..]));
+        parts.extend(self.footers.values().map(|footer| &footer.content[..]));
+        if let Some(footnotes) = &self.footnotes {
+            parts.extend(footnotes.content.iter().map(|note| &note.content[..]));
+        }
+        if let Some(endnotes) = &self.endnotes {
+            parts.extend(endnotes.content.iter().map(|note| &note.content[..]));
+        }
+        if let Some(cell) = parts
+            .into_iter()
+            .find_map(|blocks| locate_cell(blocks, para))
+        {
+            let table_style = match &cell.table.property.style_id {
+                Some(id) => Some(&*id.value),
+                None => styles
+                    .default_of(StyleType::Table)
+                    .map(|style| &*style.style_id),
+            };
+            if let Some(id) = table_style {
+                overlay(
+                    &mut property,
+                    &styles.table_run_properties(id, &cell, para),
+                    true,
+                );
+            }
+        }
+
+        let para_style = match para.property.as_ref().and_then(|p| p.style_id.as_ref()) {
+            Some(id) => Some(&*id.value),
+            None => styles
+                .default_of(StyleType::Paragraph)
+                .map(|style| &*style.style_id),
+        };
+        if let Some(id) = para_style {
+            overlay(&mut property, &styles.style_run_properties(id), true);
+        }
+
+        let numbering = styles.paragraph_numbering(para, para_style);
+        let level = numbering.and_then(|numbering| {
+            let level = numbering.level.as_ref().map_or(0, |level| level.value);
+            self.numbering
+                .as_ref()?
+                .numbering_details(numbering.id.as_ref()?.value)?
+                .levels
+                .into_iter()
+                .find(|l| l.i_level == Some(level))
+        });
+        for r_pr in level.into_iter().flat_map(|level| level.r_pr) {
+            overlay(&mut property, &r_pr.into_owned(), false);
+        }
+
+        let direct = run.property.as_ref();
+        let run_style = match direct.and_then(|p| p.style_id.as_ref()) {
+            Some(id) => Some(&*id.value),
+            None => styles
+                .default_of(StyleType::Character)
+                .map(|style| &*style.style_id),
+        };
+        if let Some(id) = run_style {
+            overlay(&mut property, &styles.style_run_properties(id), true);
+        }
+        if let Some(direct) = direct {
+            overlay(&mut property, direct, false);
+        }
+
+        property
+    }
+
+    /// Returns a resolver for the theme colors and theme fonts used in the document
+    ///
+    /// The theme is the one related to the main document part, the mapping of its text and
+    /// background colors comes from the settings. Returns `None` if there is no theme.
+    ///
+    /// ```rust
+    /// use docx_rust::Docx;
+    ///
+    /// let docx = Docx::default();
+    /// assert!(docx.theme_resolver().is_none());
+    /// ```
+    pub fn theme_resolver(&self) -> Option<ThemeResolver<'_, 'a>> {
+        let related = self
+            .document_rels
+            .as_ref()
+            .and_then(|rels| rels.relationships.iter().find(|rel| rel.ty == SCHEMA_THEME))
+            .and_then(|rel| self.themes.get(&*rel.target));
+        let theme = match related {
+            Some(theme) => theme,
+            None => sorted(&self.themes).into_iter().next()?.1,
+        };
+        let mapping = self
+            .settings
+            .as_ref()
+            .and_then(|settings| settings.clr_scheme_mapping.as_ref());
+        Some(ThemeResolver::new(theme, mapping))
+    }
+
+    /// Replaces a style with another one everywhere in the document
+    ///
+    /// Every paragraph, run and table of the body, the headers, the footers, the notes and the
+    /// comments which refers to `old_id` is changed to refer to `new_id`, and so are the styles
+    /// which are based on, link to or name `old_id` as the style of the next paragraph, the
+    /// default table and click and type styles of the settings and the numbering levels. If
+    /// `new_id` is defined already, the definition of `old_id` is removed, otherwise it is
+    /// renamed. Returns the number of rewritten references.
+    ///
+    /// ```rust
+    /// use docx_rust::document::Paragraph;
+    /// use docx_rust::formatting::ParagraphProperty;
+    /// use docx_rust::Docx;
+    ///
+    /// let mut docx = Docx::with_default_styles();
+    /// docx.document.push(
+    ///     Paragraph::default().property(ParagraphProperty::default().style_id("Heading1")),
+    /// );
+    ///
+    /// docx.remap_style("Heading1", "Title");
+    ///
+    /// assert!(docx.styles.get("Heading1").is_none());
+    /// assert_eq!(docx.styles.get("TOCHeading").unwrap().base.as_ref().unwrap().value, "Title");
+    /// ```
+    pub fn remap_style(&mut self, old_id: &str, new_id: &str) -> usize {
+        if old_id == new_id {
+            return 0;
+        }
+        let mut remapper = StyleRemapper {
+            old_id,
+            new_id,
+            count: 0,
+        };
+        self.walk_parts_mut(&mut remapper);
+        remapper.remap_styles(&mut self.styles);
+        if let Some(settings) = &mut self.settings {
+            remapper.remap_settings(settings);
+        }
+        if let Some(numbering) = &mut self.numbering {
+            remapper.remap_numbering(numbering);
+        }
+
+        if self.styles.get(new_id).is_some() {
+            self.styles.styles.retain(|style| style.style_id != old_id);
+        } else if let Some(style) = self
+            .styles
+            .styles
+            .iter_mut()
+            .find(|style| style.style_id == old_id)
+        {
+            style.style_id = new_id.to_string().into();
+        }
+        remapper.count
+    }
+
+    /// Returns the width between the margins of the last section, in twentieths of a point
+    fn text_width(&self) -> isize {
+        let sect_pr = self
+            .document
+            .body
+            .content
+            .iter()
+            .rev()
+            .find_map(|content| match content {
+                BodyContent::SectionProperty(sect_pr) => Some(sect_pr),
+                _ => None,
+            });
+        let width = sect_pr.and_then(|sect_pr| Some(sect_pr.page_size.as_ref()?.weight));
+        let margin = sect_pr.and_then(|sect_pr| sect_pr.page_margin.as_ref());
+        match (width, margin) {
+            (Some(width), Some(margin)) => {
+                width - margin.left.unwrap_or_default() - margin.right.unwrap_or_default()
+            }
+            // Letter with margins of one inch
+            _ => 9360,
+        }
+    }
+
+    /// Adds a header part and returns the id of its relationship
+    ///
+    /// The header is shown on the pages selected by `kind` in the last section, whose
+    /// properties are added to the end of the body if they are missing. To use it in other
+    /// sections too, pass the id to [`SectionProperty::set_header_reference`] on the entries of
+    /// [`Body::sections_mut`].
+    ///
+    /// [`SectionProperty::set_header_reference`]: crate::formatting::SectionProperty::set_header_reference
+    /// [`Body::sections_mut`]: crate::document::Body::sections_mut
+    ///
+    /// An even page header also turns on `w:evenAndOddHeaders` in the settings.
+    ///
+    /// ```rust
+    /// use docx_rust::document::{Header, HeaderFooterReferenceType, Paragraph};
+    /// use docx_rust::Docx;
+    ///
+    /// let mut header = Header::default();
+    /// header.push(Paragraph::default().push_text("Draft"));
+    ///
+    /// let mut docx = Docx::default();
+    /// let id = docx.add_header(HeaderFooterReferenceType::Default, header);
+    /// assert_eq!(id, "rId1");
+    /// ```
+    pub fn add_header(&mut self, kind: HeaderFooterReferenceType, header: Header<'a>) -> String {
+        let name = self.next_header_footer_name("header");
+        let rel_id = self
+            .document_rels
+            .get_or_insert(Relationships::default())
+            .add_rel(SCHEMA_HEADER, name.clone());
+        self.content_types
+            .add_override(format!("/word/{}", name), CONTENT_TYPE_HEADER);
+        self.headers.insert(name, header);
+
+        self.enable_header_footer_kind(&kind);
+        self.document
+            .body
+            .section_property_mut()
+            .set_header_reference(kind, rel_id.clone());
+        rel_id
+    }
+
+    /// Adds a footer part and returns the id of its relationship
+    ///
+    /// Works like [`Docx::add_header`].
+    pub fn add_footer(&mut self, kind: HeaderFooterReferenceType, footer: Footer<'a>) -> String {
+        let name = self.next_header_footer_name("footer");
+        let rel_id = self
+            .document_rels
+            .get_or_insert(Relationships::default())
+            .add_rel(SCHEMA_FOOTER, name.clone());
+        self.content_types
+            .add_override(format!("/word/{}", name), CONTENT_TYPE_FOOTER);
+        self.footers.insert(name, footer);
+
+        self.enable_header_footer_kind(&kind);
+        self.document
+            .body
+            .section_property_mut()
+            .set_footer_reference(kind, rel_id.clone());
+        rel_id
+    }
+
+    /// Declares the content type of every part which is written, and only of those
+    fn reconcile_content_types(&mut self) {
+        let modeled = [
+            (
+                self.app.is_some(),
+                "docProps/app.xml",
+                Some(CONTENT_TYPE_EXTENDED),
+            ),
+            (
+                self.core.is_some(),
+                "docProps/core.xml",
+                Some(CONTENT_TYPE_CORE),
+            ),
+            (
+                self.custom_properties.is_some(),
+                "docProps/custom.xml",
+                Some(CONTENT_TYPE_CUSTOM),
+            ),
+            (true, "_rels/.rels", None),
+            (true, "word/document.xml", Some(CONTENT_TYPE_DOCUMENT)),
+            (true, "word/styles.xml", Some(CONTENT_TYPE_STYLES)),
+            (
+                self.font_table.is_some(),
+                "word/fontTable.xml",
+                Some(CONTENT_TYPE_FONT_TABLE),
+            ),
+            (
+                self.footnotes.is_some(),
+                "word/footnotes.xml",
+                Some(CONTENT_TYPE_FOOTNOTES),
+            ),
+            (
+                self.endnotes.is_some(),
+                "word/endnotes.xml",
+                Some(CONTENT_TYPE_ENDNOTES),
+            ),
+            (
+                self.settings.is_some(),
+                "word/settings.xml",
+                Some(CONTENT_TYPE_SETTINGS),
+            ),
+            (
+                self.web_settings.is_some(),
+                "word/webSettings.xml",
+                Some(CONTENT_TYPE_WEB_SETTINGS),
+            ),
+            (
+                self.comments.is_some(),
+                "word/comments.xml",
+                Some(CONTENT_TYPE_COMMENTS),
+            ),
+            (
+                self.numbering.is_some(),
+                "word/numbering.xml",
+                Some(CONTENT_TYPE_NUMBERING),
+            ),
+            (
+                self.document_rels.is_some(),
+                "word/_rels/document.xml.rels",
+                None,
+            ),
+            (
+                self.settings_rels.is_some(),
+                "word/_rels/settings.xml.rels",
+                None,
+            ),
+        ];
+        let mut parts: Vec<(String, Option<&'static str>)> = modeled
+            .into_iter()
+            .filter(|(written, _, _)| *written)
+            .map(|(_, name, ty)| (format!("/{}", name), ty))
+            .collect();
+        for (name, _) in sorted(&self.headers) {
+            parts.push((format!("/word/{}", name), Some(CONTENT_TYPE_HEADER)));
+        }
+        for (name, _) in sorted(&self.footers) {
+            parts.push((format!("/word/{}", name), Some(CONTENT_TYPE_FOOTER)));
+        }
+        for (name, _) in sorted(&self.themes) {
+            parts.push((format!("/word/{}", name), Some(CONTENT_TYPE_THEME)));
+        }
+        for (name, _) in sorted(&self.media) {
+            if let (Some((_, ext)), Some(ty)) =
+                (name.rsplit_once('.'), get_media_content_type(name))
+            {
+                self.content_types.add_default(ext.to_ascii_lowercase(), ty);
+            }
+            parts.push((format!("/word/{}", name), None));
+        }
+        // unmodeled parts keep the content type they were read with
+        for (name, _) in sorted(&self.custom_xml)
+            .into_iter()
+            .chain(sorted(&self.opaque_parts))
+        {
+            parts.push((format!("/{}", name), None));
+        }
+
+        self.content_types.reconcile(&parts);
+    }
+
+    /// Returns the first free name like `header1.xml`, relative to `word/`
+    fn next_header_footer_name(&self, prefix: &str) -> String {
+        (1..)
+            .map(|i| format!("{}{}.xml", prefix, i))
+            .find(|name| {
+                !self.headers.contains_key(name)
+                    && !self.footers.contains_key(name)
+                    && !self.opaque_parts.contains_key(&format!("word/{}", name))
+            })
+            .unwrap()
+    }
+
+    /// Even page headers and footers are ignored unless the settings turn them on
+    fn enable_header_footer_kind(&mut self, kind: &HeaderFooterReferenceType) {
+        if let HeaderFooterReferenceType::Even = kind {
+            self.settings
+                .get_or_insert_with(Settings::default)
+                .even_and_odd_headers = Some(EvenAndOddHeaders::default());
+        }
+    }
+
+    /// Returns an id for `wp:docPr`, which has to be unique across the document
+    fn next_drawing_id(&mut self) -> isize {
+        let mut max_id = MaxDrawingId(0);
+        self.walk_parts_mut(&mut max_id);
+        max_id.0 + 1
+    }
+
+    /// Returns an id following the ids of the revisions of the document
+    fn next_revision_id(&mut self) -> isize {
+        let mut revisions = RevisionIds::default();
+        self.walk_parts_mut(&mut revisions);
+        revisions.max_id + 1
+    }
+
+    /// Walks the content of every part which holds paragraphs
+    ///
+    /// Headers and footers are walked in the order of their names.
+    pub(crate) fn walk_parts<V: Visit<'a>>(&self, visitor: &mut V) {
+        walk_blocks(visitor, &self.document.body.content);
+        for (_, header) in sorted(&self.headers) {
+            walk_blocks(visitor, &header.content);
+        }
+        for (_, footer) in sorted(&self.footers) {
+            walk_blocks(visitor, &footer.content);
+        }
+        if let Some(footnotes) = &self.footnotes {
+            for note in footnotes.content.iter() {
+                walk_blocks(visitor, &note.content);
+            }
+        }
+        if let Some(endnotes) = &self.endnotes {
+            for note in endnotes.content.iter() {
+                walk_blocks(visitor, &note.content);
+            }
+        }
+        if let Some(comments) = &self.comments {
+            for comment in comments.comments.iter() {
+                walk_paragraph(visitor, &comment.content);
+            }
+        }
+    }
+
+    /// Walks the same content as [`Docx::walk_parts`], in the same order
+    fn walk_parts_mut<V: VisitMut<'a>>(&mut self, visitor: &mut V) {
+        walk_blocks_mut(visitor, &mut self.document.body.content);
+        for (_, header) in sorted_mut(&mut self.headers) {
+            walk_blocks_mut(visitor, &mut header.content);
+        }
+        for (_, footer) in sorted_mut(&mut self.footers) {
+            walk_blocks_mut(visitor, &mut footer.content);
+        }
+        if let Some(footnotes) = &mut self.footnotes {
+            for note in footnotes.content.iter_mut() {
+                walk_blocks_mut(visitor, &mut note.content);
+            }
+        }
+        if let Some(endnotes) = &mut self.endnotes {
+            for note in endnotes.content.iter_mut() {
+                walk_blocks_mut(visitor, &mut note.content);
+            }
+        }
+        if let Some(comments) = &mut self.comments {
+            for comment in comments.comments.iter_mut() {
+                walk_paragraph_mut(visitor, &mut comment.content);
+            }
+        }
+    }
+}
+
+/// Moves the section properties at the end of a body into its last paragraph, so the
+/// content added afterwards starts a new section
+fn end_last_section<'a>(content: &mut Vec<BodyContent<'a>>) {
+    let property = match content
+        .iter()
+        .rposition(|content| matches!(content, BodyContent::SectionProperty(_)))
+    {
+        Some(index) => match content.remove(index) {
+            BodyContent::SectionProperty(property) => property,
+            _ => unreachable!(),
+        },
+        None => SectionProperty::default(),
+    };
+    if let Some(BodyContent::Paragraph(para)) = content.last_mut() {
+        let para_property = para.property.get_or_insert_with(Default::default);
+        if para_property.section_property.is_none() {
+            para_property.section_property = Some(property);
+            return;
+        }
+    }
+    content.push(
+        Paragraph::default()
+            .property(ParagraphProperty {
+                section_property: Some(property),
+                ..Default::default()
+            })
+            .into(),
+    );
+}
+
+/// Scales the natural size of an image to the requested width or height, both in EMUs
+fn fit_extent(info: &ImageInfo, width: Option<u64>, height: Option<u64>) -> (u64, u64) {
+    let (cx, cy) = info.extent();
+    match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        (Some(width), None) => (width, width * cy / cx.max(1)),
+        (None, Some(height)) => (height * cx / cy.max(1), height),
+        (None, None) => (cx, cy),
+    }
+}
+
+/// Finds the largest `wp:docPr` id
+struct MaxDrawingId(isize);
+
+impl<'a> VisitMut<'a> for MaxDrawingId {
+    fn visit_run(&mut self, run: &mut Run<'a>) {
+        for content in run.content.iter() {
+            if let RunContent::Drawing(drawing) = content {
+                let inline = drawing.inline.iter().map(|inline| inline.doc_property.id);
+                let anchor = drawing.anchor.iter().map(|anchor| anchor.doc_property.id);
+                self.0 = inline.chain(anchor).flatten().fold(self.0, isize::max);
+            }
+        }
+    }
+}
+
+impl Docx<'static> {
+    /// Reads a docx file into a `Docx` which owns all of its data
+    ///
+    /// This is [`DocxFile::from_reader`] followed by [`DocxFile::parse`] and
+    /// [`Docx::into_owned`], so the result can be kept without the `DocxFile`.
+    pub fn from_reader<T: Read + Seek>(reader: T) -> DocxResult<Self> {
+        Ok(DocxFile::from_reader(reader)?.parse()?.into_owned())
+    }
+
+    /// Reads a docx file into a `Docx` which owns all of its data, see [`Docx::from_reader`]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> DocxResult<Self> {
+        Self::from_reader(File::open(path)?)
+    }
 }
 
 #[cfg(feature = "async")]
 impl<'a> Docx<'a> {
-    pub async fn write_async<W: AsyncWrite + Unpin>(&'a mut self, writer: W) -> DocxResult<W> {
+    pub async fn write_async<W: AsyncWrite + Unpin>(&mut self, writer: W) -> DocxResult<W> {
         use async_zip::base::write::ZipFileWriter;
 
         let mut writer = ZipFileWriter::new(writer);
@@ -262,6 +1872,10 @@ impl<'a> Docx<'a> {
             self.rels.add_rel(SCHEMA_CORE, "docProps/core.xml");
         }
 
+        if self.custom_properties.is_some() {
+            self.rels.add_rel(SCHEMA_CUSTOM, "docProps/custom.xml");
+        }
+
         self.rels
             .add_rel(SCHEMA_OFFICE_DOCUMENT, "word/document.xml");
 
@@ -311,31 +1925,33 @@ impl<'a> Docx<'a> {
                 .add_rel(SCHEMA_NUMBERING, "numbering.xml");
         }
 
-        for hd in &self.headers {
+        for hd in sorted(&self.headers) {
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(SCHEMA_HEADER, hd.0);
+                .add_rel(SCHEMA_HEADER, hd.0.clone());
         }
 
-        for ft in &self.footers {
+        for ft in sorted(&self.footers) {
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(SCHEMA_HEADER, ft.0);
+                .add_rel(SCHEMA_FOOTER, ft.0.clone());
         }
 
-        for theme in &self.themes {
+        for theme in sorted(&self.themes) {
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(SCHEMA_THEME, theme.0);
+                .add_rel(SCHEMA_THEME, theme.0.clone());
         }
 
-        for media in &self.media {
+        for media in sorted(&self.media) {
             let rel = crate::media::get_media_type_relation_type(&media.1 .0);
             self.document_rels
                 .get_or_insert(Relationships::default())
-                .add_rel(rel, media.0);
+                .add_rel(rel, media.0.clone());
         }
 
+        self.reconcile_content_types();
+
         // ==== Write Zip Item ====
 
         macro_rules! write_xml {
@@ -351,7 +1967,8 @@ impl<'a> Docx<'a> {
             ($xml:expr => $name:tt) => {
                 let mut buf = XmlWriter::new(Vec::new());
                 $xml.to_writer(&mut buf)?;
-                let opt = ZipEntryBuilder::new(($name.as_ref() as &str).into(), Compression::Deflate);
+                let opt = ZipEntryBuilder::new(($name.as_ref() as &str).into(), Compression::Deflate)
+                    .last_modification_date(zip_date_time());
                 writer.write_entry_whole(opt, &buf.into_inner()).await?;
             };
             ($xml:expr => $name:tt $($rest:tt)*) => {
@@ -364,6 +1981,7 @@ impl<'a> Docx<'a> {
             self.content_types        => "[Content_Types].xml"
             Some(self.app)            => "docProps/app.xml"
             Some(self.core)           => "docProps/core.xml"
+            Some(self.custom_properties) => "docProps/custom.xml"
             self.rels                 => "_rels/.rels"
             self.document             => "word/document.xml"
             self.styles               => "word/styles.xml"
@@ -378,47 +1996,82 @@ impl<'a> Docx<'a> {
             Some(self.settings_rels)  => "word/_rels/settings.xml.rels"
         );
 
-        for (filename, content) in self.headers.iter() {
+        for (filename, content) in sorted(&self.headers) {
             let file_path = format!("word/{}", filename);
             write_xml!(
                 content => file_path
             );
         }
 
-        for (filename, content) in self.footers.iter() {
+        for (filename, content) in sorted(&self.footers) {
             let file_path = format!("word/{}", filename);
             write_xml!(
                 content => file_path
             );
         }
 
-        for (filename, content) in self.themes.iter() {
+        for (filename, content) in sorted(&self.themes) {
             let file_path = format!("word/{}", filename);
             write_xml!(
                 content => file_path
             );
         }
 
-        for (filename, (_, content)) in self.media.iter() {
+        for (filename, (_, content)) in sorted(&self.media) {
             let file_path = format!("word/{}", filename);
-            let opt = ZipEntryBuilder::new(file_path.as_str().into(), Compression::Deflate);
+            let opt = ZipEntryBuilder::new(file_path.as_str().into(), Compression::Deflate)
+                .last_modification_date(zip_date_time());
             writer.write_entry_whole(opt, content).await?;
         }
 
-        for (file_path, content) in &self.custom_xml {
-            let opt = ZipEntryBuilder::new(file_path.as_str().into(), Compression::Deflate);
+        for (file_path, content) in sorted(&self.custom_xml) {
+            let opt = ZipEntryBuilder::new(file_path.as_str().into(), Compression::Deflate)
+                .last_modification_date(zip_date_time());
             writer.write_entry_whole(opt, &content).await?;
         }
 
+        for (file_path, content) in sorted(&self.opaque_parts) {
+            let opt = ZipEntryBuilder::new(file_path.as_str().into(), Compression::Deflate)
+                .last_modification_date(zip_date_time());
+            writer.write_entry_whole(opt, content).await?;
+        }
+
         Ok(writer.close().await?)
     }
 }
 
+/// Returns the entries of a map of parts ordered by name
+///
+/// Parts and relationships are written in this order, so that the package doesn't depend on
+/// the iteration order of the `HashMap`.
+fn sorted<V>(parts: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut parts: Vec<_> = parts.iter().collect();
+    parts.sort_unstable_by_key(|(name, _)| *name);
+    parts
+}
+
+fn sorted_mut<V>(parts: &mut HashMap<String, V>) -> Vec<(&String, &mut V)> {
+    let mut parts: Vec<_> = parts.iter_mut().collect();
+    parts.sort_unstable_by_key(|(name, _)| *name);
+    parts
+}
+
+/// Returns the timestamp of every entry written by [`Docx::write_async`], the same as [`Docx::write`]
+#[cfg(feature = "async")]
+fn zip_date_time() -> async_zip::ZipDateTime {
+    async_zip::ZipDateTimeBuilder::new()
+        .year(1980)
+        .month(1)
+        .day(1)
+        .build()
+}
+
 /// An extracted docx file
 pub struct DocxFile {
     app: Option<String>,
     content_types: String,
     core: Option<String>,
+    custom_properties: Option<String>,
     document: String,
     document_rels: Option<String>,
     settings_rels: Option<String>,
@@ -436,6 +2089,34 @@ pub struct DocxFile {
     comments: Option<String>,
     numbering: Option<String>,
     custom_xml: Vec<(String, Vec<u8>)>,
+    opaque_parts: Vec<(String, Vec<u8>)>,
+}
+
+/// Returns `true` if the ZIP item is extracted into one of the typed fields of `DocxFile`.
+fn is_modeled_part(name: &str) -> bool {
+    matches!(
+        name,
+        "docProps/app.xml"
+            | "[Content_Types].xml"
+            | "docProps/core.xml"
+            | "docProps/custom.xml"
+            | "word/_rels/document.xml.rels"
+            | "word/_rels/settings.xml.rels"
+            | "word/document.xml"
+            | "word/fontTable.xml"
+            | "_rels/.rels"
+            | "word/styles.xml"
+            | "word/settings.xml"
+            | "word/webSettings.xml"
+            | "word/footnotes.xml"
+            | "word/endnotes.xml"
+            | "word/comments.xml"
+            | "word/numbering.xml"
+    ) || name.contains("word/header")
+        || name.contains("word/footer")
+        || name.contains("word/theme/theme")
+        || name.contains("word/media")
+        || name.contains("custom")
 }
 
 impl DocxFile {
@@ -505,6 +2186,7 @@ impl DocxFile {
         let app = option_read!(App, "docProps/app.xml");
         let content_types = read!(ContentTypes, "[Content_Types].xml");
         let core = option_read!(Core, "docProps/core.xml");
+        let custom_properties = option_read!(CustomProperties, "docProps/custom.xml");
         let document_rels = option_read!(Relationships, "word/_rels/document.xml.rels");
         let settings_rels = option_read!(Relationships, "word/_rels/settings.xml.rels");
         let document = read!(Document, "word/document.xml");
@@ -522,12 +2204,27 @@ impl DocxFile {
         let footers = option_read_multiple!(Footers, "word/footer");
         let themes = option_read_multiple!(Themes, "word/theme/theme");
         let medias = option_read_multiple_files!(Medias, "word/media");
-        let custom_xml = option_read_multiple_files!(_, "custom");
+        let mut custom_xml = option_read_multiple_files!(_, "custom");
+        custom_xml.retain(|(name, _)| name != "docProps/custom.xml");
+
+        let names: Vec<_> = zip
+            .file_names()
+            .filter(|n| !n.ends_with('/') && !is_modeled_part(n))
+            .map(|n| n.to_string())
+            .collect();
+        let mut opaque_parts = Vec::with_capacity(names.len());
+        for name in names {
+            let mut file = zip.by_name(&name)?;
+            let mut buffer = Vec::new();
+            file.read_to_end(&mut buffer)?;
+            opaque_parts.push((name, buffer));
+        }
 
         Ok(DocxFile {
             app,
             content_types,
             core,
+            custom_properties,
             document_rels,
             settings_rels,
             document,
@@ -545,6 +2242,7 @@ impl DocxFile {
             comments,
             numbering,
             custom_xml,
+            opaque_parts,
         })
     }
 
@@ -578,13 +2276,22 @@ impl DocxFile {
             footers.insert(name, ft);
         }
 
+        let mut opaque_parts: HashMap<_, _> = self
+            .opaque_parts
+            .iter()
+            .map(|(name, content)| (name.to_string(), Cow::Borrowed(content.as_slice())))
+            .collect();
+
         let mut media = HashMap::new();
         for m in self.medias.iter() {
             let mt = crate::media::get_media_type(&m.0);
             if let Some(mt) = mt {
                 let name = m.0.replace("word/", "");
-                let m = (mt, &m.1);
+                let m = (mt, Cow::Borrowed(m.1.as_slice()));
                 media.insert(name, m);
+            } else {
+                // keep unsupported media as-is, its relationship is kept as well
+                opaque_parts.insert(m.0.to_string(), Cow::Borrowed(m.1.as_slice()));
             }
         }
 
@@ -604,38 +2311,17 @@ impl DocxFile {
             None
         };
 
-        let document_rels: Option<Relationships> = if let Some(content) = &self.document_rels {
+        let custom_properties = self
+            .custom_properties
+            .as_deref()
+            .map(CustomProperties::from_str)
+            .transpose()?;
+
+        let document_rels = if let Some(content) = &self.document_rels {
             Some(Relationships::from_str(content)?)
         } else {
             None
         };
-        let document_rels = document_rels.map(|rel: Relationships| {
-            let rrr: Vec<_> = rel
-                .relationships
-                .iter()
-                .filter(|r2| {
-                    matches!(
-                        r2.ty.to_string().as_str(),
-                        crate::schema::SCHEMA_HEADER
-                            | crate::schema::SCHEMA_FOOTER
-                            | crate::schema::SCHEMA_THEME
-                            | crate::schema::SCHEMA_FONT_TABLE
-                            | crate::schema::SCHEMA_STYLES
-                            | crate::schema::SCHEMA_FOOTNOTES
-                            | crate::schema::SCHEMA_ENDNOTES
-                            | crate::schema::SCHEMA_SETTINGS
-                            | crate::schema::SCHEMA_WEB_SETTINGS
-                            | crate::schema::SCHEMA_COMMENTS
-                            | crate::schema::SCHEMA_IMAGE
-                            | crate::schema::SCHEMA_HYPERLINK
-                            | crate::schema::SCHEMA_NUMBERING
-                    )
-                })
-                .map(|d| d.to_owned())
-                .collect();
-            Relationships { relationships: rrr }
-        });
-
         let settings_rels = self
             .settings_rels
             .as_deref()
@@ -687,22 +2373,6 @@ impl DocxFile {
         };
 
         let rels = Relationships::from_str(&self.rels)?;
-        let rels = {
-            let rrr: Vec<_> = rels
-                .relationships
-                .iter()
-                .filter(|r2| {
-                    matches!(
-                        r2.ty.to_string().as_str(),
-                        crate::schema::SCHEMA_CORE
-                            | crate::schema::SCHEMA_REL_EXTENDED
-                            | crate::schema::SCHEMA_OFFICE_DOCUMENT
-                    )
-                })
-                .map(|d| d.to_owned())
-                .collect();
-            Relationships { relationships: rrr }
-        };
 
         let styles = self
             .styles
@@ -721,6 +2391,7 @@ impl DocxFile {
             app,
             content_types,
             core,
+            custom_properties,
             document,
             document_rels,
             settings_rels,
@@ -738,6 +2409,7 @@ impl DocxFile {
             comments,
             numbering,
             custom_xml,
+            opaque_parts,
         })
     }
 }
@@ -753,6 +2425,7 @@ impl DocxFile {
             app: None,
             content_types: String::new(),
             core: None,
+            custom_properties: None,
             document: String::new(),
             document_rels: None,
             settings_rels: None,
@@ -770,6 +2443,7 @@ impl DocxFile {
             comments: None,
             numbering: None,
             custom_xml: vec![],
+            opaque_parts: vec![],
         };
 
         while let Some(mut next) = reader.next_with_entry().await? {
@@ -804,6 +2478,7 @@ impl DocxFile {
                 "docProps/app.xml" => read_to_string!(docx.app),
                 "[Content_Types].xml" => read_to_string!(docx.content_types),
                 "docProps/core.xml" => read_to_string!(docx.core),
+                "docProps/custom.xml" => read_to_string!(docx.custom_properties),
                 "word/_rels/document.xml.rels" => read_to_string!(docx.document_rels),
                 "word/_rels/settings.xml.rels" => read_to_string!(docx.settings_rels),
                 "word/document.xml" => read_to_string!(docx.document),
@@ -823,6 +2498,7 @@ impl DocxFile {
                 }
                 _ if filename.contains("word/media") => read_multiple_to_bytes!(docx.medias),
                 _ if filename.contains("custom") => read_multiple_to_bytes!(docx.custom_xml),
+                _ if !filename.ends_with('/') => read_multiple_to_bytes!(docx.opaque_parts),
                 _ => {}
             }
             reader = next.skip().await?;
@@ -13,6 +13,10 @@ pub enum DocxError {
     Xml(#[from] XmlError),
     #[error("unable to unpack file: {0}")]
     Zip(#[from] ZipError),
+    #[error("unsupported image format")]
+    UnsupportedImage,
+    #[error("unsupported media type: {0}")]
+    UnsupportedMedia(String),
     #[cfg(feature = "async")]
     #[error("unable to unpack file: {0}")]
     AsyncZip(#[from] async_zip::error::ZipError),
@@ -1,6 +1,8 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
+use crate::__into_owned;
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:charset")]
@@ -9,6 +11,8 @@ pub struct Charset<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { Charset { value } }
+
 impl<'a, S: Into<Cow<'a, str>>> From<S> for Charset<'a> {
     fn from(s: S) -> Self {
         Charset { value: s.into() }
@@ -2,7 +2,7 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
 use crate::{
-    __setter, __xml_test_suites,
+    __into_owned, __setter, __xml_test_suites,
     font_table::{Charset, Family, Pitch},
 };
 
@@ -30,6 +30,8 @@ pub struct Font<'a> {
     pub pitch: Option<Pitch<'a>>,
 }
 
+__into_owned! { Font { name, charset, family, pitch } }
+
 impl<'a> Font<'a> {
     __setter!(charset: Option<Charset<'a>>);
     __setter!(family: Option<Family<'a>>);
@@ -13,8 +13,8 @@ pub use self::{charset::*, family::*, font::*, pitch::*};
 use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::io::Write;
 
-use crate::__xml_test_suites;
 use crate::schema::{SCHEMA_MAIN, SCHEMA_RELATIONSHIPS_DOCUMENT, SCHEMA_XML};
+use crate::{__into_owned, __xml_test_suites};
 
 /// Font Table
 ///
@@ -33,6 +33,8 @@ pub struct FontTable<'a> {
     pub fonts: Vec<Font<'a>>,
 }
 
+__into_owned! { FontTable { fonts } }
+
 impl<'a> XmlWrite for FontTable<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let FontTable { fonts } = self;
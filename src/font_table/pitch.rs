@@ -1,6 +1,8 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
+use crate::__into_owned;
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:pitch")]
@@ -9,6 +11,8 @@ pub struct Pitch<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { Pitch { value } }
+
 impl<'a, S: Into<Cow<'a, str>>> From<S> for Pitch<'a> {
     fn from(s: S) -> Self {
         Pitch { value: s.into() }
@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__setter, __xml_test_suites, formatting::BorderStyle};
+use crate::{__into_owned, __setter, __xml_test_suites, formatting::BorderStyle};
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -27,6 +27,13 @@ pub struct BetweenBorder<'a> {
     pub frame: Option<bool>,
 }
 
+__into_owned! {
+    BetweenBorder {
+        color, theme_tint, theme_shade;
+        style, theme_color, size, space, shadow, frame
+    }
+}
+
 impl<'a> BetweenBorder<'a> {
     __setter!(color: Option<Cow<'a, str>>);
     __setter!(shadow: Option<bool>);
@@ -2,13 +2,19 @@ mod bar_border;
 mod between_border;
 mod border_style;
 mod bottom_border;
+mod end_border;
 mod inside_horizon_border;
 mod inside_vertical_border;
 mod left_border;
 mod right_border;
+mod start_border;
 mod top_border;
+mod top_left_to_bottom_right_border;
+mod top_right_to_bottom_left_border;
 
 pub use self::{
-    bar_border::*, between_border::*, border_style::*, bottom_border::*, inside_horizon_border::*,
-    inside_vertical_border::*, left_border::*, right_border::*, top_border::*,
+    bar_border::*, between_border::*, border_style::*, bottom_border::*, end_border::*,
+    inside_horizon_border::*, inside_vertical_border::*, left_border::*, right_border::*,
+    start_border::*, top_border::*, top_left_to_bottom_right_border::*,
+    top_right_to_bottom_left_border::*,
 };
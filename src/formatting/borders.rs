@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 
 use crate::{
-    __setter, __xml_test_suites,
+    __into_owned, __setter, __xml_test_suites,
     formatting::{BarBorder, BetweenBorder, BottomBorder, LeftBorder, RightBorder, TopBorder},
 };
 
@@ -24,6 +24,8 @@ pub struct Borders<'a> {
     pub bar: Option<BarBorder<'a>>,
 }
 
+__into_owned! { Borders { top, left, bottom, right, between, bar } }
+
 impl<'a> Borders<'a> {
     __setter!(top: Option<TopBorder<'a>>);
     __setter!(bottom: Option<BottomBorder<'a>>);
@@ -2,152 +2,253 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
 use crate::{
-    __setter, __string_enum, __xml_test_suites,
+    __into_owned, __setter, __string_enum, __xml_test_suites,
+    document::{DeletionMark, InsertionMark, MoveFromMark, MoveToMark},
     formatting::{Bold, Color, Dstrike, Fonts, Italics, Lang, Outline, Size, Strike, Underline},
 };
 
 use super::{BoldComplex, Caps, Highlight, ItalicsComplex, Position, SmallCaps, VertAlign};
 
-/// Character Property
+/// Defines a run properties element, with the formatting shared by every `w:rPr`
 ///
-/// ```rust
-/// use docx_rust::formatting::{CharacterProperty, UnderlineStyle, HighlightType};
-///
-/// let prop = CharacterProperty::default()
-///     .style_id("foo")
-///     .color("00ff00")
-///     .color(0xff0000)
-///     .color((0x00, 0x00, 0xff))
-///     .size(42isize)
-///     .bold(true)
-///     .italics(false)
-///     .strike(true)
-///     .dstrike(false)
-///     .outline(true)
-///     .highlight(HighlightType::Yellow)
-///     .underline("00ff00")
-///     .underline(("ff0000", UnderlineStyle::Dash));
-/// ```
-#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
-#[xml(tag = "w:rPr")]
-pub struct CharacterProperty<'a> {
-    /// Specifies the style ID of the character style.
-    #[xml(child = "w:rStyle")]
-    pub style_id: Option<CharacterStyleId<'a>>,
-    /// Specifies the font.
-    #[xml(child = "w:rFonts")]
-    pub fonts: Option<Fonts>,
-    /// Specifies that the text of the text run is to be bold.
-    #[xml(child = "w:b")]
-    pub bold: Option<Bold>,
-    #[xml(child = "w:bCs")]
-    pub bold_complex: Option<BoldComplex>,
-    /// Specifies that the text of the text run is to be italics.
-    #[xml(child = "w:i")]
-    pub italics: Option<Italics>,
-    ///  Complex Script Italics
-    #[xml(child = "w:iCs")]
-    pub italics_complex: Option<ItalicsComplex>,
-    ///  Display All Characters As Capital Letters
-    #[xml(child = "w:caps")]
-    pub caps: Option<Caps>,
-    ///  Small Caps
-    #[xml(child = "w:smallCaps")]
-    pub small_caps: Option<SmallCaps>,
-    /// Specifies that the contents are to be displayed with a horizontal line through the center of the line.
-    #[xml(child = "w:strike")]
-    pub strike: Option<Strike>,
-    /// Specifies that the contents are to be displayed with two horizontal lines through each character.
-    #[xml(child = "w:dstrike")]
-    pub dstrike: Option<Dstrike>,
-    /// Specifies that the content should be displayed as if it had an outline.
-    #[xml(child = "w:outline")]
-    pub outline: Option<Outline>,
-    ///  Shadow
-    #[xml(child = "w:shadow")]
-    pub shadow: Option<Shadow>,
-    ///  Embossing
-    #[xml(child = "w:emboss")]
-    pub emboss: Option<Emboss>,
-    ///  Imprinting
-    #[xml(child = "w:imprint")]
-    pub imprint: Option<Imprint>,
-    ///  Do Not Check Spelling or Grammar
-    #[xml(child = "w:noProof")]
-    pub no_proof: Option<NoProof>,
-    /// Use Document Grid Settings For Inter-Character Spacing
-    #[xml(child = "w:snapToGrid")]
-    pub snap_to_grid: Option<super::SnapToGrid>,
-    ///  Hidden Text
-    #[xml(child = "w:vanish")]
-    pub vanish: Option<Vanish>,
-    ///  Web Hidden Text
-    #[xml(child = "w:webHidden")]
-    pub web_hidden: Option<WebHidden>,
-    /// Specifies the color to be used to display text.
-    #[xml(child = "w:color")]
-    pub color: Option<Color<'a>>,
-    ///  Character Spacing Adjustment
-    #[xml(child = "w:spacing")]
-    pub spacing: Option<TextSpacing>,
-    ///Expanded/Compressed Text
-    #[xml(child = "w:w")]
-    pub scale: Option<Scale>,
-    ///  Font Kerning
-    #[xml(child = "w:kern")]
-    pub kern: Option<Kern>,
-    /// Vertically Raised or Lowered Text
-    #[xml(child = "w:position")]
-    pub position: Option<Position>,
-    /// Specifies the font size in half points.
-    #[xml(child = "w:sz")]
-    pub size: Option<Size>,
-    ///  Complex Script Font Size
-    #[xml(child = "w:szCs")]
-    pub size_complex: Option<SizeComplex>,
-    ///  Text Highlighting
-    #[xml(child = "w:highlight")]
-    pub highlight: Option<Highlight>,
-    /// Specifies that the content should be displayed with an underline
-    #[xml(child = "w:u")]
-    pub underline: Option<Underline<'a>>,
-    ///  Animated Text Effect
-    #[xml(child = "w:effect")]
-    pub effect: Option<Effect>,
-    ///  Text Border
-    #[xml(child = "w:bdr")]
-    pub border: Option<TextBorder<'a>>,
-    ///  Run Shading
-    #[xml(child = "w:shd")]
-    pub shading: Option<Shading<'a>>,
-    ///  Manual Run Width
-    #[xml(child = "w:fitText")]
-    pub fit_text: Option<FitText>,
-    /// Subscript/Superscript Text
-    #[xml(child = "w:vertAlign")]
-    pub vertical_align: Option<VertAlign>,
-    ///  Right To Left Text
-    #[xml(child = "w:rtl")]
-    pub rtl: Option<RightToLeftText>,
-    ///  Use Complex Script Formatting on Run
-    #[xml(child = "w:cs")]
-    pub complex_script: Option<ComplexScript>,
-    ///  Emphasis Mark
-    #[xml(child = "w:em")]
-    pub emphasis: Option<Emphasis>,
-    /// Specifies the language to be used.
-    #[xml(child = "w:lang")]
-    pub lang: Option<Lang<'a>>,
-    ///  East Asian Typography Settings
-    #[xml(child = "w:eastAsianLayout")]
-    pub east_asian_layout: Option<EastAsianLayout>,
-    ///  Paragraph Mark Is Always Hidden
-    #[xml(child = "w:specVanish")]
-    pub spec_vanish: Option<SpecVanish>,
-    ///  Office Open XML Math
-    #[xml(child = "w:oMath")]
-    pub o_math: Option<OMath>,
+/// The first and second block of fields come before and after the formatting. The
+/// `=> Target` form also converts the element into `Target`, keeping only the formatting.
+macro_rules! character_property {
+    (
+        $(#[$doc:meta])*
+        $name:ident $(=> $into:ident)? {
+            $($(#[$lead_meta:meta])* $lead:ident: Option<$lead_ty:ty>,)*
+        } {
+            $($(#[$tail_meta:meta])* $tail:ident: Option<$tail_ty:ty>,)*
+        }
+    ) => {
+        $(#[$doc])*
+        #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+        #[cfg_attr(test, derive(PartialEq))]
+        #[xml(tag = "w:rPr")]
+        pub struct $name<'a> {
+            $($(#[$lead_meta])* pub $lead: Option<$lead_ty>,)*
+            /// Specifies the style ID of the character style.
+            #[xml(child = "w:rStyle")]
+            pub style_id: Option<CharacterStyleId<'a>>,
+            /// Specifies the font.
+            #[xml(child = "w:rFonts")]
+            pub fonts: Option<Fonts>,
+            /// Specifies that the text of the text run is to be bold.
+            #[xml(child = "w:b")]
+            pub bold: Option<Bold>,
+            #[xml(child = "w:bCs")]
+            pub bold_complex: Option<BoldComplex>,
+            /// Specifies that the text of the text run is to be italics.
+            #[xml(child = "w:i")]
+            pub italics: Option<Italics>,
+            ///  Complex Script Italics
+            #[xml(child = "w:iCs")]
+            pub italics_complex: Option<ItalicsComplex>,
+            ///  Display All Characters As Capital Letters
+            #[xml(child = "w:caps")]
+            pub caps: Option<Caps>,
+            ///  Small Caps
+            #[xml(child = "w:smallCaps")]
+            pub small_caps: Option<SmallCaps>,
+            /// Specifies that the contents are to be displayed with a horizontal line through the center of the line.
+            #[xml(child = "w:strike")]
+            pub strike: Option<Strike>,
+            /// Specifies that the contents are to be displayed with two horizontal lines through each character.
+            #[xml(child = "w:dstrike")]
+            pub dstrike: Option<Dstrike>,
+            /// Specifies that the content should be displayed as if it had an outline.
+            #[xml(child = "w:outline")]
+            pub outline: Option<Outline>,
+            ///  Shadow
+            #[xml(child = "w:shadow")]
+            pub shadow: Option<Shadow>,
+            ///  Embossing
+            #[xml(child = "w:emboss")]
+            pub emboss: Option<Emboss>,
+            ///  Imprinting
+            #[xml(child = "w:imprint")]
+            pub imprint: Option<Imprint>,
+            ///  Do Not Check Spelling or Grammar
+            #[xml(child = "w:noProof")]
+            pub no_proof: Option<NoProof>,
+            /// Use Document Grid Settings For Inter-Character Spacing
+            #[xml(child = "w:snapToGrid")]
+            pub snap_to_grid: Option<super::SnapToGrid>,
+            ///  Hidden Text
+            #[xml(child = "w:vanish")]
+            pub vanish: Option<Vanish>,
+            ///  Web Hidden Text
+            #[xml(child = "w:webHidden")]
+            pub web_hidden: Option<WebHidden>,
+            /// Specifies the color to be used to display text.
+            #[xml(child = "w:color")]
+            pub color: Option<Color<'a>>,
+            ///  Character Spacing Adjustment
+            #[xml(child = "w:spacing")]
+            pub spacing: Option<TextSpacing>,
+            ///Expanded/Compressed Text
+            #[xml(child = "w:w")]
+            pub scale: Option<Scale>,
+            ///  Font Kerning
+            #[xml(child = "w:kern")]
+            pub kern: Option<Kern>,
+            /// Vertically Raised or Lowered Text
+            #[xml(child = "w:position")]
+            pub position: Option<Position>,
+            /// Specifies the font size in half points.
+            #[xml(child = "w:sz")]
+            pub size: Option<Size>,
+            ///  Complex Script Font Size
+            #[xml(child = "w:szCs")]
+            pub size_complex: Option<SizeComplex>,
+            ///  Text Highlighting
+            #[xml(child = "w:highlight")]
+            pub highlight: Option<Highlight>,
+            /// Specifies that the content should be displayed with an underline
+            #[xml(child = "w:u")]
+            pub underline: Option<Underline<'a>>,
+            ///  Animated Text Effect
+            #[xml(child = "w:effect")]
+            pub effect: Option<Effect>,
+            ///  Text Border
+            #[xml(child = "w:bdr")]
+            pub border: Option<TextBorder<'a>>,
+            ///  Run Shading
+            #[xml(child = "w:shd")]
+            pub shading: Option<Shading<'a>>,
+            ///  Manual Run Width
+            #[xml(child = "w:fitText")]
+            pub fit_text: Option<FitText>,
+            /// Subscript/Superscript Text
+            #[xml(child = "w:vertAlign")]
+            pub vertical_align: Option<VertAlign>,
+            ///  Right To Left Text
+            #[xml(child = "w:rtl")]
+            pub rtl: Option<RightToLeftText>,
+            ///  Use Complex Script Formatting on Run
+            #[xml(child = "w:cs")]
+            pub complex_script: Option<ComplexScript>,
+            ///  Emphasis Mark
+            #[xml(child = "w:em")]
+            pub emphasis: Option<Emphasis>,
+            /// Specifies the language to be used.
+            #[xml(child = "w:lang")]
+            pub lang: Option<Lang<'a>>,
+            ///  East Asian Typography Settings
+            #[xml(child = "w:eastAsianLayout")]
+            pub east_asian_layout: Option<EastAsianLayout>,
+            ///  Paragraph Mark Is Always Hidden
+            #[xml(child = "w:specVanish")]
+            pub spec_vanish: Option<SpecVanish>,
+            ///  Office Open XML Math
+            #[xml(child = "w:oMath")]
+            pub o_math: Option<OMath>,
+            $($(#[$tail_meta])* pub $tail: Option<$tail_ty>,)*
+        }
+
+        __into_owned! {
+            $name {
+                $($lead,)* style_id, color, underline, border, shading, lang $(, $tail)*;
+                fonts, bold, bold_complex, italics, italics_complex, caps, small_caps, strike,
+                dstrike, outline, shadow, emboss, imprint, no_proof, snap_to_grid, vanish,
+                web_hidden, spacing, scale, kern, position, size, size_complex, highlight, effect,
+                fit_text, vertical_align, rtl, complex_script, emphasis, east_asian_layout,
+                spec_vanish, o_math
+            }
+        }
+
+        $(
+            /// Restores the formatting recorded by a `w:rPrChange`.
+            impl<'a> From<$name<'a>> for $into<'a> {
+                fn from(value: $name<'a>) -> Self {
+                    $into {
+                        style_id: value.style_id,
+                        fonts: value.fonts,
+                        bold: value.bold,
+                        bold_complex: value.bold_complex,
+                        italics: value.italics,
+                        italics_complex: value.italics_complex,
+                        caps: value.caps,
+                        small_caps: value.small_caps,
+                        strike: value.strike,
+                        dstrike: value.dstrike,
+                        outline: value.outline,
+                        shadow: value.shadow,
+                        emboss: value.emboss,
+                        imprint: value.imprint,
+                        no_proof: value.no_proof,
+                        snap_to_grid: value.snap_to_grid,
+                        vanish: value.vanish,
+                        web_hidden: value.web_hidden,
+                        color: value.color,
+                        spacing: value.spacing,
+                        scale: value.scale,
+                        kern: value.kern,
+                        position: value.position,
+                        size: value.size,
+                        size_complex: value.size_complex,
+                        highlight: value.highlight,
+                        underline: value.underline,
+                        effect: value.effect,
+                        border: value.border,
+                        shading: value.shading,
+                        fit_text: value.fit_text,
+                        vertical_align: value.vertical_align,
+                        rtl: value.rtl,
+                        complex_script: value.complex_script,
+                        emphasis: value.emphasis,
+                        lang: value.lang,
+                        east_asian_layout: value.east_asian_layout,
+                        spec_vanish: value.spec_vanish,
+                        o_math: value.o_math,
+                        ..Default::default()
+                    }
+                }
+            }
+        )?
+    };
+}
+
+character_property! {
+    /// Character Property
+    ///
+    /// ```rust
+    /// use docx_rust::formatting::{CharacterProperty, UnderlineStyle, HighlightType};
+    ///
+    /// let prop = CharacterProperty::default()
+    ///     .style_id("foo")
+    ///     .color("00ff00")
+    ///     .color(0xff0000)
+    ///     .color((0x00, 0x00, 0xff))
+    ///     .size(42isize)
+    ///     .bold(true)
+    ///     .italics(false)
+    ///     .strike(true)
+    ///     .dstrike(false)
+    ///     .outline(true)
+    ///     .highlight(HighlightType::Yellow)
+    ///     .underline("00ff00")
+    ///     .underline(("ff0000", UnderlineStyle::Dash));
+    /// ```
+    CharacterProperty {
+        ///  Inserted Paragraph
+        #[xml(child = "w:ins")]
+        inserted: Option<InsertionMark<'a>>,
+        ///  Deleted Paragraph
+        #[xml(child = "w:del")]
+        deleted: Option<DeletionMark<'a>>,
+        ///  Move Source Paragraph
+        #[xml(child = "w:moveFrom")]
+        move_from: Option<MoveFromMark<'a>>,
+        ///  Move Destination Paragraph
+        #[xml(child = "w:moveTo")]
+        move_to: Option<MoveToMark<'a>>,
+    } {
+        /// Revision Information for Run Properties
+        #[xml(child = "w:rPrChange")]
+        r_pr_change: Option<RevisionCharacterProperty<'a>>,
+    }
 }
 
 impl<'a> CharacterProperty<'a> {
@@ -164,6 +265,28 @@ impl<'a> CharacterProperty<'a> {
     __setter!(fonts: Option<Fonts>);
 }
 
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:rPrChange")]
+pub struct RevisionCharacterProperty<'a> {
+    #[xml(attr = "w:id")]
+    pub id: isize,
+    #[xml(attr = "w:author")]
+    pub author: Cow<'a, str>,
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+
+    #[xml(child = "w:rPr")]
+    pub previous_property: Option<PreviousCharacterProperty<'a>>,
+}
+
+__into_owned! { RevisionCharacterProperty { author, date, previous_property; id } }
+
+character_property! {
+    /// Run properties recorded before a formatting change
+    PreviousCharacterProperty => CharacterProperty {} {}
+}
+
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:rStyle")]
@@ -172,6 +295,8 @@ pub struct CharacterStyleId<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { CharacterStyleId { value } }
+
 impl<'a, T: Into<Cow<'a, str>>> From<T> for CharacterStyleId<'a> {
     fn from(val: T) -> Self {
         CharacterStyleId { value: val.into() }
@@ -407,6 +532,13 @@ pub struct TextBorder<'a> {
     pub frame: Option<bool>,
 }
 
+__into_owned! {
+    TextBorder {
+        color, theme_tint, theme_shade;
+        style, theme_color, size, space, shadow, frame
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum ThemeColor {
@@ -476,6 +608,13 @@ pub struct Shading<'a> {
     pub theme_fill_shade: Option<Cow<'a, str>>,
 }
 
+__into_owned! {
+    Shading {
+        color, theme_tint, theme_shade, fill, theme_fill_tint, theme_fill_shade;
+        style, theme_color, theme_fill
+    }
+}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:em")]
@@ -616,4 +755,15 @@ __xml_test_suites!(
     r#"<w:rPr><w:u/></w:rPr>"#,
     CharacterProperty::default().fonts(Fonts::default().east_asia("宋体")),
     r#"<w:rPr><w:rFonts w:eastAsia="宋体"/></w:rPr>"#,
+    CharacterProperty {
+        bold: Some(true.into()),
+        r_pr_change: Some(RevisionCharacterProperty {
+            id: 1,
+            author: "John".into(),
+            date: None,
+            previous_property: Some(PreviousCharacterProperty::default()),
+        }),
+        ..Default::default()
+    },
+    r#"<w:rPr><w:b w:val="true"/><w:rPrChange w:id="1" w:author="John"><w:rPr/></w:rPrChange></w:rPr>"#,
 );
@@ -1,7 +1,9 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__string_enum, __xml_test_suites};
+use crate::{__into_owned, __setter, __string_enum, __xml_test_suites};
+
+use super::ThemeColor;
 
 /// Text Color
 ///
@@ -21,12 +23,30 @@ use crate::{__string_enum, __xml_test_suites};
 pub struct Color<'a> {
     #[xml(attr = "w:val")]
     pub value: Cow<'a, str>,
+    /// Specifies a theme color to be used instead of `value`.
+    #[xml(attr = "w:themeColor")]
+    pub theme_color: Option<ThemeColor>,
+    /// Specifies the tint applied to the theme color, as a hex byte.
+    #[xml(attr = "w:themeTint")]
+    pub theme_tint: Option<Cow<'a, str>>,
+    /// Specifies the shade applied to the theme color, as a hex byte.
+    #[xml(attr = "w:themeShade")]
+    pub theme_shade: Option<Cow<'a, str>>,
+}
+
+__into_owned! { Color { value, theme_tint, theme_shade; theme_color } }
+
+impl<'a> Color<'a> {
+    __setter!(theme_color: Option<ThemeColor>);
+    __setter!(theme_tint: Option<Cow<'a, str>>);
+    __setter!(theme_shade: Option<Cow<'a, str>>);
 }
 
 impl<'a> From<&'a str> for Color<'a> {
     fn from(val: &'a str) -> Self {
         Color {
             value: Cow::Borrowed(val),
+            ..Default::default()
         }
     }
 }
@@ -35,6 +55,7 @@ impl From<String> for Color<'_> {
     fn from(val: String) -> Self {
         Color {
             value: Cow::Owned(val),
+            ..Default::default()
         }
     }
 }
@@ -43,6 +64,7 @@ impl From<u32> for Color<'_> {
     fn from(val: u32) -> Self {
         Color {
             value: Cow::Owned(format!("{:06x}", val)),
+            ..Default::default()
         }
     }
 }
@@ -51,6 +73,7 @@ impl From<(u8, u8, u8)> for Color<'_> {
     fn from(val: (u8, u8, u8)) -> Self {
         Color {
             value: Cow::Owned(format!("{:02x}{:02x}{:02x}", val.0, val.1, val.2)),
+            ..Default::default()
         }
     }
 }
@@ -145,4 +168,8 @@ __xml_test_suites!(
     r#"<w:color w:val="000000"/>"#,
     Color::from((0u8, 0u8, 0u8)),
     r#"<w:color w:val="000000"/>"#,
+    Color::from("1F3864")
+        .theme_color(ThemeColor::Accent1)
+        .theme_shade("80"),
+    r#"<w:color w:val="1F3864" w:themeColor="accent1" w:themeShade="80"/>"#,
 );
@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__setter, __xml_test_suites};
+use crate::{__into_owned, __setter, __xml_test_suites};
 
 /// Size
 ///
@@ -23,6 +23,8 @@ pub struct Lang<'a> {
     pub bidi: Option<Cow<'a, str>>,
 }
 
+__into_owned! { Lang { val, east_asia, bidi } }
+
 impl<'a> Lang<'a> {
     __setter!(east_asia: Option<Cow<'a, str>>);
     __setter!(bidi: Option<Cow<'a, str>>);
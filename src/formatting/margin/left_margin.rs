@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::__setter;
+use crate::{__into_owned, __setter};
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -14,6 +14,8 @@ pub struct LeftMargin<'a> {
     pub ty: Option<Cow<'a, str>>,
 }
 
+__into_owned! { LeftMargin { ty; size } }
+
 impl<'a> LeftMargin<'a> {
     __setter!(ty: Option<Cow<'a, str>>);
     __setter!(size: Option<isize>);
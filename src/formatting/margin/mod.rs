@@ -1,6 +1,11 @@
 mod bottom_margin;
+mod end_margin;
 mod left_margin;
 mod right_margin;
+mod start_margin;
 mod top_margin;
 
-pub use self::{bottom_margin::*, left_margin::*, right_margin::*, top_margin::*};
+pub use self::{
+    bottom_margin::*, end_margin::*, left_margin::*, right_margin::*, start_margin::*,
+    top_margin::*,
+};
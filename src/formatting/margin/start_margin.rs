@@ -0,0 +1,22 @@
+use std::borrow::Cow;
+
+use hard_xml::{XmlRead, XmlWrite};
+
+use crate::{__into_owned, __setter};
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:start")]
+pub struct StartMargin<'a> {
+    #[xml(attr = "w:w")]
+    pub size: Option<isize>,
+    #[xml(attr = "w:type")]
+    pub ty: Option<Cow<'a, str>>,
+}
+
+__into_owned! { StartMargin { ty; size } }
+
+impl<'a> StartMargin<'a> {
+    __setter!(ty: Option<Cow<'a, str>>);
+    __setter!(size: Option<isize>);
+}
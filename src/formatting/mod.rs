@@ -44,9 +44,10 @@ mod widow_control;
 // re-export
 pub use self::{
     bold::*, border::*, borders::*, character_property::*, color::*, dstrike::*, fonts::*,
-    indent::*, indent_level::*, italics::*, justification::*, lang::*, numbering_id::*,
+    indent::*, indent_level::*, italics::*, justification::*, lang::*, margin::*, numbering_id::*,
     numbering_property::*, outline::*, page_cols::*, page_grid::*, page_margin::*, page_size::*,
     paragraph_property::*, section_property::*, size::*, spacing::*, strike::*, table_borders::*,
     table_cell_property::*, table_header::*, table_indent::*, table_justification::*,
-    table_property::*, table_row_property::*, table_width::*, underline::*, widow_control::*,
+    table_margin::*, table_property::*, table_row_property::*, table_width::*, underline::*,
+    widow_control::*,
 };
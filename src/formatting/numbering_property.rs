@@ -3,8 +3,8 @@ use std::borrow::Cow;
 
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::__xml_test_suites;
 use crate::formatting::{IndentLevel, NumberingId};
+use crate::{__into_owned, __xml_test_suites};
 
 /// Numbering Property
 ///
@@ -31,6 +31,8 @@ pub struct NumberingProperty<'a> {
     pub ins: Option<InsertedProperties<'a>>,
 }
 
+__into_owned! { NumberingProperty { numbering_change, ins; level, id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:ins")]
@@ -43,6 +45,8 @@ pub struct InsertedProperties<'a> {
     pub date: Option<Cow<'a, str>>,
 }
 
+__into_owned! { InsertedProperties { author, date; id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:numberingChange")]
@@ -57,6 +61,8 @@ pub struct NumberingChange<'a> {
     pub original: Option<Cow<'a, str>>,
 }
 
+__into_owned! { NumberingChange { author, date, original; id } }
+
 impl From<(isize, isize)> for NumberingProperty<'_> {
     fn from(val: (isize, isize)) -> Self {
         NumberingProperty {
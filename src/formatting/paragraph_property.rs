@@ -2,7 +2,7 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
 use crate::{
-    __define_enum, __define_struct, __define_struct_vec, __setter, __xml_test_suites,
+    __define_enum, __define_struct, __define_struct_vec, __into_owned, __setter, __xml_test_suites,
     formatting::{Borders, Indent, Justification, NumberingProperty, Spacing, WidowControl},
 };
 
@@ -128,6 +128,17 @@ pub struct ParagraphProperty<'a> {
     pub p_pr_change: Option<RevisionParagraphProperty<'a>>,
 }
 
+__into_owned! {
+    ParagraphProperty {
+        style_id, numbering, border, shading, cnf_style, r_pr, section_property, p_pr_change;
+        keep_next, keep_lines, page_break_before, widow_control, suppress_line_numbers, tabs,
+        suppress_auto_hyphens, kinsoku, word_wrap, overflow_punct, top_line_punct, auto_space_de,
+        auto_space_dn, bidi, adjust_right_ind, snap_to_grid, spacing, indent, contextual_spacing,
+        mirror_indents, suppress_overlap, justification, text_direction, text_alignment,
+        textbox_tight_wrap, outline_lvl, div_id
+    }
+}
+
 impl<'a> ParagraphProperty<'a> {
     __setter!(style_id: Option<ParagraphStyleId<'a>>);
     __setter!(justification: Option<Justification>);
@@ -145,6 +156,8 @@ pub struct ParagraphStyleId<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { ParagraphStyleId { value } }
+
 impl<'a, T: Into<Cow<'a, str>>> From<T> for ParagraphStyleId<'a> {
     fn from(val: T) -> Self {
         ParagraphStyleId { value: val.into() }
@@ -311,6 +324,8 @@ pub struct CnfStyle<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { CnfStyle { value } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:pPrChange")]
@@ -326,6 +341,8 @@ pub struct RevisionParagraphProperty<'a> {
     pub previous_property: Option<PreviousParagraphProperty<'a>>,
 }
 
+__into_owned! { RevisionParagraphProperty { author, date, previous_property; id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:pPr")]
@@ -432,6 +449,58 @@ pub struct PreviousParagraphProperty<'a> {
     pub cnf_style: Option<CnfStyle<'a>>,
 }
 
+__into_owned! {
+    PreviousParagraphProperty {
+        style_id, numbering, border, shading, cnf_style;
+        keep_next, keep_lines, page_break_before, widow_control, suppress_line_numbers, tabs,
+        suppress_auto_hyphens, kinsoku, word_wrap, overflow_punct, top_line_punct, auto_space_de,
+        auto_space_dn, bidi, adjust_right_ind, snap_to_grid, spacing, indent, contextual_spacing,
+        mirror_indents, suppress_overlap, justification, text_direction, text_alignment,
+        textbox_tight_wrap, outline_lvl, div_id
+    }
+}
+
+/// Restores the formatting recorded by a `w:pPrChange`.
+impl<'a> From<PreviousParagraphProperty<'a>> for ParagraphProperty<'a> {
+    fn from(value: PreviousParagraphProperty<'a>) -> Self {
+        ParagraphProperty {
+            style_id: value.style_id,
+            keep_next: value.keep_next,
+            keep_lines: value.keep_lines,
+            page_break_before: value.page_break_before,
+            widow_control: value.widow_control,
+            numbering: value.numbering,
+            suppress_line_numbers: value.suppress_line_numbers,
+            border: value.border,
+            shading: value.shading,
+            tabs: value.tabs,
+            suppress_auto_hyphens: value.suppress_auto_hyphens,
+            kinsoku: value.kinsoku,
+            word_wrap: value.word_wrap,
+            overflow_punct: value.overflow_punct,
+            top_line_punct: value.top_line_punct,
+            auto_space_de: value.auto_space_de,
+            auto_space_dn: value.auto_space_dn,
+            bidi: value.bidi,
+            adjust_right_ind: value.adjust_right_ind,
+            snap_to_grid: value.snap_to_grid,
+            spacing: value.spacing,
+            indent: value.indent,
+            contextual_spacing: value.contextual_spacing,
+            mirror_indents: value.mirror_indents,
+            suppress_overlap: value.suppress_overlap,
+            justification: value.justification,
+            text_direction: value.text_direction,
+            text_alignment: value.text_alignment,
+            textbox_tight_wrap: value.textbox_tight_wrap,
+            outline_lvl: value.outline_lvl,
+            div_id: value.div_id,
+            cnf_style: value.cnf_style,
+            ..Default::default()
+        }
+    }
+}
+
 __define_enum! {
     TabStopType  {
         Clear = "clear", // No Tab Stop
@@ -3,8 +3,10 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
 use crate::{
-    __define_enum, __define_struct, __string_enum,
-    document::HeaderFooterReference,
+    __define_enum, __define_struct, __into_owned, __string_enum,
+    document::{
+        FooterReference, HeaderFooterReference, HeaderFooterReferenceType, HeaderReference,
+    },
     formatting::{PageCols, PageGrid, PageMargin, PageSize},
 };
 
@@ -88,6 +90,15 @@ pub struct SectionProperty<'a> {
     pub revision: Option<Revision<'a>>,
 }
 
+__into_owned! {
+    SectionProperty {
+        rsid_r, rsid_r_default, header_footer_references, revision;
+        footnote_property, endnote_property, ty, page_size, page_margin, paper_source, page_borders,
+        line_numbering, page_numbering, cols, form_prot, v_align, no_endnote, title_page,
+        text_direction, bidi, rtl_gutter, grid
+    }
+}
+
 /// Previous Section Properties
 ///
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
@@ -160,6 +171,44 @@ pub struct PreviousSectionProperty<'a> {
     //pub printer_settings: Option<PrinterSettings>,
 }
 
+__into_owned! {
+    PreviousSectionProperty {
+        rsid_r, rsid_r_default;
+        footnote_property, endnote_property, ty, page_size, page_margin, paper_source, page_borders,
+        line_numbering, page_numbering, cols, form_prot, v_align, no_endnote, title_page,
+        text_direction, bidi, rtl_gutter, grid
+    }
+}
+
+/// Restores the section settings recorded by a `w:sectPrChange`.
+impl<'a> From<PreviousSectionProperty<'a>> for SectionProperty<'a> {
+    fn from(value: PreviousSectionProperty<'a>) -> Self {
+        SectionProperty {
+            rsid_r: value.rsid_r,
+            rsid_r_default: value.rsid_r_default,
+            footnote_property: value.footnote_property,
+            endnote_property: value.endnote_property,
+            ty: value.ty,
+            page_size: value.page_size,
+            page_margin: value.page_margin,
+            paper_source: value.paper_source,
+            page_borders: value.page_borders,
+            line_numbering: value.line_numbering,
+            page_numbering: value.page_numbering,
+            cols: value.cols,
+            form_prot: value.form_prot,
+            v_align: value.v_align,
+            no_endnote: value.no_endnote,
+            title_page: value.title_page,
+            text_direction: value.text_direction,
+            bidi: value.bidi,
+            rtl_gutter: value.rtl_gutter,
+            grid: value.grid,
+            ..Default::default()
+        }
+    }
+}
+
 __define_struct! {
     ("w:pgBorders", PgBorders) {
         "w:zOrder",	z_order, PageBorderZOrder	//Z-Ordering of Page Border
@@ -364,10 +413,12 @@ pub struct Revision<'a> {
     #[xml(attr = "w:date")]
     pub date: Option<Cow<'a, str>>,
 
-    #[xml(child = "w:secPr")]
+    #[xml(child = "w:sectPr")]
     pub section_property: Option<PreviousSectionProperty<'a>>,
 }
 
+__into_owned! { Revision { author, date, section_property; id } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:type")]
@@ -860,6 +911,57 @@ impl<'a> SectionProperty<'a> {
         }
         self
     }
+
+    /// Shows the header with relationship id `id` on the pages selected by `ty`
+    ///
+    /// A reference of the same type is replaced. A first page header also sets `w:titlePg`,
+    /// without which the first page uses the default header.
+    pub fn set_header_reference<S: Into<Cow<'a, str>>>(
+        &mut self,
+        ty: HeaderFooterReferenceType,
+        id: S,
+    ) -> &mut Self {
+        self.remove_reference(&ty, true);
+        if let HeaderFooterReferenceType::First = ty {
+            self.first_page_has_diffrent_header_and_footer(true);
+        }
+        self.header_footer_references
+            .push(HeaderReference::default().ty(ty).id(id.into()).into());
+        self
+    }
+
+    /// Shows the footer with relationship id `id` on the pages selected by `ty`
+    ///
+    /// Works like [`SectionProperty::set_header_reference`].
+    pub fn set_footer_reference<S: Into<Cow<'a, str>>>(
+        &mut self,
+        ty: HeaderFooterReferenceType,
+        id: S,
+    ) -> &mut Self {
+        self.remove_reference(&ty, false);
+        if let HeaderFooterReferenceType::First = ty {
+            self.first_page_has_diffrent_header_and_footer(true);
+        }
+        self.header_footer_references
+            .push(FooterReference::default().ty(ty).id(id.into()).into());
+        self
+    }
+
+    fn remove_reference(&mut self, ty: &HeaderFooterReferenceType, header: bool) {
+        // a reference without a type is a default one
+        let same_type = |other: &Option<HeaderFooterReferenceType>| {
+            std::mem::discriminant(
+                other
+                    .as_ref()
+                    .unwrap_or(&HeaderFooterReferenceType::Default),
+            ) == std::mem::discriminant(ty)
+        };
+        self.header_footer_references
+            .retain(|reference| match reference {
+                HeaderFooterReference::Header(h) => !header || !same_type(&h.ty),
+                HeaderFooterReference::Footer(f) => header || !same_type(&f.ty),
+            });
+    }
 }
 
 // #[derive(Debug, XmlRead, XmlWrite, Clone)]
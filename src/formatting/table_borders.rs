@@ -1,9 +1,11 @@
 use hard_xml::{XmlRead, XmlWrite};
 
 use crate::{
-    __setter, __xml_test_suites,
+    __into_owned, __setter, __xml_test_suites,
     formatting::{
-        BottomBorder, InsideHorizonBorder, InsideVerticalBorder, LeftBorder, RightBorder, TopBorder,
+        BottomBorder, EndBorder, InsideHorizonBorder, InsideVerticalBorder, LeftBorder,
+        RightBorder, StartBorder, TopBorder, TopLeftToBottomRightBorder,
+        TopRightToBottomLeftBorder,
     },
 };
 
@@ -25,11 +27,61 @@ pub struct TableBorders<'a> {
     pub inside_vertical: Option<InsideVerticalBorder<'a>>,
 }
 
+__into_owned! { TableBorders { top, left, bottom, right, inside_horizon, inside_vertical } }
+
 impl<'a> TableBorders<'a> {
     __setter!(top: Option<TopBorder<'a>>);
     __setter!(bottom: Option<BottomBorder<'a>>);
 }
 
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:tcBorders")]
+pub struct TableCellBorders<'a> {
+    #[xml(child = "w:top")]
+    pub top: Option<TopBorder<'a>>,
+    #[xml(child = "w:left")]
+    pub left: Option<LeftBorder<'a>>,
+    /// Leading edge border, on the left in left-to-right text
+    #[xml(child = "w:start")]
+    pub start: Option<StartBorder<'a>>,
+    #[xml(child = "w:bottom")]
+    pub bottom: Option<BottomBorder<'a>>,
+    #[xml(child = "w:right")]
+    pub right: Option<RightBorder<'a>>,
+    /// Trailing edge border, on the right in left-to-right text
+    #[xml(child = "w:end")]
+    pub end: Option<EndBorder<'a>>,
+    #[xml(child = "w:insideH")]
+    pub inside_horizon: Option<InsideHorizonBorder<'a>>,
+    #[xml(child = "w:insideV")]
+    pub inside_vertical: Option<InsideVerticalBorder<'a>>,
+    /// Diagonal border from the top left corner to the bottom right one
+    #[xml(child = "w:tl2br")]
+    pub top_left_to_bottom_right: Option<TopLeftToBottomRightBorder<'a>>,
+    /// Diagonal border from the top right corner to the bottom left one
+    #[xml(child = "w:tr2bl")]
+    pub top_right_to_bottom_left: Option<TopRightToBottomLeftBorder<'a>>,
+}
+
+__into_owned! {
+    TableCellBorders {
+        top, left, start, bottom, right, end, inside_horizon, inside_vertical,
+        top_left_to_bottom_right, top_right_to_bottom_left
+    }
+}
+
+impl<'a> TableCellBorders<'a> {
+    __setter!(top: Option<TopBorder<'a>>);
+    __setter!(left: Option<LeftBorder<'a>>);
+    __setter!(bottom: Option<BottomBorder<'a>>);
+    __setter!(right: Option<RightBorder<'a>>);
+    __setter!(start: Option<StartBorder<'a>>);
+    __setter!(end: Option<EndBorder<'a>>);
+    __setter!(top_left_to_bottom_right: Option<TopLeftToBottomRightBorder<'a>>);
+    __setter!(top_right_to_bottom_left: Option<TopRightToBottomLeftBorder<'a>>);
+}
+
 __xml_test_suites!(
     TableBorders,
     TableBorders::default(),
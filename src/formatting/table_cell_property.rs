@@ -1,20 +1,158 @@
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__setter, __xml_test_suites};
+use crate::{
+    __define_enum, __into_owned, __setter, __xml_test_suites,
+    formatting::{TableCellBorders, TableCellMargins},
+};
 
+/// Table Cell Property
+///
+/// ```rust
+/// use docx_rust::formatting::*;
+///
+/// let prop = TableCellProperty::default()
+///     .grid_span(2isize)
+///     .v_merge(MergeType::Restart)
+///     .no_wrap(true);
+/// ```
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tcPr")]
-pub struct TableCellProperty {
+pub struct TableCellProperty<'a> {
     #[xml(child = "w:tcW")]
     pub wide: Option<super::TableCellWidth>,
+    ///  Grid Columns Spanned by Current Table Cell
+    #[xml(child = "w:gridSpan")]
+    pub grid_span: Option<GridSpan>,
+    ///  Horizontally Merged Cell
+    #[xml(child = "w:hMerge")]
+    pub h_merge: Option<HMerge>,
+    ///  Vertically Merged Cell
+    #[xml(child = "w:vMerge")]
+    pub v_merge: Option<VMerge>,
+    ///  Table Cell Borders
+    #[xml(child = "w:tcBorders")]
+    pub borders: Option<TableCellBorders<'a>>,
+    ///  Table Cell Shading
+    #[xml(child = "w:shd")]
+    pub shading: Option<super::Shading<'a>>,
+    ///  Don't Wrap Cell Content
+    #[xml(child = "w:noWrap")]
+    pub no_wrap: Option<NoWrap>,
+    ///  Single Table Cell Margins
+    #[xml(child = "w:tcMar")]
+    pub margins: Option<TableCellMargins<'a>>,
+    ///  Table Cell Text Flow Direction
+    #[xml(child = "w:textDirection")]
+    pub text_direction: Option<super::TextDirection>,
     #[xml(default, child = "w:vAlign")]
     pub v_align: super::VAlign,
 }
 
-impl TableCellProperty {
+__into_owned! {
+    TableCellProperty {
+        borders, shading, margins;
+        wide, grid_span, h_merge, v_merge, no_wrap, text_direction, v_align
+    }
+}
+
+impl<'a> TableCellProperty<'a> {
     __setter!(v_align: super::VAlign);
     __setter!(wide: Option<super::TableCellWidth>);
+    __setter!(grid_span: Option<GridSpan>);
+    __setter!(h_merge: Option<HMerge>);
+    __setter!(v_merge: Option<VMerge>);
+    __setter!(borders: Option<TableCellBorders<'a>>);
+    __setter!(shading: Option<super::Shading<'a>>);
+    __setter!(no_wrap: Option<NoWrap>);
+    __setter!(margins: Option<TableCellMargins<'a>>);
+    __setter!(text_direction: Option<super::TextDirection>);
+
+    /// Returns the number of grid columns spanned by the cell.
+    pub fn column_span(&self) -> usize {
+        self.grid_span
+            .as_ref()
+            .map_or(1, |span| span.value.max(1) as usize)
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:gridSpan")]
+pub struct GridSpan {
+    #[xml(attr = "w:val")]
+    pub value: isize,
+}
+
+impl From<isize> for GridSpan {
+    fn from(value: isize) -> Self {
+        GridSpan { value }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:hMerge")]
+pub struct HMerge {
+    /// A missing value continues the merged region.
+    #[xml(attr = "w:val")]
+    pub value: Option<MergeType>,
+}
+
+impl From<MergeType> for HMerge {
+    fn from(value: MergeType) -> Self {
+        HMerge { value: Some(value) }
+    }
+}
+
+impl HMerge {
+    /// Returns whether the cell starts a merged region.
+    pub fn is_restart(&self) -> bool {
+        matches!(self.value, Some(MergeType::Restart))
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:vMerge")]
+pub struct VMerge {
+    /// A missing value continues the merged region.
+    #[xml(attr = "w:val")]
+    pub value: Option<MergeType>,
+}
+
+impl From<MergeType> for VMerge {
+    fn from(value: MergeType) -> Self {
+        VMerge { value: Some(value) }
+    }
+}
+
+impl VMerge {
+    /// Returns whether the cell starts a merged region.
+    pub fn is_restart(&self) -> bool {
+        matches!(self.value, Some(MergeType::Restart))
+    }
+}
+
+__define_enum! {
+    MergeType {
+        Restart = "restart", // Begin Merged Cell
+        Continue = "continue", // Continue Merged Cell
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:noWrap")]
+pub struct NoWrap {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl<T: Into<Option<bool>>> From<T> for NoWrap {
+    fn from(val: T) -> Self {
+        NoWrap { value: val.into() }
+    }
 }
 
 __xml_test_suites!(
@@ -23,4 +161,23 @@ __xml_test_suites!(
     r#"<w:tcPr><w:vAlign w:val="top"/></w:tcPr>"#,
     TableCellProperty::default().v_align(super::VAlignType::Bottom),
     r#"<w:tcPr><w:vAlign w:val="bottom"/></w:tcPr>"#,
+    TableCellProperty::default()
+        .grid_span(2isize)
+        .v_merge(MergeType::Restart),
+    r#"<w:tcPr><w:gridSpan w:val="2"/><w:vMerge w:val="restart"/><w:vAlign w:val="top"/></w:tcPr>"#,
+    TableCellProperty::default().v_merge(VMerge::default()),
+    r#"<w:tcPr><w:vMerge/><w:vAlign w:val="top"/></w:tcPr>"#,
+    TableCellProperty::default()
+        .borders(TableCellBorders::default().top(super::TopBorder::default()))
+        .no_wrap(true)
+        .margins(TableCellMargins::default()),
+    r#"<w:tcPr><w:tcBorders><w:top w:val="none"/></w:tcBorders><w:noWrap w:val="true"/><w:tcMar/><w:vAlign w:val="top"/></w:tcPr>"#,
+    TableCellProperty::default().borders(
+        TableCellBorders::default()
+            .start(super::StartBorder::default())
+            .end(super::EndBorder::default())
+            .top_left_to_bottom_right(super::TopLeftToBottomRightBorder::default())
+            .top_right_to_bottom_left(super::TopRightToBottomLeftBorder::default())
+    ),
+    r#"<w:tcPr><w:tcBorders><w:start w:val="none"/><w:end w:val="none"/><w:tl2br w:val="none"/><w:tr2bl w:val="none"/></w:tcBorders><w:vAlign w:val="top"/></w:tcPr>"#,
 );
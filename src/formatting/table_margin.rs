@@ -1,8 +1,8 @@
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::__setter;
+use crate::{__into_owned, __setter, __xml_test_suites};
 
-use super::margin::{BottomMargin, LeftMargin, RightMargin, TopMargin};
+use super::margin::{BottomMargin, EndMargin, LeftMargin, RightMargin, StartMargin, TopMargin};
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -12,16 +12,53 @@ pub struct TableMargins<'a> {
     pub top: Option<TopMargin<'a>>,
     #[xml(child = "w:left")]
     pub left: Option<LeftMargin<'a>>,
+    #[xml(child = "w:start")]
+    pub start: Option<StartMargin<'a>>,
     #[xml(child = "w:bottom")]
     pub bottom: Option<BottomMargin<'a>>,
     #[xml(child = "w:right")]
     pub right: Option<RightMargin<'a>>,
+    #[xml(child = "w:end")]
+    pub end: Option<EndMargin<'a>>,
 }
 
+__into_owned! { TableMargins { top, left, start, bottom, right, end } }
+
 impl<'a> TableMargins<'a> {
     __setter!(top: Option<TopMargin<'a>>);
     __setter!(left: Option<LeftMargin<'a>>);
+    __setter!(start: Option<StartMargin<'a>>);
     __setter!(right: Option<RightMargin<'a>>);
+    __setter!(end: Option<EndMargin<'a>>);
+    __setter!(bottom: Option<BottomMargin<'a>>);
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:tcMar")]
+pub struct TableCellMargins<'a> {
+    #[xml(child = "w:top")]
+    pub top: Option<TopMargin<'a>>,
+    #[xml(child = "w:left")]
+    pub left: Option<LeftMargin<'a>>,
+    #[xml(child = "w:start")]
+    pub start: Option<StartMargin<'a>>,
+    #[xml(child = "w:bottom")]
+    pub bottom: Option<BottomMargin<'a>>,
+    #[xml(child = "w:right")]
+    pub right: Option<RightMargin<'a>>,
+    #[xml(child = "w:end")]
+    pub end: Option<EndMargin<'a>>,
+}
+
+__into_owned! { TableCellMargins { top, left, start, bottom, right, end } }
+
+impl<'a> TableCellMargins<'a> {
+    __setter!(top: Option<TopMargin<'a>>);
+    __setter!(left: Option<LeftMargin<'a>>);
+    __setter!(start: Option<StartMargin<'a>>);
+    __setter!(right: Option<RightMargin<'a>>);
+    __setter!(end: Option<EndMargin<'a>>);
     __setter!(bottom: Option<BottomMargin<'a>>);
 }
 
@@ -34,3 +71,13 @@ impl<'a> TableMargins<'a> {
 //     TableBorders::default().bottom(BottomBorder::default()),
 //     r#"<w:tblBorders><w:bottom/></w:tblBorders>"#,
 // );
+
+__xml_test_suites!(
+    TableCellMargins,
+    TableCellMargins::default(),
+    r#"<w:tcMar/>"#,
+    TableCellMargins::default()
+        .start(StartMargin::default().size(108isize).ty("dxa"))
+        .end(EndMargin::default().size(108isize).ty("dxa")),
+    r#"<w:tcMar><w:start w:w="108" w:type="dxa"/><w:end w:w="108" w:type="dxa"/></w:tcMar>"#,
+);
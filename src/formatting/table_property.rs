@@ -2,7 +2,7 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
 use crate::{
-    __setter, __string_enum, __xml_test_suites,
+    __into_owned, __setter, __string_enum, __xml_test_suites,
     formatting::{TableBorders, TableIndent, TableJustification, TableWidth},
 };
 
@@ -37,6 +37,8 @@ pub struct TableProperty<'a> {
     pub margins: Option<TableMargins<'a>>,
 }
 
+__into_owned! { TableProperty { style_id, borders, margins; width, justification, indent } }
+
 impl<'a> TableProperty<'a> {
     __setter!(style_id: Option<TableStyleId<'a>>);
     __setter!(justification: Option<TableJustification>);
@@ -53,6 +55,8 @@ pub struct TableStyleId<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { TableStyleId { value } }
+
 impl<'a, T: Into<Cow<'a, str>>> From<T> for TableStyleId<'a> {
     fn from(val: T) -> Self {
         TableStyleId { value: val.into() }
@@ -63,7 +67,7 @@ impl<'a, T: Into<Cow<'a, str>>> From<T> for TableStyleId<'a> {
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tblStylePr")]
 pub struct ConditionalTableProperty<'a> {
-    #[xml(attr = "type")]
+    #[xml(attr = "w:type")]
     pub condition: Option<ConditionType>,
     /// Specifies a set of paragraph properties
     #[xml(default, child = "w:pPr")]
@@ -74,9 +78,16 @@ pub struct ConditionalTableProperty<'a> {
     #[xml(default, child = "w:tblPr")]
     pub table: Option<TableProperty<'a>>,
     #[xml(child = "w:trPr")]
-    pub table_row: Option<crate::formatting::TableRowProperty>,
+    pub table_row: Option<crate::formatting::TableRowProperty<'a>>,
     #[xml(child = "w:tcPr")]
-    pub table_cell: Option<crate::formatting::TableCellProperty>,
+    pub table_cell: Option<crate::formatting::TableCellProperty<'a>>,
+}
+
+__into_owned! {
+    ConditionalTableProperty {
+        paragraph, character, table, table_row, table_cell;
+        condition
+    }
 }
 
 #[derive(Debug, Default, Clone)]
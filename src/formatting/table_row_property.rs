@@ -1,6 +1,11 @@
 use hard_xml::{XmlRead, XmlWrite};
 
-use crate::{__setter, __xml_test_suites, formatting::TableHeader, formatting::TableJustification};
+use crate::{
+    __into_owned, __setter, __xml_test_suites,
+    document::{DeletionMark, InsertionMark},
+    formatting::TableHeader,
+    formatting::TableJustification,
+};
 
 /// Table Row Property
 ///
@@ -13,18 +18,76 @@ use crate::{__setter, __xml_test_suites, formatting::TableHeader, formatting::Ta
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:trPr")]
-pub struct TableRowProperty {
+pub struct TableRowProperty<'a> {
+    /// Number of grid columns skipped before the first cell of the row
+    #[xml(child = "w:gridBefore")]
+    pub grid_before: Option<GridBefore>,
+    /// Number of grid columns left after the last cell of the row
+    #[xml(child = "w:gridAfter")]
+    pub grid_after: Option<GridAfter>,
     /// Specifies the alignment of the row with respect to the text margins in the section.
     #[xml(child = "w:jc")]
     pub justification: Option<TableJustification>,
     /// Repeat Table Row on Every New Page
     #[xml(child = "w:tblHeader")]
     pub table_header: Option<TableHeader>,
+    /// Inserted Table Row
+    #[xml(child = "w:ins")]
+    pub inserted: Option<InsertionMark<'a>>,
+    /// Deleted Table Row
+    #[xml(child = "w:del")]
+    pub deleted: Option<DeletionMark<'a>>,
 }
 
-impl TableRowProperty {
+__into_owned! {
+    TableRowProperty {
+        inserted, deleted;
+        grid_before, grid_after, justification, table_header
+    }
+}
+
+impl<'a> TableRowProperty<'a> {
+    __setter!(grid_before: Option<GridBefore>);
+    __setter!(grid_after: Option<GridAfter>);
     __setter!(justification: Option<TableJustification>);
     __setter!(table_header: Option<TableHeader>);
+    __setter!(inserted: Option<InsertionMark<'a>>);
+    __setter!(deleted: Option<DeletionMark<'a>>);
+
+    /// Returns the number of grid columns skipped before the first cell of the row.
+    pub fn columns_before(&self) -> usize {
+        self.grid_before
+            .as_ref()
+            .map_or(0, |grid| grid.value.max(0) as usize)
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:gridBefore")]
+pub struct GridBefore {
+    #[xml(attr = "w:val")]
+    pub value: isize,
+}
+
+impl From<isize> for GridBefore {
+    fn from(value: isize) -> Self {
+        GridBefore { value }
+    }
+}
+
+#[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:gridAfter")]
+pub struct GridAfter {
+    #[xml(attr = "w:val")]
+    pub value: isize,
+}
+
+impl From<isize> for GridAfter {
+    fn from(value: isize) -> Self {
+        GridAfter { value }
+    }
 }
 
 __xml_test_suites!(
@@ -35,4 +98,14 @@ __xml_test_suites!(
         .justification(crate::formatting::TableJustificationVal::Start)
         .table_header(crate::formatting::OnOffOnlyType::On),
     r#"<w:trPr><w:jc w:val="start"/><w:tblHeader w:val="on"/></w:trPr>"#,
+    TableRowProperty::default()
+        .grid_before(GridBefore::from(1))
+        .grid_after(GridAfter::from(2)),
+    r#"<w:trPr><w:gridBefore w:val="1"/><w:gridAfter w:val="2"/></w:trPr>"#,
+    TableRowProperty::default().inserted(InsertionMark {
+        id: 1,
+        author: "John".into(),
+        date: None,
+    }),
+    r#"<w:trPr><w:ins w:id="1" w:author="John"/></w:trPr>"#,
 );
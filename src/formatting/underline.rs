@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
-use crate::{__string_enum, __xml_test_suites};
+use crate::{__into_owned, __string_enum, __xml_test_suites};
 
 /// Underline
 ///
@@ -23,6 +23,8 @@ pub struct Underline<'a> {
     pub val: Option<UnderlineStyle>,
 }
 
+__into_owned! { Underline { color; val } }
+
 impl From<String> for Underline<'_> {
     fn from(val: String) -> Self {
         Underline {
@@ -66,6 +66,24 @@
 //!
 //! [`DocxFile::from_reader`]: struct.DocxFile.html#method.from_reader
 //!
+//! If the `Docx` has to outlive its source, read it with [`Docx::from_file`] instead,
+//! or convert a parsed one with [`Docx::into_owned`]. Either way you get a `Docx<'static>`
+//! which owns all of its data:
+//!
+//! ```no_run
+//! use docx_rust::Docx;
+//!
+//! fn open(path: &str) -> Docx<'static> {
+//!     Docx::from_file(path).unwrap()
+//! }
+//!
+//! let mut docx = open("foo.docx");
+//! std::thread::spawn(move || docx.write_file("foo.docx").unwrap());
+//! ```
+//!
+//! [`Docx::from_file`]: struct.Docx.html#method.from_file
+//! [`Docx::into_owned`]: struct.Docx.html#method.into_owned
+//!
 //! # Similar Projects
 //!
 //! [`bokuweb/docx-rs`]: A .docx file writer with Rust/WebAssembly.
@@ -82,12 +100,14 @@ mod macros;
 pub mod app;
 pub mod content_type;
 pub mod core;
+pub mod custom;
 pub mod document;
 mod docx;
 mod error;
 pub mod font_table;
 pub mod formatting;
 pub mod media;
+mod owned;
 pub mod rels;
 mod schema;
 pub mod settings;
@@ -100,6 +120,7 @@ use hard_xml::{XmlWrite, XmlWriter};
 
 pub use crate::docx::{Docx, DocxFile};
 pub use crate::error::{DocxError, DocxResult};
+pub use crate::owned::IntoOwned;
 
 pub fn write_attr<W: Write, T: XmlWrite>(
     element: &Option<T>,
@@ -205,6 +205,40 @@ macro_rules! __setter {
     };
 }
 
+/// Implements `IntoOwned` for a type which borrows from the parsed XML
+///
+/// Fields (or variants) listed before the `;` are converted recursively,
+/// the ones after it hold no borrowed data and are moved as they are.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __into_owned {
+    (enum $name:ident { $($variant:ident),* $(; $($kept:ident),*)? }) => {
+        impl<'a> $crate::IntoOwned for $name<'a> {
+            type Owned = $name<'static>;
+
+            fn into_owned(self) -> Self::Owned {
+                match self {
+                    $( $name::$variant(v) => $name::$variant($crate::IntoOwned::into_owned(v)), )*
+                    $($( $name::$kept(v) => $name::$kept(v), )*)?
+                }
+            }
+        }
+    };
+    ($name:ident { $($field:ident),* $(; $($kept:ident),*)? }) => {
+        impl<'a> $crate::IntoOwned for $name<'a> {
+            type Owned = $name<'static>;
+
+            fn into_owned(self) -> Self::Owned {
+                let $name { $($field,)* $($($kept,)*)? } = self;
+                $name {
+                    $( $field: $crate::IntoOwned::into_owned($field), )*
+                    $($( $kept, )*)?
+                }
+            }
+        }
+    };
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __xml_test_suites {
@@ -0,0 +1,381 @@
+/// EMUs per inch, the unit used by DrawingML extents
+pub const EMU_PER_INCH: u64 = 914_400;
+
+/// Resolution assumed when the image does not specify one
+const DEFAULT_DPI: u32 = 96;
+
+/// Specifies the format of an image file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Bmp,
+    Tiff,
+    Emf,
+    Wmf,
+    Svg,
+}
+
+impl ImageFormat {
+    /// Returns the file extension used for the media part
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpeg",
+            ImageFormat::Gif => "gif",
+            ImageFormat::Bmp => "bmp",
+            ImageFormat::Tiff => "tiff",
+            ImageFormat::Emf => "emf",
+            ImageFormat::Wmf => "wmf",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Format, pixel size and resolution of an image, read from its header
+///
+/// ```rust
+/// use docx_rust::media::{ImageFormat, ImageInfo};
+///
+/// let gif = b"GIF89a\x20\x00\x10\x00\x00\x00\x00";
+/// let info = ImageInfo::from_bytes(gif).unwrap();
+/// assert_eq!(info.format, ImageFormat::Gif);
+/// assert_eq!((info.width, info.height), (32, 16));
+/// assert_eq!(info.extent(), (304800, 152400));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageInfo {
+    pub format: ImageFormat,
+    /// Width in pixels, or in the logical units of a metafile
+    pub width: u32,
+    /// Height in pixels, or in the logical units of a metafile
+    pub height: u32,
+    /// Horizontal resolution in dots per inch, 96 if the image does not specify one
+    pub dpi_x: u32,
+    /// Vertical resolution in dots per inch, 96 if the image does not specify one
+    pub dpi_y: u32,
+}
+
+impl ImageInfo {
+    /// Detects the image format and reads its dimensions
+    ///
+    /// Returns `None` if the bytes are not a PNG, JPEG, GIF, BMP, TIFF, EMF, placeable WMF
+    /// or SVG image, if the header is truncated, or if an SVG image specifies no size.
+    /// The size of an SVG image is taken from the `width`, `height` and `viewBox` attributes
+    /// of its root element.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            read_png(bytes)
+        } else if bytes.starts_with(&[0xFF, 0xD8]) {
+            read_jpeg(bytes)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            read_gif(bytes)
+        } else if bytes.starts_with(b"BM") {
+            read_bmp(bytes)
+        } else if bytes.starts_with(b"II*\0") || bytes.starts_with(b"MM\0*") {
+            read_tiff(bytes)
+        } else if bytes.starts_with(&[1, 0, 0, 0]) && bytes.get(40..44) == Some(b" EMF") {
+            read_emf(bytes)
+        } else if bytes.starts_with(&[0xD7, 0xCD, 0xC6, 0x9A]) {
+            read_wmf(bytes)
+        } else {
+            read_svg(bytes)
+        }
+    }
+
+    /// Returns the natural size of the image in EMUs, as `(cx, cy)`
+    pub fn extent(&self) -> (u64, u64) {
+        (
+            self.width as u64 * EMU_PER_INCH / self.dpi_x as u64,
+            self.height as u64 * EMU_PER_INCH / self.dpi_y as u64,
+        )
+    }
+
+    fn new(format: ImageFormat, width: u32, height: u32) -> Self {
+        ImageInfo {
+            format,
+            width,
+            height,
+            dpi_x: DEFAULT_DPI,
+            dpi_y: DEFAULT_DPI,
+        }
+    }
+
+    fn dpi(mut self, dpi_x: f64, dpi_y: f64) -> Self {
+        if dpi_x >= 1.0 && dpi_y >= 1.0 {
+            self.dpi_x = dpi_x.round() as u32;
+            self.dpi_y = dpi_y.round() as u32;
+        }
+        self
+    }
+}
+
+fn be_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn be_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_i32(bytes: &[u8], at: usize) -> Option<i32> {
+    Some(i32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn read_png(bytes: &[u8]) -> Option<ImageInfo> {
+    // IHDR is required to be the first chunk
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let info = ImageInfo::new(ImageFormat::Png, be_u32(bytes, 16)?, be_u32(bytes, 20)?);
+
+    let mut at = 8;
+    while let (Some(len), Some(ty)) = (be_u32(bytes, at), bytes.get(at + 4..at + 8)) {
+        match ty {
+            b"pHYs" => {
+                // only a unit of 1 (metre) carries an absolute resolution
+                if bytes.get(at + 16) == Some(&1) {
+                    let x = be_u32(bytes, at + 8)? as f64 * 0.0254;
+                    let y = be_u32(bytes, at + 12)? as f64 * 0.0254;
+                    return Some(info.dpi(x, y));
+                }
+                break;
+            }
+            b"IDAT" | b"IEND" => break,
+            _ => at += 12 + len as usize,
+        }
+    }
+
+    Some(info)
+}
+
+fn read_jpeg(bytes: &[u8]) -> Option<ImageInfo> {
+    let mut density = None;
+    let mut at = 2;
+
+    loop {
+        if *bytes.get(at)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(at + 1)?;
+        match marker {
+            // fill bytes
+            0xFF => {
+                at += 1;
+                continue;
+            }
+            // markers without a payload
+            0x01 | 0xD0..=0xD7 => {
+                at += 2;
+                continue;
+            }
+            _ => {}
+        }
+        let len = be_u16(bytes, at + 2)? as usize;
+        let segment = bytes.get(at + 4..at + 2 + len)?;
+
+        match marker {
+            0xE0 if segment.starts_with(b"JFIF\0") => {
+                let x = be_u16(segment, 8)? as f64;
+                let y = be_u16(segment, 10)? as f64;
+                density = match segment.get(7)? {
+                    1 => Some((x, y)),
+                    2 => Some((x * 2.54, y * 2.54)),
+                    _ => None,
+                };
+            }
+            // start of frame, excluding DHT, JPG and DAC
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                let height = be_u16(segment, 1)? as u32;
+                let width = be_u16(segment, 3)? as u32;
+                let info = ImageInfo::new(ImageFormat::Jpeg, width, height);
+                return Some(match density {
+                    Some((x, y)) => info.dpi(x, y),
+                    None => info,
+                });
+            }
+            // start of scan, no frame header found before the image data
+            0xDA => return None,
+            _ => {}
+        }
+        at += 2 + len;
+    }
+}
+
+fn read_gif(bytes: &[u8]) -> Option<ImageInfo> {
+    Some(ImageInfo::new(
+        ImageFormat::Gif,
+        le_u16(bytes, 6)? as u32,
+        le_u16(bytes, 8)? as u32,
+    ))
+}
+
+fn read_bmp(bytes: &[u8]) -> Option<ImageInfo> {
+    let header_size = le_i32(bytes, 14)?;
+    if header_size == 12 {
+        // BITMAPCOREHEADER
+        return Some(ImageInfo::new(
+            ImageFormat::Bmp,
+            le_u16(bytes, 18)? as u32,
+            le_u16(bytes, 20)? as u32,
+        ));
+    }
+
+    // height is negative for top-down bitmaps
+    let info = ImageInfo::new(
+        ImageFormat::Bmp,
+        le_i32(bytes, 18)?.unsigned_abs(),
+        le_i32(bytes, 22)?.unsigned_abs(),
+    );
+    if header_size >= 40 {
+        let x = le_i32(bytes, 38)? as f64 * 0.0254;
+        let y = le_i32(bytes, 42)? as f64 * 0.0254;
+        Some(info.dpi(x, y))
+    } else {
+        Some(info)
+    }
+}
+
+fn read_tiff(bytes: &[u8]) -> Option<ImageInfo> {
+    let little = bytes.starts_with(b"II");
+    let u16_at = |at: usize| {
+        let b: [u8; 2] = bytes.get(at..at + 2)?.try_into().ok()?;
+        Some(if little {
+            u16::from_le_bytes(b)
+        } else {
+            u16::from_be_bytes(b)
+        })
+    };
+    let u32_at = |at: usize| {
+        let b: [u8; 4] = bytes.get(at..at + 4)?.try_into().ok()?;
+        Some(if little {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        })
+    };
+    let rational_at = |at: usize| {
+        let den = u32_at(at + 4)?;
+        (den != 0).then(|| u32_at(at).map(|num| num as f64 / den as f64))?
+    };
+
+    let ifd = u32_at(4)? as usize;
+    let (mut width, mut height) = (None, None);
+    let (mut x, mut y, mut unit) = (None, None, 2);
+
+    for i in 0..u16_at(ifd)? as usize {
+        let entry = ifd + 2 + i * 12;
+        // SHORT values are left-aligned in the value field
+        let value = match u16_at(entry + 2)? {
+            3 => u16_at(entry + 8)? as u32,
+            _ => u32_at(entry + 8)?,
+        };
+        match u16_at(entry)? {
+            256 => width = Some(value),
+            257 => height = Some(value),
+            282 => x = rational_at(value as usize),
+            283 => y = rational_at(value as usize),
+            296 => unit = value,
+            _ => {}
+        }
+    }
+
+    let info = ImageInfo::new(ImageFormat::Tiff, width?, height?);
+    Some(match (x, y, unit) {
+        (Some(x), Some(y), 2) => info.dpi(x, y),
+        (Some(x), Some(y), 3) => info.dpi(x * 2.54, y * 2.54),
+        _ => info,
+    })
+}
+
+fn read_emf(bytes: &[u8]) -> Option<ImageInfo> {
+    // the frame of the picture is given in 0.01 millimetres
+    let width = le_i32(bytes, 32)?.checked_sub(le_i32(bytes, 24)?)?;
+    let height = le_i32(bytes, 36)?.checked_sub(le_i32(bytes, 28)?)?;
+    let info = ImageInfo::new(
+        ImageFormat::Emf,
+        width.unsigned_abs(),
+        height.unsigned_abs(),
+    );
+    Some(info.dpi(2540.0, 2540.0))
+}
+
+fn read_wmf(bytes: &[u8]) -> Option<ImageInfo> {
+    // placeable header: bounding box followed by the number of units per inch
+    let left = le_u16(bytes, 6)? as i16 as i32;
+    let top = le_u16(bytes, 8)? as i16 as i32;
+    let right = le_u16(bytes, 10)? as i16 as i32;
+    let bottom = le_u16(bytes, 12)? as i16 as i32;
+    let inch = le_u16(bytes, 14)? as f64;
+    let info = ImageInfo::new(
+        ImageFormat::Wmf,
+        (right - left).unsigned_abs(),
+        (bottom - top).unsigned_abs(),
+    );
+    Some(info.dpi(inch, inch))
+}
+
+fn read_svg(bytes: &[u8]) -> Option<ImageInfo> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let start = text.find("<svg")?;
+    let tag = &text[start..start + text[start..].find('>')?];
+
+    let attr = |name: &str| {
+        let pattern = format!("{}=", name);
+        let at = tag
+            .match_indices(&pattern)
+            .map(|(at, _)| at)
+            .find(|&at| tag[..at].ends_with(char::is_whitespace))?
+            + pattern.len();
+        let quote = tag[at..]
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')?;
+        let value = &tag[at + 1..];
+        Some(&value[..value.find(quote)?])
+    };
+    let view_box: Vec<f64> = attr("viewBox")
+        .map(|v| {
+            v.split(|c: char| c == ',' || c.is_whitespace())
+                .filter_map(|n| n.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let width = attr("width")
+        .and_then(svg_length)
+        .or_else(|| view_box.get(2).copied())?;
+    let height = attr("height")
+        .and_then(svg_length)
+        .or_else(|| view_box.get(3).copied())?;
+
+    Some(ImageInfo::new(
+        ImageFormat::Svg,
+        width.round() as u32,
+        height.round() as u32,
+    ))
+}
+
+/// Converts an SVG length to CSS pixels, which are fixed at 96 per inch
+fn svg_length(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+        .unwrap_or(value.len());
+    let number: f64 = value[..split].parse().ok()?;
+    let scale = match &value[split..] {
+        "" | "px" => 1.0,
+        "in" => 96.0,
+        "cm" => 96.0 / 2.54,
+        "mm" => 96.0 / 25.4,
+        "pt" => 96.0 / 72.0,
+        "pc" => 16.0,
+        _ => return None,
+    };
+    Some(number * scale)
+}
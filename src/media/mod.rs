@@ -1,4 +1,8 @@
-use crate::schema::SCHEMA_IMAGE;
+mod image;
+
+pub use self::image::*;
+
+use crate::schema::{SCHEMA_AUDIO, SCHEMA_IMAGE, SCHEMA_VIDEO};
 
 /// Specifies the type of a media file
 ///
@@ -6,22 +10,50 @@ use crate::schema::SCHEMA_IMAGE;
 #[cfg_attr(test, derive(PartialEq))]
 pub enum MediaType {
     Image,
+    Video,
+    Audio,
 }
 
 pub fn get_media_type_relation_type(mt: &MediaType) -> &'static str {
     match mt {
         MediaType::Image => SCHEMA_IMAGE,
+        MediaType::Video => SCHEMA_VIDEO,
+        MediaType::Audio => SCHEMA_AUDIO,
     }
 }
 
 pub fn get_media_type(filename: &str) -> Option<MediaType> {
-    if filename.ends_with("png")
-        | filename.ends_with("jpg")
-        | filename.ends_with("jpeg")
-        | filename.ends_with("bmp")
-    {
-        Some(MediaType::Image)
-    } else {
-        None
+    match get_media_content_type(filename)?.split('/').next() {
+        Some("image") => Some(MediaType::Image),
+        Some("video") => Some(MediaType::Video),
+        Some("audio") => Some(MediaType::Audio),
+        _ => None,
     }
 }
+
+/// Returns the content type registered in `[Content_Types].xml` for the extension of a media file
+pub fn get_media_content_type(filename: &str) -> Option<&'static str> {
+    let (_, ext) = filename.rsplit_once('.')?;
+    let ty = match ext.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "tif" | "tiff" => "image/tiff",
+        "emf" => "image/x-emf",
+        "wmf" => "image/x-wmf",
+        "svg" => "image/svg+xml",
+        "mp4" => "video/mp4",
+        "m4v" => "video/x-m4v",
+        "mov" => "video/quicktime",
+        "avi" => "video/x-msvideo",
+        "wmv" => "video/x-ms-wmv",
+        "mpg" | "mpeg" => "video/mpeg",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "wav" => "audio/wav",
+        "wma" => "audio/x-ms-wma",
+        _ => return None,
+    };
+    Some(ty)
+}
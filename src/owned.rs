@@ -0,0 +1,60 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Converts a value which may borrow from the parsed XML into one owning all of its data
+///
+/// Every type of the document model which has a lifetime implements this trait,
+/// its owned form is the same type with the `'static` lifetime.
+///
+/// ```rust
+/// use docx_rust::document::Paragraph;
+/// use docx_rust::IntoOwned;
+///
+/// fn parse(xml: String) -> Paragraph<'static> {
+///     use hard_xml::XmlRead;
+///     Paragraph::from_str(&xml).unwrap().into_owned()
+/// }
+///
+/// let para = parse(r#"<w:p><w:r><w:t>text</w:t></w:r></w:p>"#.to_string());
+/// assert_eq!("text", para.text());
+/// ```
+pub trait IntoOwned {
+    type Owned: 'static;
+
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl<'a, B: ToOwned + ?Sized + 'static> IntoOwned for Cow<'a, B> {
+    type Owned = Cow<'static, B>;
+
+    fn into_owned(self) -> Cow<'static, B> {
+        Cow::Owned(Cow::into_owned(self))
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
+
+impl<K: Eq + Hash + 'static, V: IntoOwned> IntoOwned for HashMap<K, V> {
+    type Owned = HashMap<K, V::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter()
+            .map(|(key, value)| (key, value.into_owned()))
+            .collect()
+    }
+}
@@ -7,8 +7,8 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::borrow::Cow;
 use std::io::Write;
 
-use crate::__string_enum;
 use crate::schema::SCHEMA_RELATIONSHIPS;
+use crate::{__into_owned, __string_enum};
 
 #[derive(Debug, Default, XmlRead, Clone)]
 #[xml(tag = "Relationships")]
@@ -17,6 +17,8 @@ pub struct Relationships<'a> {
     pub relationships: Vec<Relationship<'a>>,
 }
 
+__into_owned! { Relationships { relationships } }
+
 impl<'a> XmlWrite for Relationships<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let Relationships { relationships } = self;
@@ -45,69 +47,73 @@ impl<'a> XmlWrite for Relationships<'a> {
 }
 
 impl<'a> Relationships<'a> {
-    pub fn add_rel(&mut self, schema: &'a str, target: &'a str) {
+    /// Adds a relationship unless one to the same target exists, returning its id
+    pub fn add_rel<T: Into<Cow<'a, str>>>(&mut self, schema: &'a str, target: T) -> String {
+        let target = target.into();
         let has = self.relationships.iter().find(|r| r.target == target);
-        if has.is_none() {
-            let ids: Vec<_> = self
-                .relationships
-                .iter()
-                .map(|r| r.id.to_string())
-                .collect();
-
-            let len = self.relationships.len();
-
-            let mut available = false;
-            let mut id = len;
-            while !available {
-                id += 1;
-                let idstr = format!("rId{}", id);
-                available = !ids.contains(&idstr);
-            }
-
+        if let Some(rel) = has {
+            rel.id.to_string()
+        } else {
             //hack
             //let target = target.replace("jpeg","png");
+            let id = self.next_id();
             self.relationships.push(Relationship {
-                id: format!("rId{}", id).into(),
-                target: target.into(),
+                id: id.clone().into(),
+                target,
                 ty: schema.into(),
                 target_mode: None,
             });
+            id
         }
     }
 
-    pub fn add_rel_with_target_mode(
+    /// Adds a relationship with the given target mode, returning its id
+    ///
+    /// A relationship to a target inside the package is only added if there is none to the same
+    /// target yet, like with [`Relationships::add_rel`], while one to an external target is
+    /// always added, like with [`Relationships::add_external_rel`].
+    pub fn add_rel_with_target_mode<T: Into<Cow<'a, str>>>(
         &mut self,
         schema: &'a str,
-        target: &'a str,
+        target: T,
         target_mode: Option<&'a str>,
-    ) {
-        let has = self.relationships.iter().find(|r| r.target == target);
-        if has.is_none() {
-            let ids: Vec<_> = self
-                .relationships
-                .iter()
-                .map(|r| r.id.to_string())
-                .collect();
-
-            let len = self.relationships.len();
-
-            let mut available = false;
-            let mut id = len;
-            while !available {
-                id += 1;
-                let idstr = format!("rId{}", id);
-                available = !ids.contains(&idstr);
+    ) -> String {
+        let target = target.into();
+        let target_mode = TargetMode::from_str(target_mode);
+        if target_mode != Some(TargetMode::External) {
+            if let Some(rel) = self.relationships.iter().find(|r| r.target == target) {
+                return rel.id.to_string();
             }
-
-            //hack
-            //let target = target.replace("jpeg","png");
-            self.relationships.push(Relationship {
-                id: format!("rId{}", id).into(),
-                target: target.into(),
-                ty: schema.into(),
-                target_mode: TargetMode::from_str(target_mode),
-            });
         }
+        //hack
+        //let target = target.replace("jpeg","png");
+        let id = self.next_id();
+        self.relationships.push(Relationship {
+            id: id.clone().into(),
+            target,
+            ty: schema.into(),
+            target_mode,
+        });
+        id
+    }
+
+    /// Adds a relationship to a target outside of the package, returning its id
+    ///
+    /// Unlike [`Relationships::add_rel`], a new relationship is added even if one to the same
+    /// target exists, so every hyperlink can have a relationship of its own.
+    pub fn add_external_rel<T: Into<Cow<'a, str>>>(
+        &mut self,
+        schema: &'a str,
+        target: T,
+    ) -> String {
+        let id = self.next_id();
+        self.relationships.push(Relationship {
+            id: id.clone().into(),
+            target: target.into(),
+            ty: schema.into(),
+            target_mode: Some(TargetMode::External),
+        });
+        id
     }
 
     pub fn get_target(&self, id: &str) -> Option<&str> {
@@ -116,6 +122,38 @@ impl<'a> Relationships<'a> {
             .find(|r| r.id == id)
             .map(|r| &*r.target)
     }
+
+    /// Returns the target of a relationship which points outside of the package, like a URL
+    pub fn get_external_target(&self, id: &str) -> Option<&str> {
+        self.relationships
+            .iter()
+            .find(|r| r.id == id && r.target_mode == Some(TargetMode::External))
+            .map(|r| &*r.target)
+    }
+
+    /// Returns an id like `rId1` which is not in use
+    ///
+    /// The search starts after the number of relationships, so ids stay close to the order in
+    /// which relationships are added.
+    pub(crate) fn next_id(&self) -> String {
+        let ids: Vec<_> = self
+            .relationships
+            .iter()
+            .map(|r| r.id.to_string())
+            .collect();
+
+        let len = self.relationships.len();
+
+        let mut available = false;
+        let mut id = len;
+        while !available {
+            id += 1;
+            let idstr = format!("rId{}", id);
+            available = !ids.contains(&idstr);
+        }
+
+        format!("rId{}", id)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -162,3 +200,5 @@ pub struct Relationship<'a> {
     #[xml(attr = "TargetMode")]
     pub target_mode: Option<TargetMode>,
 }
+
+__into_owned! { Relationship { id, target, ty; target_mode } }
@@ -13,10 +13,14 @@ pub const SCHEMA_WORDML_14: &str = "http://schemas.microsoft.com/office/word/201
 pub const SCHEMA_DRAWINGML: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
 pub const SCHEMA_WP: &str =
     "http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing";
+pub const SCHEMA_PICTURE: &str = "http://schemas.openxmlformats.org/drawingml/2006/picture";
+pub const SCHEMA_SVG: &str = "http://schemas.microsoft.com/office/drawing/2016/SVG/main";
 pub const SCHEMA_RELATIONSHIPS_DOCUMENT: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
 pub const SCHEMAS_EXTENDED: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/extended-properties";
+pub const SCHEMA_CUSTOM_PROPERTIES: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/custom-properties";
 
 // relationships
 pub const SCHEMA_RELATIONSHIPS: &str =
@@ -49,6 +53,10 @@ pub const SCHEMA_THEME: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme";
 pub const SCHEMA_IMAGE: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
+pub const SCHEMA_VIDEO: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/video";
+pub const SCHEMA_AUDIO: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/audio";
 pub const SCHEMA_COMMENTS: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments";
 pub const SCHEMA_NUMBERING: &str =
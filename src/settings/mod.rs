@@ -8,7 +8,7 @@ use std::borrow::Cow;
 use std::io::Write;
 
 use crate::schema::{SCHEMA_MAIN, SCHEMA_RELATIONSHIPS_DOCUMENT, SCHEMA_WORDML_14};
-use crate::{__string_enum, __xml_test_suites, write_attr};
+use crate::{__into_owned, __string_enum, __xml_test_suites, write_attr};
 
 /// The root element of the main document part.
 #[derive(Debug, Default, XmlRead, Clone)]
@@ -152,10 +152,10 @@ pub struct Settings<'a> {
     pub summary_length: Option<SummaryLength>,
     ///  Paragraph Style Applied to Automatically Generated Paragraphs
     #[xml(child = "w:clickAndTypeStyle")]
-    pub click_and_type_style: Option<ClickAndTypeStyle>,
+    pub click_and_type_style: Option<ClickAndTypeStyle<'a>>,
     ///  Default Table Style for Newly Inserted Tables
     #[xml(child = "w:defaultTableStyle")]
-    pub default_table_style: Option<DefaultTableStyle>,
+    pub default_table_style: Option<DefaultTableStyle<'a>>,
     ///  Different Even/Odd Page Headers and Footers
     #[xml(child = "w:evenAndOddHeaders")]
     pub even_and_odd_headers: Option<EvenAndOddHeaders>,
@@ -314,6 +314,38 @@ pub struct Settings<'a> {
     pub list_separator: Option<ListSeparator<'a>>,
 }
 
+__into_owned! {
+    Settings {
+        attached_template, doc_vars, rsids, theme_font_lang, decimal_symbol, list_separator,
+        click_and_type_style, default_table_style;
+        write_protection, view, zoom, remove_personal_information, remove_date_and_time,
+        do_not_display_page_boundaries, display_background_shape, print_post_script_over_text,
+        print_fractional_character_width, print_forms_data, embed_true_type_fonts,
+        embed_system_fonts, save_subset_fonts, save_forms_data, mirror_margins,
+        align_borders_and_edges, borders_do_not_surround_header, borders_do_not_surround_footer,
+        gutter_at_top, hide_spelling_errors, hide_grammatical_errors, active_writing_style,
+        proof_state, forms_design, link_styles, style_pane_format_filter, style_pane_sort_method,
+        document_type, mail_merge, revision_view, track_revisions, do_not_track_moves,
+        do_not_track_formatting, document_protection, auto_format_override, style_lock_theme,
+        style_lock_qfset, default_tab_stop, auto_hyphenation, consecutive_hyphen_limit,
+        hyphenation_zone, do_not_hyphenate_caps, show_envelope, summary_length,
+        even_and_odd_headers, book_fold_rev_printing, book_fold_printing, book_fold_printing_sheets,
+        drawing_grid_horizontal_spacing, drawing_grid_vertical_spacing,
+        display_horizontal_drawing_grid_every,
+        display_vertical_drawing_grid_every, do_not_use_margins_for_drawing_grid_origin,
+        drawing_grid_horizontal_origin, drawing_grid_vertical_origin, do_not_shade_form_data,
+        no_punctuation_kerning, character_spacing_control, print_two_on_one,
+        strict_first_and_last_chars, no_line_breaks_after, no_line_breaks_before,
+        save_preview_picture, do_not_validate_against_schema, save_invalid_xml,
+        ignore_mixed_content, always_show_placeholder_text, do_not_demarcate_invalid_xml,
+        save_xml_data_only, use_xsltwhen_saving, save_through_xslt, show_xmltags,
+        always_merge_empty_namespace, update_fields, hdr_shape_defaults, footnote_pr, endnote_pr,
+        compat, ui_compat97_to2003, clr_scheme_mapping, do_not_include_subdocs_in_stats,
+        do_not_auto_compress_pictures, force_upgrade, captions, read_mode_ink_lock_down,
+        shape_defaults, do_not_embed_smart_tags
+    }
+}
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:writeProtection")]
@@ -574,6 +606,8 @@ pub struct AttachedTemplate<'a> {
     pub val: Cow<'a, str>,
 }
 
+__into_owned! { AttachedTemplate { val } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:linkStyles")]
@@ -756,12 +790,22 @@ pub struct SummaryLength {}
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:clickAndTypeStyle")]
-pub struct ClickAndTypeStyle {}
+pub struct ClickAndTypeStyle<'a> {
+    #[xml(attr = "w:val")]
+    pub val: Cow<'a, str>,
+}
+
+__into_owned! { ClickAndTypeStyle { val } }
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:defaultTableStyle")]
-pub struct DefaultTableStyle {}
+pub struct DefaultTableStyle<'a> {
+    #[xml(attr = "w:val")]
+    pub val: Cow<'a, str>,
+}
+
+__into_owned! { DefaultTableStyle { val } }
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -1012,6 +1056,8 @@ pub struct DocVars<'a> {
     pub vars: Vec<DocVar<'a>>,
 }
 
+__into_owned! { DocVars { vars } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:docVar")]
@@ -1022,6 +1068,8 @@ pub struct DocVar<'a> {
     pub val: Cow<'a, str>,
 }
 
+__into_owned! { DocVar { name, val } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:rsids")]
@@ -1032,6 +1080,8 @@ pub struct Rsids<'a> {
     pub rsids: Vec<Rsid<'a>>,
 }
 
+__into_owned! { Rsids { ro, rsids } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:rsid")]
@@ -1040,6 +1090,8 @@ pub struct Rsid<'a> {
     pub val: Cow<'a, str>,
 }
 
+__into_owned! { Rsid { val } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:rsidRoot")]
@@ -1048,6 +1100,8 @@ pub struct RsidRoot<'a> {
     pub val: Cow<'a, str>,
 }
 
+__into_owned! { RsidRoot { val } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:uiCompat97To2003")]
@@ -1059,7 +1113,79 @@ pub struct UiCompat97to2003 {
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:clrSchemeMapping")]
-pub struct ClrSchemeMapping {}
+pub struct ClrSchemeMapping {
+    ///  Background 1 Theme Color Mapping
+    #[xml(attr = "w:bg1")]
+    pub background1: Option<ColorSchemeIndex>,
+    ///  Text 1 Theme Color Mapping
+    #[xml(attr = "w:t1")]
+    pub text1: Option<ColorSchemeIndex>,
+    ///  Background 2 Theme Color Mapping
+    #[xml(attr = "w:bg2")]
+    pub background2: Option<ColorSchemeIndex>,
+    ///  Text 2 Theme Color Mapping
+    #[xml(attr = "w:t2")]
+    pub text2: Option<ColorSchemeIndex>,
+    ///  Accent 1 Theme Color Mapping
+    #[xml(attr = "w:accent1")]
+    pub accent1: Option<ColorSchemeIndex>,
+    ///  Accent 2 Theme Color Mapping
+    #[xml(attr = "w:accent2")]
+    pub accent2: Option<ColorSchemeIndex>,
+    ///  Accent 3 Theme Color Mapping
+    #[xml(attr = "w:accent3")]
+    pub accent3: Option<ColorSchemeIndex>,
+    ///  Accent 4 Theme Color Mapping
+    #[xml(attr = "w:accent4")]
+    pub accent4: Option<ColorSchemeIndex>,
+    ///  Accent 5 Theme Color Mapping
+    #[xml(attr = "w:accent5")]
+    pub accent5: Option<ColorSchemeIndex>,
+    ///  Accent 6 Theme Color Mapping
+    #[xml(attr = "w:accent6")]
+    pub accent6: Option<ColorSchemeIndex>,
+    ///  Hyperlink Theme Color Mapping
+    #[xml(attr = "w:hyperlink")]
+    pub hyperlink: Option<ColorSchemeIndex>,
+    ///  Followed Hyperlink Theme Color Mapping
+    #[xml(attr = "w:followedHyperlink")]
+    pub followed_hyperlink: Option<ColorSchemeIndex>,
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum ColorSchemeIndex {
+    #[default]
+    Dark1, //	Dark Color 1
+    Light1,            //	Light Color 1
+    Dark2,             //	Dark Color 2
+    Light2,            //	Light Color 2
+    Accent1,           //	Accent Color 1
+    Accent2,           //	Accent Color 2
+    Accent3,           //	Accent Color 3
+    Accent4,           //	Accent Color 4
+    Accent5,           //	Accent Color 5
+    Accent6,           //	Accent Color 6
+    Hyperlink,         //	Hyperlink Color
+    FollowedHyperlink, //	Followed Hyperlink Color
+}
+
+__string_enum! {
+    ColorSchemeIndex {
+        Dark1 = "dark1",
+        Light1 = "light1",
+        Dark2 = "dark2",
+        Light2 = "light2",
+        Accent1 = "accent1",
+        Accent2 = "accent2",
+        Accent3 = "accent3",
+        Accent4 = "accent4",
+        Accent5 = "accent5",
+        Accent6 = "accent6",
+        Hyperlink = "hyperlink",
+        FollowedHyperlink = "followedHyperlink",
+    }
+}
 
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -1113,6 +1239,8 @@ pub struct DecimalSymbol<'a> {
     pub val: Cow<'a, str>,
 }
 
+__into_owned! { DecimalSymbol { val } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:listSeparator")]
@@ -1121,6 +1249,8 @@ pub struct ListSeparator<'a> {
     pub val: Cow<'a, str>,
 }
 
+__into_owned! { ListSeparator { val } }
+
 #[derive(Debug, Default, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:evenAndOddHeaders")]
@@ -1136,6 +1266,8 @@ pub struct ThemeFontLang<'a> {
     pub east_asia: Option<Cow<'a, str>>,
 }
 
+__into_owned! { ThemeFontLang { val, east_asia } }
+
 impl<'a> XmlWrite for Settings<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let Settings {
@@ -0,0 +1,193 @@
+//! Built-in styles
+//!
+//! The definitions follow the ones Word writes into a new blank document, so a generated
+//! document looks the same in Word as a document created there.
+
+use hard_xml::XmlRead;
+
+use crate::styles::{Style, Styles};
+
+const BUILTIN_STYLES: &str = r#"<w:styles>
+<w:docDefaults>
+<w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:eastAsia="Calibri" w:hAnsi="Calibri" w:cs="Times New Roman"/><w:sz w:val="22"/><w:szCs w:val="22"/><w:lang w:val="en-US" w:eastAsia="en-US" w:bidi="ar-SA"/></w:rPr></w:rPrDefault>
+<w:pPrDefault><w:pPr><w:spacing w:after="160" w:line="259" w:lineRule="auto"/></w:pPr></w:pPrDefault>
+</w:docDefaults>
+<w:latentStyles w:defLockedState="false" w:defUIPriority="99" w:defSemiHidden="false" w:defUnhideWhenUsed="false" w:defQFormat="false" w:count="376">
+<w:lsdException w:name="Normal" w:uiPriority="0" w:qFormat="true"/>
+<w:lsdException w:name="heading 1" w:uiPriority="9" w:qFormat="true"/>
+<w:lsdException w:name="heading 2" w:semiHidden="true" w:uiPriority="9" w:unhideWhenUsed="true" w:qFormat="true"/>
+<w:lsdException w:name="heading 3" w:semiHidden="true" w:uiPriority="9" w:unhideWhenUsed="true" w:qFormat="true"/>
+<w:lsdException w:name="heading 4" w:semiHidden="true" w:uiPriority="9" w:unhideWhenUsed="true" w:qFormat="true"/>
+<w:lsdException w:name="heading 5" w:semiHidden="true" w:uiPriority="9" w:unhideWhenUsed="true" w:qFormat="true"/>
+<w:lsdException w:name="heading 6" w:semiHidden="true" w:uiPriority="9" w:unhideWhenUsed="true" w:qFormat="true"/>
+<w:lsdException w:name="heading 7" w:semiHidden="true" w:uiPriority="9" w:unhideWhenUsed="true" w:qFormat="true"/>
+<w:lsdException w:name="heading 8" w:semiHidden="true" w:uiPriority="9" w:unhideWhenUsed="true" w:qFormat="true"/>
+<w:lsdException w:name="heading 9" w:semiHidden="true" w:uiPriority="9" w:unhideWhenUsed="true" w:qFormat="true"/>
+<w:lsdException w:name="toc 1" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="toc 2" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="toc 3" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="toc 4" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="toc 5" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="toc 6" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="toc 7" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="toc 8" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="toc 9" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="footnote text" w:semiHidden="true" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="caption" w:semiHidden="true" w:uiPriority="35" w:unhideWhenUsed="true" w:qFormat="true"/>
+<w:lsdException w:name="footnote reference" w:semiHidden="true" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="Title" w:uiPriority="10" w:qFormat="true"/>
+<w:lsdException w:name="Default Paragraph Font" w:semiHidden="true" w:uiPriority="1" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="Subtitle" w:uiPriority="11" w:qFormat="true"/>
+<w:lsdException w:name="Hyperlink" w:semiHidden="true" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="Strong" w:uiPriority="22" w:qFormat="true"/>
+<w:lsdException w:name="Emphasis" w:uiPriority="20" w:qFormat="true"/>
+<w:lsdException w:name="Normal Table" w:semiHidden="true" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="No List" w:semiHidden="true" w:unhideWhenUsed="true"/>
+<w:lsdException w:name="Table Grid" w:uiPriority="39"/>
+<w:lsdException w:name="No Spacing" w:uiPriority="1" w:qFormat="true"/>
+<w:lsdException w:name="List Paragraph" w:uiPriority="34" w:qFormat="true"/>
+<w:lsdException w:name="Quote" w:uiPriority="29" w:qFormat="true"/>
+<w:lsdException w:name="Intense Quote" w:uiPriority="30" w:qFormat="true"/>
+<w:lsdException w:name="TOC Heading" w:semiHidden="true" w:uiPriority="39" w:unhideWhenUsed="true" w:qFormat="true"/>
+</w:latentStyles>
+<w:style w:type="paragraph" w:default="true" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style>
+<w:style w:type="character" w:default="true" w:styleId="DefaultParagraphFont"><w:name w:val="Default Paragraph Font"/><w:uiPriority w:val="1"/><w:semiHidden/><w:unhideWhenUsed/></w:style>
+<w:style w:type="table" w:default="true" w:styleId="TableNormal"><w:name w:val="Normal Table"/><w:uiPriority w:val="99"/><w:semiHidden/><w:unhideWhenUsed/><w:tblPr><w:tblInd w:w="0" w:type="dxa"/><w:tblCellMar><w:top w:w="0" w:type="dxa"/><w:left w:w="108" w:type="dxa"/><w:bottom w:w="0" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr></w:style>
+<w:style w:type="numbering" w:default="true" w:styleId="NoList"><w:name w:val="No List"/><w:uiPriority w:val="99"/><w:semiHidden/><w:unhideWhenUsed/></w:style>
+<w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="Heading1Char"/><w:uiPriority w:val="9"/><w:qFormat/><w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before="240" w:after="0"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="2F5496" w:themeColor="accent1" w:themeShade="BF"/><w:sz w:val="32"/><w:szCs w:val="32"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="Heading2Char"/><w:uiPriority w:val="9"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before="40" w:after="0"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="2F5496" w:themeColor="accent1" w:themeShade="BF"/><w:sz w:val="26"/><w:szCs w:val="26"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading3"><w:name w:val="heading 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="Heading3Char"/><w:uiPriority w:val="9"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before="40" w:after="0"/><w:outlineLvl w:val="2"/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="1F3763" w:themeColor="accent1" w:themeShade="7F"/><w:sz w:val="24"/><w:szCs w:val="24"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading4"><w:name w:val="heading 4"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="Heading4Char"/><w:uiPriority w:val="9"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before="40" w:after="0"/><w:outlineLvl w:val="3"/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:i/><w:iCs/><w:color w:val="2F5496" w:themeColor="accent1" w:themeShade="BF"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading5"><w:name w:val="heading 5"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="Heading5Char"/><w:uiPriority w:val="9"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before="40" w:after="0"/><w:outlineLvl w:val="4"/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="2F5496" w:themeColor="accent1" w:themeShade="BF"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading6"><w:name w:val="heading 6"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="Heading6Char"/><w:uiPriority w:val="9"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before="40" w:after="0"/><w:outlineLvl w:val="5"/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="1F3763" w:themeColor="accent1" w:themeShade="7F"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading7"><w:name w:val="heading 7"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="Heading7Char"/><w:uiPriority w:val="9"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before="40" w:after="0"/><w:outlineLvl w:val="6"/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:i/><w:iCs/><w:color w:val="1F3763" w:themeColor="accent1" w:themeShade="7F"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading8"><w:name w:val="heading 8"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="Heading8Char"/><w:uiPriority w:val="9"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before="40" w:after="0"/><w:outlineLvl w:val="7"/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="272727" w:themeColor="text1" w:themeTint="D8"/><w:sz w:val="21"/><w:szCs w:val="21"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading9"><w:name w:val="heading 9"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="Heading9Char"/><w:uiPriority w:val="9"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before="40" w:after="0"/><w:outlineLvl w:val="8"/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:i/><w:iCs/><w:color w:val="272727" w:themeColor="text1" w:themeTint="D8"/><w:sz w:val="21"/><w:szCs w:val="21"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="Heading1Char"><w:name w:val="Heading 1 Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Heading1"/><w:uiPriority w:val="9"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="2F5496" w:themeColor="accent1" w:themeShade="BF"/><w:sz w:val="32"/><w:szCs w:val="32"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="Heading2Char"><w:name w:val="Heading 2 Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Heading2"/><w:uiPriority w:val="9"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="2F5496" w:themeColor="accent1" w:themeShade="BF"/><w:sz w:val="26"/><w:szCs w:val="26"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="Heading3Char"><w:name w:val="Heading 3 Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Heading3"/><w:uiPriority w:val="9"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="1F3763" w:themeColor="accent1" w:themeShade="7F"/><w:sz w:val="24"/><w:szCs w:val="24"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="Heading4Char"><w:name w:val="Heading 4 Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Heading4"/><w:uiPriority w:val="9"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:i/><w:iCs/><w:color w:val="2F5496" w:themeColor="accent1" w:themeShade="BF"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="Heading5Char"><w:name w:val="Heading 5 Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Heading5"/><w:uiPriority w:val="9"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="2F5496" w:themeColor="accent1" w:themeShade="BF"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="Heading6Char"><w:name w:val="Heading 6 Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Heading6"/><w:uiPriority w:val="9"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="1F3763" w:themeColor="accent1" w:themeShade="7F"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="Heading7Char"><w:name w:val="Heading 7 Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Heading7"/><w:uiPriority w:val="9"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:i/><w:iCs/><w:color w:val="1F3763" w:themeColor="accent1" w:themeShade="7F"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="Heading8Char"><w:name w:val="Heading 8 Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Heading8"/><w:uiPriority w:val="9"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:color w:val="272727" w:themeColor="text1" w:themeTint="D8"/><w:sz w:val="21"/><w:szCs w:val="21"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="Heading9Char"><w:name w:val="Heading 9 Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Heading9"/><w:uiPriority w:val="9"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:i/><w:iCs/><w:color w:val="272727" w:themeColor="text1" w:themeTint="D8"/><w:sz w:val="21"/><w:szCs w:val="21"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:link w:val="TitleChar"/><w:uiPriority w:val="10"/><w:qFormat/><w:pPr><w:spacing w:after="0" w:line="240" w:lineRule="auto"/><w:contextualSpacing/></w:pPr><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:spacing w:val="-10"/><w:kern w:val="28"/><w:sz w:val="56"/><w:szCs w:val="56"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="TitleChar"><w:name w:val="Title Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="Title"/><w:uiPriority w:val="10"/><w:rPr><w:rFonts w:ascii="Calibri Light" w:eastAsia="Times New Roman" w:hAnsi="Calibri Light" w:cs="Times New Roman"/><w:spacing w:val="-10"/><w:kern w:val="28"/><w:sz w:val="56"/><w:szCs w:val="56"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Caption"><w:name w:val="caption"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:uiPriority w:val="35"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:spacing w:after="200" w:line="240" w:lineRule="auto"/></w:pPr><w:rPr><w:i/><w:iCs/><w:color w:val="44546A" w:themeColor="text2"/><w:sz w:val="18"/><w:szCs w:val="18"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOCHeading"><w:name w:val="TOC Heading"/><w:basedOn w:val="Heading1"/><w:next w:val="Normal"/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:qFormat/><w:pPr><w:outlineLvl w:val="9"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOC1"><w:name w:val="toc 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:autoRedefine/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="100"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOC2"><w:name w:val="toc 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:autoRedefine/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="100"/><w:ind w:left="220"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOC3"><w:name w:val="toc 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:autoRedefine/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="100"/><w:ind w:left="440"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOC4"><w:name w:val="toc 4"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:autoRedefine/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="100"/><w:ind w:left="660"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOC5"><w:name w:val="toc 5"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:autoRedefine/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="100"/><w:ind w:left="880"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOC6"><w:name w:val="toc 6"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:autoRedefine/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="100"/><w:ind w:left="1100"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOC7"><w:name w:val="toc 7"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:autoRedefine/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="100"/><w:ind w:left="1320"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOC8"><w:name w:val="toc 8"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:autoRedefine/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="100"/><w:ind w:left="1540"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TOC9"><w:name w:val="toc 9"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:autoRedefine/><w:uiPriority w:val="39"/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="100"/><w:ind w:left="1760"/></w:pPr></w:style>
+<w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:basedOn w:val="DefaultParagraphFont"/><w:uiPriority w:val="99"/><w:unhideWhenUsed/><w:rPr><w:color w:val="0563C1" w:themeColor="hyperlink"/><w:u w:val="single"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="ListParagraph"><w:name w:val="List Paragraph"/><w:basedOn w:val="Normal"/><w:uiPriority w:val="34"/><w:qFormat/><w:pPr><w:ind w:left="720"/><w:contextualSpacing/></w:pPr></w:style>
+<w:style w:type="table" w:styleId="TableGrid"><w:name w:val="Table Grid"/><w:basedOn w:val="TableNormal"/><w:uiPriority w:val="39"/><w:pPr><w:spacing w:after="0" w:line="240" w:lineRule="auto"/></w:pPr><w:tblPr><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:left w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:right w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="auto"/></w:tblBorders></w:tblPr></w:style>
+<w:style w:type="paragraph" w:styleId="FootnoteText"><w:name w:val="footnote text"/><w:basedOn w:val="Normal"/><w:link w:val="FootnoteTextChar"/><w:uiPriority w:val="99"/><w:semiHidden/><w:unhideWhenUsed/><w:pPr><w:spacing w:after="0" w:line="240" w:lineRule="auto"/></w:pPr><w:rPr><w:sz w:val="20"/><w:szCs w:val="20"/></w:rPr></w:style>
+<w:style w:type="character" w:customStyle="true" w:styleId="FootnoteTextChar"><w:name w:val="Footnote Text Char"/><w:basedOn w:val="DefaultParagraphFont"/><w:link w:val="FootnoteText"/><w:uiPriority w:val="99"/><w:semiHidden/><w:rPr><w:sz w:val="20"/><w:szCs w:val="20"/></w:rPr></w:style>
+<w:style w:type="character" w:styleId="FootnoteReference"><w:name w:val="footnote reference"/><w:basedOn w:val="DefaultParagraphFont"/><w:uiPriority w:val="99"/><w:semiHidden/><w:unhideWhenUsed/><w:rPr><w:vertAlign w:val="superscript"/></w:rPr></w:style>
+</w:styles>"#;
+
+impl Styles<'static> {
+    /// Returns the styles of a new blank Word document
+    ///
+    /// Besides the document defaults and the latent styles, it defines `Normal`, the default
+    /// character, table and numbering styles, `Heading1` to `Heading9` with their linked
+    /// character styles, `Title`, `Caption`, `TOCHeading`, `TOC1` to `TOC9`, `Hyperlink`,
+    /// `ListParagraph`, `TableGrid`, `FootnoteText` and `FootnoteReference`.
+    ///
+    /// ```rust
+    /// use docx_rust::styles::Styles;
+    ///
+    /// let styles = Styles::builtin();
+    /// let heading = styles.get("Heading2").unwrap();
+    /// assert_eq!(heading.base.as_ref().unwrap().value, "Normal");
+    /// assert_eq!(heading.link.as_ref().unwrap().value, "Heading2Char");
+    /// ```
+    pub fn builtin() -> Self {
+        Styles::from_str(BUILTIN_STYLES).expect("built-in styles are well-formed")
+    }
+
+    /// Returns the built-in style with the given id
+    pub fn builtin_style(style_id: &str) -> Option<Style<'static>> {
+        Styles::builtin()
+            .styles
+            .into_iter()
+            .find(|style| style.style_id == style_id)
+    }
+}
+
+impl<'a> Styles<'a> {
+    /// Adds a built-in style and the styles it refers to, unless they are defined already
+    ///
+    /// The styles it is based on, the style of the next paragraph and the linked style are
+    /// added too, so the document stays consistent. Returns `false` if there is no built-in
+    /// style with that id.
+    ///
+    /// ```rust
+    /// use docx_rust::styles::Styles;
+    ///
+    /// let mut styles = Styles::new();
+    /// assert!(styles.add_builtin("Heading1"));
+    /// assert!(!styles.add_builtin("Heading10"));
+    ///
+    /// let ids: Vec<_> = styles.styles.iter().map(|s| &*s.style_id).collect();
+    /// assert_eq!(ids, ["Heading1", "Normal", "Heading1Char", "DefaultParagraphFont"]);
+    /// ```
+    pub fn add_builtin(&mut self, style_id: &str) -> bool {
+        let builtin = Styles::builtin();
+        if builtin.get(style_id).is_none() {
+            return false;
+        }
+
+        let mut pending = vec![style_id.to_string()];
+        while let Some(id) = pending.pop() {
+            if self.get(&id).is_some() {
+                continue;
+            }
+            let Some(style) = builtin.get(&id) else {
+                continue;
+            };
+            let refs = [
+                style.link.as_ref().map(|link| &link.value),
+                style.next.as_ref().map(|next| &next.value),
+                style.base.as_ref().map(|base| &base.value),
+            ];
+            pending.extend(refs.into_iter().flatten().map(|id| id.to_string()));
+            self.styles.push(style.clone());
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_styles_refer_to_each_other() {
+        let styles = Styles::builtin();
+        assert!(styles.default.is_some());
+        assert!(styles.latent_styles.is_some());
+        for style in styles.styles.iter() {
+            let refs = [
+                style.base.as_ref().map(|base| &base.value),
+                style.next.as_ref().map(|next| &next.value),
+                style.link.as_ref().map(|link| &link.value),
+            ];
+            for id in refs.into_iter().flatten() {
+                assert!(
+                    styles.get(id).is_some(),
+                    "{} refers to {}",
+                    style.style_id,
+                    id
+                );
+            }
+        }
+    }
+}
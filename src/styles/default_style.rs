@@ -2,7 +2,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 
 use crate::{
-    __setter, __xml_test_suites,
+    __into_owned, __setter, __xml_test_suites,
     formatting::{CharacterProperty, ParagraphProperty},
 };
 
@@ -28,6 +28,8 @@ pub struct DefaultStyle<'a> {
     pub paragraph: DefaultParagraphProperty<'a>,
 }
 
+__into_owned! { DefaultStyle { character, paragraph } }
+
 impl<'a> DefaultStyle<'a> {
     __setter!(character: DefaultCharacterProperty<'a>);
     __setter!(paragraph: DefaultParagraphProperty<'a>);
@@ -43,6 +45,8 @@ pub struct DefaultCharacterProperty<'a> {
     pub inner: Option<CharacterProperty<'a>>,
 }
 
+__into_owned! { DefaultCharacterProperty { inner } }
+
 impl<'a, T: Into<CharacterProperty<'a>>> From<T> for DefaultCharacterProperty<'a> {
     fn from(val: T) -> Self {
         DefaultCharacterProperty {
@@ -61,6 +65,8 @@ pub struct DefaultParagraphProperty<'a> {
     pub inner: Option<ParagraphProperty<'a>>,
 }
 
+__into_owned! { DefaultParagraphProperty { inner } }
+
 impl<'a, T: Into<ParagraphProperty<'a>>> From<T> for DefaultParagraphProperty<'a> {
     fn from(val: T) -> Self {
         DefaultParagraphProperty {
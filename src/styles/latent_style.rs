@@ -2,6 +2,8 @@
 use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
+use crate::__into_owned;
+
 /// Style
 ///
 /// A style that applied to a region of the document.
@@ -33,3 +35,5 @@ pub struct LatentStyle<'a> {
     #[xml(attr = "w:qFormat")]
     pub q_format: Option<bool>,
 }
+
+__into_owned! { LatentStyle { name; locked, priority, semi_hidden, unhiden_when_used, q_format } }
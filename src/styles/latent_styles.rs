@@ -2,6 +2,8 @@ use hard_xml::{XmlRead, XmlWrite};
 
 use super::latent_style::LatentStyle;
 
+use crate::__into_owned;
+
 /// Styles of the document
 ///
 /// Styles are predefined sets of properties which can be applied to text.
@@ -34,6 +36,13 @@ pub struct LatentStyles<'a> {
     pub styles: Vec<LatentStyle<'a>>,
 }
 
+__into_owned! {
+    LatentStyles {
+        styles;
+        locked_state, priority, semi_hidden, unhide_when_used, format, count
+    }
+}
+
 impl<'a> LatentStyles<'a> {
     pub fn push(&mut self, style: LatentStyle<'a>) -> &mut Self {
         self.styles.push(style);
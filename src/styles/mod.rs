@@ -2,22 +2,27 @@
 //!
 //! The corresponding ZIP item is `/word/styles.xml`.
 
+mod builtin;
 mod default_style;
 mod latent_style;
 mod latent_styles;
 mod priority;
+mod resolve;
 mod semi_hidden;
 mod style;
 mod unhidden_when_used;
+mod usage;
 
 use self::latent_styles::LatentStyles;
+pub(crate) use self::resolve::{locate_cell, overlay};
+pub(crate) use self::usage::StyleRemapper;
 pub use self::{default_style::*, style::*};
 
 use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::io::Write;
 
 use crate::schema::{SCHEMA_MAIN, SCHEMA_XML};
-use crate::{__xml_test_suites, write_attr};
+use crate::{__into_owned, __xml_test_suites, write_attr};
 
 /// Styles of the document
 ///
@@ -44,6 +49,8 @@ pub struct Styles<'a> {
     pub styles: Vec<Style<'a>>,
 }
 
+__into_owned! { Styles { default, latent_styles, styles } }
+
 impl<'a> XmlWrite for Styles<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let Styles {
@@ -0,0 +1,465 @@
+//! Resolution of the formatting a run ends up with
+//!
+//! Run properties are layered: the document defaults come first, then the table style, the
+//! paragraph style, the numbering level, the character style and finally the direct
+//! formatting of the run. Each later layer overrides the properties it specifies.
+//!
+//! Toggle properties such as bold and italics are special. Within one style and the styles it
+//! is based on they override as usual, but between the table, paragraph and character styles
+//! a property that is turned on flips the state inherited so far, so a bold character style
+//! applied inside a bold paragraph style gives text that is not bold. The defaults, the
+//! numbering level and the direct formatting always set the state outright.
+
+use crate::{
+    document::{BodyContent, Paragraph, Table, TableCell, TableCellContent, TableRowContent},
+    formatting::{CharacterProperty, ConditionType, Fonts, Lang, NumberingProperty},
+    styles::{Style, StyleType, Styles},
+};
+
+/// The order in which conditional table formatting is applied, later ones winning
+const CONDITION_ORDER: [ConditionType; 13] = [
+    ConditionType::WholeTable,
+    ConditionType::Band1Vert,
+    ConditionType::Band2Vert,
+    ConditionType::Band1Horz,
+    ConditionType::Band2Horz,
+    ConditionType::FirstCol,
+    ConditionType::LastCol,
+    ConditionType::FirstRow,
+    ConditionType::LastRow,
+    ConditionType::NeCell,
+    ConditionType::NwCell,
+    ConditionType::SeCell,
+    ConditionType::SwCell,
+];
+
+fn condition_rank(condition: &ConditionType) -> usize {
+    match condition {
+        ConditionType::WholeTable => 0,
+        ConditionType::Band1Vert => 1,
+        ConditionType::Band2Vert => 2,
+        ConditionType::Band1Horz => 3,
+        ConditionType::Band2Horz => 4,
+        ConditionType::FirstCol => 5,
+        ConditionType::LastCol => 6,
+        ConditionType::FirstRow => 7,
+        ConditionType::LastRow => 8,
+        ConditionType::NeCell => 9,
+        ConditionType::NwCell => 10,
+        ConditionType::SeCell => 11,
+        ConditionType::SwCell => 12,
+    }
+}
+
+/// Where a paragraph sits in the innermost table containing it
+pub(crate) struct CellPosition<'t, 'a> {
+    pub table: &'t Table<'a>,
+    pub row: usize,
+    pub column: usize,
+}
+
+impl<'t, 'a> CellPosition<'t, 'a> {
+    /// Returns which conditional formats of the table style apply, indexed by their rank
+    ///
+    /// A `w:cnfStyle` on the paragraph, as written by Word, is taken as is. Otherwise the
+    /// conditions are derived from the position of the cell with Word's default table look:
+    /// header row, first column and banded rows.
+    fn conditions(&self, para: &Paragraph) -> [bool; 13] {
+        let mut applies = [false; 13];
+        applies[condition_rank(&ConditionType::WholeTable)] = true;
+
+        let cnf_style = para
+            .property
+            .as_ref()
+            .and_then(|property| property.cnf_style.as_ref());
+        if let Some(cnf_style) = cnf_style {
+            // Bits in the order of the `w:cnfStyle` value
+            let bits = [
+                ConditionType::FirstRow,
+                ConditionType::LastRow,
+                ConditionType::FirstCol,
+                ConditionType::LastCol,
+                ConditionType::Band1Vert,
+                ConditionType::Band2Vert,
+                ConditionType::Band1Horz,
+                ConditionType::Band2Horz,
+                ConditionType::NwCell,
+                ConditionType::NeCell,
+                ConditionType::SwCell,
+                ConditionType::SeCell,
+            ];
+            for (bit, condition) in cnf_style.value.chars().zip(bits.iter()) {
+                if bit == '1' {
+                    applies[condition_rank(condition)] = true;
+                }
+            }
+            return applies;
+        }
+
+        let first_row = self.row == 0;
+        let first_column = self.column == 0;
+        applies[condition_rank(&ConditionType::FirstRow)] = first_row;
+        applies[condition_rank(&ConditionType::FirstCol)] = first_column;
+        applies[condition_rank(&ConditionType::NwCell)] = first_row && first_column;
+        if !first_row {
+            let band = if self.row % 2 == 1 {
+                ConditionType::Band1Horz
+            } else {
+                ConditionType::Band2Horz
+            };
+            applies[condition_rank(&band)] = true;
+        }
+        applies
+    }
+}
+
+/// Finds the innermost table cell holding the paragraph, comparing by address
+pub(crate) fn locate_cell<'t, 'a>(
+    blocks: &'t [BodyContent<'a>],
+    para: &Paragraph<'a>,
+) -> Option<CellPosition<'t, 'a>> {
+    blocks.iter().find_map(|block| match block {
+        BodyContent::Table(table) => locate_in_table(table, para),
+        BodyContent::Sdt(sdt) => locate_cell(&sdt.content.as_ref()?.content, para),
+        _ => None,
+    })
+}
+
+fn locate_in_table<'t, 'a>(
+    table: &'t Table<'a>,
+    para: &Paragraph<'a>,
+) -> Option<CellPosition<'t, 'a>> {
+    for (row, table_row) in table.rows.iter().enumerate() {
+        let cells: Vec<&TableCell> = table_row
+            .cells
+            .iter()
+            .flat_map(|content| match content {
+                TableRowContent::TableCell(cell) => vec![cell],
+                TableRowContent::SDT(sdt) => sdt
+                    .content
+                    .iter()
+                    .flat_map(|content| content.content.iter())
+                    .filter_map(|block| match block {
+                        BodyContent::TableCell(cell) => Some(cell),
+                        _ => None,
+                    })
+                    .collect(),
+            })
+            .collect();
+        for (column, cell) in cells.into_iter().enumerate() {
+            let position = CellPosition { table, row, column };
+            for content in cell.content.iter() {
+                match content {
+                    TableCellContent::Paragraph(p) if std::ptr::eq(p, para) => {
+                        return Some(position)
+                    }
+                    TableCellContent::Table(nested) => {
+                        if let Some(found) = locate_in_table(nested, para) {
+                            return Some(found);
+                        }
+                    }
+                    TableCellContent::SDT(sdt) => {
+                        let blocks = sdt.content.iter().flat_map(|c| c.content.iter());
+                        for block in blocks {
+                            match block {
+                                BodyContent::Paragraph(p) if std::ptr::eq(p, para) => {
+                                    return Some(position)
+                                }
+                                BodyContent::Table(nested) => {
+                                    if let Some(found) = locate_in_table(nested, para) {
+                                        return Some(found);
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    None
+}
+
+impl<'a> Styles<'a> {
+    /// Returns the style with the given id
+    pub fn get(&self, style_id: &str) -> Option<&Style<'a>> {
+        self.styles.iter().find(|style| style.style_id == style_id)
+    }
+
+    /// Returns the style applied to content of the given type that names no style itself
+    pub fn default_of(&self, ty: StyleType) -> Option<&Style<'a>> {
+        let ty = std::mem::discriminant(&ty);
+        self.styles.iter().find(|style| {
+            style.default == Some(true) && style.ty.as_ref().map(std::mem::discriminant) == Some(ty)
+        })
+    }
+
+    /// Returns the style and the styles it is based on, the outermost base first
+    pub(crate) fn chain(&self, style_id: &str) -> Vec<&Style<'a>> {
+        let mut chain = Vec::new();
+        let mut next = self.get(style_id);
+        while let Some(style) = next {
+            if chain
+                .iter()
+                .any(|seen: &&Style| seen.style_id == style.style_id)
+            {
+                break;
+            }
+            chain.push(style);
+            next = style.base.as_ref().and_then(|base| self.get(&base.value));
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// Collects the run properties of a style, following `w:basedOn`
+    pub(crate) fn style_run_properties(&self, style_id: &str) -> CharacterProperty<'a> {
+        let mut property = CharacterProperty::default();
+        for style in self.chain(style_id) {
+            if let Some(character) = &style.character {
+                overlay(&mut property, character, false);
+            }
+        }
+        property
+    }
+
+    /// Collects the run properties a table style gives to the paragraph at `position`
+    pub(crate) fn table_run_properties(
+        &self,
+        style_id: &str,
+        position: &CellPosition,
+        para: &Paragraph,
+    ) -> CharacterProperty<'a> {
+        let applies = position.conditions(para);
+        let chain = self.chain(style_id);
+        let mut property = CharacterProperty::default();
+        for condition in CONDITION_ORDER.iter() {
+            let rank = condition_rank(condition);
+            if !applies[rank] {
+                continue;
+            }
+            for style in chain.iter() {
+                if rank == 0 {
+                    if let Some(character) = &style.character {
+                        overlay(&mut property, character, false);
+                    }
+                }
+                let conditional = style
+                    .conditional_table_property
+                    .iter()
+                    .filter(|c| c.condition.as_ref().map(condition_rank) == Some(rank))
+                    .filter_map(|c| c.character.as_ref());
+                for character in conditional {
+                    overlay(&mut property, character, false);
+                }
+            }
+        }
+        property
+    }
+
+    /// Returns the numbering properties of a paragraph, looking through its style if needed
+    pub(crate) fn paragraph_numbering<'p>(
+        &'p self,
+        para: &'p Paragraph<'a>,
+        style_id: Option<&str>,
+    ) -> Option<&'p NumberingProperty<'a>> {
+        let direct = para
+            .property
+            .as_ref()
+            .and_then(|property| property.numbering.as_ref());
+        direct.or_else(|| {
+            self.chain(style_id?)
+                .into_iter()
+                .rev()
+                .find_map(|style| style.paragraph.as_ref()?.numbering.as_ref())
+        })
+    }
+}
+
+/// Applies the properties set in `source` on top of `target`
+///
+/// With `toggle`, toggle properties turned on in `source` flip their state in `target`
+/// instead of overriding it.
+pub(crate) fn overlay<'a>(
+    target: &mut CharacterProperty<'a>,
+    source: &CharacterProperty<'a>,
+    toggle: bool,
+) {
+    macro_rules! replace {
+        ($($field:ident),*) => {$(
+            if source.$field.is_some() {
+                target.$field = source.$field.clone();
+            }
+        )*};
+    }
+
+    macro_rules! toggle {
+        ($($field:ident),*) => {$(
+            if let Some(value) = &source.$field {
+                let on = value.value.unwrap_or(true);
+                let state = if toggle {
+                    let current = target.$field.as_ref().is_some_and(|t| t.value.unwrap_or(true));
+                    current != on
+                } else {
+                    on
+                };
+                let mut value = value.clone();
+                value.value = Some(state);
+                target.$field = Some(value);
+            }
+        )*};
+    }
+
+    replace!(
+        no_proof,
+        snap_to_grid,
+        web_hidden,
+        color,
+        spacing,
+        scale,
+        kern,
+        position,
+        size,
+        size_complex,
+        highlight,
+        underline,
+        effect,
+        border,
+        shading,
+        fit_text,
+        vertical_align,
+        rtl,
+        complex_script,
+        emphasis,
+        east_asian_layout,
+        spec_vanish,
+        o_math
+    );
+    toggle!(
+        bold,
+        bold_complex,
+        italics,
+        italics_complex,
+        caps,
+        small_caps,
+        strike,
+        dstrike,
+        outline,
+        shadow,
+        emboss,
+        imprint,
+        vanish
+    );
+
+    if let Some(fonts) = &source.fonts {
+        overlay_fonts(target.fonts.get_or_insert_with(Fonts::default), fonts);
+    }
+    if let Some(lang) = &source.lang {
+        overlay_lang(target.lang.get_or_insert_with(Lang::default), lang);
+    }
+}
+
+/// Overrides each script's font separately, a theme font replacing an explicit one
+fn overlay_fonts(target: &mut Fonts, source: &Fonts) {
+    macro_rules! pair {
+        ($($name:ident, $theme:ident);*) => {$(
+            if source.$name.is_some() || source.$theme.is_some() {
+                target.$name = source.$name.clone();
+                target.$theme = source.$theme.clone();
+            }
+        )*};
+    }
+
+    pair!(
+        ascii, ascii_theme;
+        east_asia, east_asia_theme;
+        h_ansi, h_ansi_theme;
+        custom, custom_theme
+    );
+    if source.hint.is_some() {
+        target.hint = source.hint.clone();
+    }
+}
+
+fn overlay_lang<'a>(target: &mut Lang<'a>, source: &Lang<'a>) {
+    if source.val.is_some() {
+        target.val = source.val.clone();
+    }
+    if source.east_asia.is_some() {
+        target.east_asia = source.east_asia.clone();
+    }
+    if source.bidi.is_some() {
+        target.bidi = source.bidi.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatting::{Bold, ThemeFont};
+    use crate::styles::BasedOn;
+
+    fn bold(value: Option<bool>) -> CharacterProperty<'static> {
+        CharacterProperty::default().bold(Bold { value })
+    }
+
+    fn is_bold(property: &CharacterProperty) -> bool {
+        property.bold.as_ref().and_then(|b| b.value) == Some(true)
+    }
+
+    #[test]
+    fn toggles_flip_between_style_levels() {
+        let mut property = CharacterProperty::default();
+        overlay(&mut property, &bold(None), true);
+        assert!(is_bold(&property));
+        overlay(&mut property, &bold(Some(true)), true);
+        assert!(!is_bold(&property));
+        overlay(&mut property, &bold(Some(false)), true);
+        assert!(!is_bold(&property));
+        overlay(&mut property, &bold(Some(true)), false);
+        assert!(is_bold(&property));
+        overlay(&mut property, &bold(Some(false)), false);
+        assert!(!is_bold(&property));
+    }
+
+    #[test]
+    fn fonts_merge_per_script() {
+        let mut property = CharacterProperty::default().fonts(
+            Fonts::default()
+                .ascii_theme(ThemeFont::MinorAscii)
+                .east_asia("SimSun"),
+        );
+        overlay(
+            &mut property,
+            &CharacterProperty::default().fonts(Fonts::default().ascii("Arial")),
+            true,
+        );
+        let fonts = property.fonts.unwrap();
+        assert_eq!(fonts.ascii.as_deref(), Some("Arial"));
+        assert!(fonts.ascii_theme.is_none());
+        assert_eq!(fonts.east_asia.as_deref(), Some("SimSun"));
+    }
+
+    #[test]
+    fn chain_follows_based_on() {
+        let mut styles = Styles::new();
+        let mut heading = Style::new(StyleType::Paragraph, "Heading1")
+            .character(CharacterProperty::default().size(32isize));
+        heading.base = Some(BasedOn {
+            value: "Normal".into(),
+        });
+        let mut normal = Style::new(StyleType::Paragraph, "Normal")
+            .character(CharacterProperty::default().size(22isize).color("FF0000"));
+        // a cycle must not hang the lookup
+        normal.base = Some(BasedOn {
+            value: "Heading1".into(),
+        });
+        styles.push(heading).push(normal);
+
+        let property = styles.style_run_properties("Heading1");
+        assert_eq!(property.size.unwrap().value, 32);
+        assert_eq!(property.color.unwrap().value, "FF0000");
+    }
+}
@@ -3,7 +3,7 @@ use hard_xml::{XmlRead, XmlWrite};
 use std::borrow::Cow;
 
 use crate::{
-    __setter, __string_enum, __xml_test_suites,
+    __into_owned, __setter, __string_enum, __xml_test_suites,
     formatting::{CharacterProperty, ParagraphProperty, TableProperty},
 };
 
@@ -87,13 +87,22 @@ pub struct Style<'a> {
     #[xml(default, child = "w:tblPr")]
     pub table: Option<TableProperty<'a>>,
     #[xml(child = "w:trPr")]
-    pub table_row: Option<crate::formatting::TableRowProperty>,
+    pub table_row: Option<crate::formatting::TableRowProperty<'a>>,
     #[xml(child = "w:tcPr")]
-    pub table_cell: Option<crate::formatting::TableCellProperty>,
+    pub table_cell: Option<crate::formatting::TableCellProperty<'a>>,
     #[xml(child = "w:tblStylePr")]
     pub conditional_table_property: Vec<crate::formatting::ConditionalTableProperty<'a>>,
 }
 
+__into_owned! {
+    Style {
+        style_id, name, aliases, base, next, link, rsid, paragraph, character, table, table_row,
+        table_cell, conditional_table_property;
+        ty, default, custom_style, auto_redefine, hidden, priority, semi_hidden, unhide_when_used,
+        q_format, locked, personal, personal_compose, personal_reply
+    }
+}
+
 impl<'a> Style<'a> {
     pub fn new<T: Into<Cow<'a, str>>>(ty: StyleType, style_id: T) -> Self {
         Style {
@@ -140,6 +149,8 @@ pub struct StyleName<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { StyleName { value } }
+
 impl<'a, S: Into<Cow<'a, str>>> From<S> for StyleName<'a> {
     fn from(val: S) -> Self {
         StyleName { value: val.into() }
@@ -154,6 +165,8 @@ pub struct Aliases<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { Aliases { value } }
+
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:next")]
@@ -162,6 +175,8 @@ pub struct Next<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { Next { value } }
+
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:link")]
@@ -170,6 +185,8 @@ pub struct Link<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { Link { value } }
+
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:autoRedefine")]
@@ -226,6 +243,8 @@ pub struct Rsid<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { Rsid { value } }
+
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:basedOn")]
@@ -234,6 +253,8 @@ pub struct BasedOn<'a> {
     pub value: Cow<'a, str>,
 }
 
+__into_owned! { BasedOn { value } }
+
 #[derive(Debug, XmlRead, XmlWrite, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:qFormat")]
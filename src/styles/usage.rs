@@ -0,0 +1,342 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::document::{Numbering, Paragraph, Run, Table, Visit, VisitMut};
+use crate::settings::Settings;
+use crate::styles::{Style, StyleType, Styles};
+use crate::Docx;
+
+/// Collects the ids of the styles applied to paragraphs, runs and tables
+#[derive(Default)]
+pub(crate) struct StyleUsage {
+    pub ids: HashSet<String>,
+}
+
+impl StyleUsage {
+    /// Collects the styles the settings and the numbering definitions of a document refer to
+    pub fn visit_definitions(&mut self, docx: &Docx) {
+        if let Some(settings) = &docx.settings {
+            let refs = [
+                settings
+                    .click_and_type_style
+                    .as_ref()
+                    .map(|style| &style.val),
+                settings
+                    .default_table_style
+                    .as_ref()
+                    .map(|style| &style.val),
+            ];
+            self.ids
+                .extend(refs.into_iter().flatten().map(|id| id.to_string()));
+        }
+        let levels = docx
+            .numbering
+            .iter()
+            .flat_map(|numbering| numbering.abstract_numberings.iter())
+            .flat_map(|abstract_num| abstract_num.levels.iter());
+        for level in levels {
+            if let Some(style_id) = &level.p_style {
+                self.ids.insert(style_id.value.to_string());
+            }
+            for mark in level.r_pr.iter() {
+                if let Some(style_id) = &mark.style_id {
+                    self.ids.insert(style_id.value.to_string());
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for StyleUsage {
+    fn visit_paragraph(&mut self, para: &Paragraph<'a>) {
+        if let Some(property) = &para.property {
+            if let Some(style_id) = &property.style_id {
+                self.ids.insert(style_id.value.to_string());
+            }
+            for mark in property.r_pr.iter() {
+                if let Some(style_id) = &mark.style_id {
+                    self.ids.insert(style_id.value.to_string());
+                }
+            }
+        }
+    }
+
+    fn visit_table(&mut self, table: &Table<'a>) {
+        if let Some(style_id) = &table.property.style_id {
+            self.ids.insert(style_id.value.to_string());
+        }
+    }
+
+    fn visit_run(&mut self, run: &Run<'a>) {
+        if let Some(style_id) = run.property.as_ref().and_then(|p| p.style_id.as_ref()) {
+            self.ids.insert(style_id.value.to_string());
+        }
+    }
+}
+
+/// Points every paragraph, run and table style reference at another style
+pub(crate) struct StyleRemapper<'s> {
+    pub old_id: &'s str,
+    pub new_id: &'s str,
+    pub count: usize,
+}
+
+impl<'s> StyleRemapper<'s> {
+    fn remap<'a>(&mut self, value: &mut Cow<'a, str>) {
+        if value == self.old_id {
+            *value = Cow::Owned(self.new_id.to_string());
+            self.count += 1;
+        }
+    }
+
+    /// Rewrites the references between style definitions
+    pub fn remap_styles(&mut self, styles: &mut Styles) {
+        for style in styles.styles.iter_mut() {
+            if let Some(base) = &mut style.base {
+                self.remap(&mut base.value);
+            }
+            if let Some(next) = &mut style.next {
+                self.remap(&mut next.value);
+            }
+            if let Some(link) = &mut style.link {
+                self.remap(&mut link.value);
+            }
+        }
+    }
+
+    /// Rewrites the styles of the settings
+    pub fn remap_settings(&mut self, settings: &mut Settings) {
+        if let Some(style) = &mut settings.click_and_type_style {
+            self.remap(&mut style.val);
+        }
+        if let Some(style) = &mut settings.default_table_style {
+            self.remap(&mut style.val);
+        }
+    }
+
+    /// Rewrites the styles of the levels of numbering definitions
+    pub fn remap_numbering(&mut self, numbering: &mut Numbering) {
+        let levels = numbering
+            .abstract_numberings
+            .iter_mut()
+            .flat_map(|abstract_num| abstract_num.levels.iter_mut());
+        for level in levels {
+            if let Some(style_id) = &mut level.p_style {
+                self.remap(&mut style_id.value);
+            }
+            for mark in level.r_pr.iter_mut() {
+                if let Some(style_id) = &mut mark.style_id {
+                    self.remap(&mut style_id.value);
+                }
+            }
+        }
+    }
+}
+
+impl<'a, 's> VisitMut<'a> for StyleRemapper<'s> {
+    fn visit_paragraph(&mut self, para: &mut Paragraph<'a>) {
+        if let Some(property) = &mut para.property {
+            if let Some(style_id) = &mut property.style_id {
+                self.remap(&mut style_id.value);
+            }
+            for mark in property.r_pr.iter_mut() {
+                if let Some(style_id) = &mut mark.style_id {
+                    self.remap(&mut style_id.value);
+                }
+            }
+        }
+    }
+
+    fn visit_table(&mut self, table: &mut Table<'a>) {
+        if let Some(style_id) = &mut table.property.style_id {
+            self.remap(&mut style_id.value);
+        }
+    }
+
+    fn visit_run(&mut self, run: &mut Run<'a>) {
+        if let Some(style_id) = run.property.as_mut().and_then(|p| p.style_id.as_mut()) {
+            self.remap(&mut style_id.value);
+        }
+    }
+}
+
+impl<'a> Styles<'a> {
+    /// Removes the styles which are not used by the content of the document
+    ///
+    /// A style is kept if a paragraph, run or table of the body, a header, a footer, a note
+    /// or a comment refers to it, if the settings or a numbering level refer to it, if it is
+    /// the default style of its type, or if it is a numbering style. The styles a kept style is based on, links to or names as the style
+    /// of the next paragraph are kept too. Returns the number of removed styles.
+    ///
+    /// ```rust
+    /// use docx_rust::document::Paragraph;
+    /// use docx_rust::formatting::ParagraphProperty;
+    /// use docx_rust::Docx;
+    ///
+    /// let mut docx = Docx::with_default_styles();
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .property(ParagraphProperty::default().style_id("Heading1"))
+    ///         .push_text("Title"),
+    /// );
+    ///
+    /// let mut styles = std::mem::take(&mut docx.styles);
+    /// styles.remove_unused(&docx);
+    /// docx.styles = styles;
+    ///
+    /// assert!(docx.styles.get("Heading1Char").is_some());
+    /// assert!(docx.styles.get("Heading2").is_none());
+    /// ```
+    pub fn remove_unused(&mut self, docx: &Docx) -> usize {
+        let mut usage = StyleUsage::default();
+        docx.walk_parts(&mut usage);
+        usage.visit_definitions(docx);
+
+        let mut pending: Vec<String> = usage.ids.into_iter().collect();
+        pending.extend(
+            self.styles
+                .iter()
+                .filter(|style| style.default == Some(true) || is_numbering(style))
+                .map(|style| style.style_id.to_string()),
+        );
+
+        let mut used = HashSet::new();
+        while let Some(id) = pending.pop() {
+            if used.contains(&id) {
+                continue;
+            }
+            if let Some(style) = self.get(&id) {
+                let refs = [
+                    style.base.as_ref().map(|base| &base.value),
+                    style.next.as_ref().map(|next| &next.value),
+                    style.link.as_ref().map(|link| &link.value),
+                ];
+                pending.extend(refs.into_iter().flatten().map(|id| id.to_string()));
+            }
+            used.insert(id);
+        }
+
+        let count = self.styles.len();
+        self.styles
+            .retain(|style| used.contains(style.style_id.as_ref()));
+        count - self.styles.len()
+    }
+}
+
+fn is_numbering(style: &Style) -> bool {
+    matches!(style.ty, Some(StyleType::Numbering))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{BodyContent, TableCell, TableRow};
+    use crate::formatting::{CharacterProperty, ParagraphProperty, TableProperty};
+
+    fn docx() -> Docx<'static> {
+        let mut docx = Docx::with_default_styles();
+        docx.document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().style_id("Title"))
+                .push(Run::default().property(CharacterProperty::default().style_id("Hyperlink"))),
+        );
+        docx.document.push(BodyContent::Table(
+            Table::default()
+                .property(TableProperty::default().style_id("TableGrid"))
+                .push_row(TableRow::default().push_cell(TableCell::paragraph(
+                    Paragraph::default().property(ParagraphProperty::default().style_id("TOC2")),
+                ))),
+        ));
+        docx
+    }
+
+    #[test]
+    fn remove_unused_keeps_referenced_closure() {
+        let docx = docx();
+        let mut styles = docx.styles.clone();
+        let removed = styles.remove_unused(&docx);
+
+        let mut ids: Vec<_> = styles.styles.iter().map(|s| &*s.style_id).collect();
+        ids.sort_unstable();
+        assert_eq!(
+            ids,
+            [
+                "DefaultParagraphFont",
+                "Hyperlink",
+                "NoList",
+                "Normal",
+                "TOC2",
+                "TableGrid",
+                "TableNormal",
+                "Title",
+                "TitleChar",
+            ]
+        );
+        assert_eq!(removed, docx.styles.styles.len() - ids.len());
+    }
+
+    #[test]
+    fn remap_rewrites_references() {
+        let mut docx = docx();
+        assert_eq!(docx.remap_style("TableGrid", "Grid"), 1);
+        assert_eq!(docx.remap_style("Hyperlink", "Link"), 1);
+        assert_eq!(docx.remap_style("Title", "TOC2"), 2);
+
+        let mut usage = StyleUsage::default();
+        docx.walk_parts(&mut usage);
+        let mut ids: Vec<_> = usage.ids.into_iter().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["Grid", "Link", "TOC2"]);
+
+        assert!(docx.styles.get("Title").is_none());
+        assert!(docx.styles.get("Grid").is_some());
+        let link = docx.styles.get("TitleChar").unwrap().link.as_ref();
+        assert_eq!(link.unwrap().value, "TOC2");
+    }
+
+    #[test]
+    fn settings_and_numbering_refer_to_styles() {
+        use crate::document::{AbstractNum, Level};
+        use crate::formatting::ParagraphStyleId;
+        use crate::settings::{ClickAndTypeStyle, DefaultTableStyle};
+
+        let mut docx = Docx::with_default_styles();
+        docx.settings = Some(Settings {
+            click_and_type_style: Some(ClickAndTypeStyle {
+                val: "Caption".into(),
+            }),
+            default_table_style: Some(DefaultTableStyle {
+                val: "TableGrid".into(),
+            }),
+            ..Default::default()
+        });
+        docx.numbering = Some(Numbering {
+            abstract_numberings: vec![AbstractNum {
+                levels: vec![Level {
+                    p_style: Some(ParagraphStyleId::from("ListParagraph")),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        let mut styles = docx.styles.clone();
+        styles.remove_unused(&docx);
+        assert!(styles.get("Caption").is_some());
+        assert!(styles.get("TableGrid").is_some());
+        assert!(styles.get("ListParagraph").is_some());
+
+        assert_eq!(docx.remap_style("Caption", "Heading3"), 1);
+        assert_eq!(docx.remap_style("TableGrid", "Grid"), 1);
+        assert_eq!(docx.remap_style("ListParagraph", "List"), 1);
+        let settings = docx.settings.as_ref().unwrap();
+        assert_eq!(
+            settings.click_and_type_style.as_ref().unwrap().val,
+            "Heading3"
+        );
+        assert_eq!(settings.default_table_style.as_ref().unwrap().val, "Grid");
+        let level = &docx.numbering.as_ref().unwrap().abstract_numberings[0].levels[0];
+        assert_eq!(level.p_style.as_ref().unwrap().value, "List");
+    }
+}
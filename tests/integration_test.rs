@@ -199,3 +199,2496 @@ async fn read_write_async() {
     let writer = Vec::new();
     let _ = docx.write_async(writer.compat_write()).await.unwrap();
 }
+
+#[test]
+fn round_trip_keeps_unknown_parts() {
+    use std::io::Cursor;
+
+    let path = std::path::Path::new("./tests/pandoc/links.docx");
+    let book = DocxFile::from_file(path).unwrap();
+    let mut docx = book.parse().unwrap();
+    assert!(docx
+        .opaque_parts
+        .contains_key("word/_rels/footnotes.xml.rels"));
+
+    docx.opaque_parts
+        .insert("word/people.xml".into(), b"<w15:people/>".as_slice().into());
+
+    let mut buffer = Vec::new();
+    docx.write(Cursor::new(&mut buffer)).unwrap();
+
+    let book = DocxFile::from_reader(Cursor::new(&buffer)).unwrap();
+    let docx = book.parse().unwrap();
+    assert_eq!(
+        b"<w15:people/>".as_slice(),
+        &*docx.opaque_parts["word/people.xml"]
+    );
+    assert!(docx
+        .opaque_parts
+        .contains_key("word/_rels/footnotes.xml.rels"));
+}
+
+#[test]
+fn round_trip_track_changes() {
+    use docx_rust::Docx;
+    use std::io::Cursor;
+
+    fn revisions(docx: &Docx) -> Vec<(&'static str, String, String)> {
+        docx.document
+            .body
+            .content
+            .iter()
+            .filter_map(|c| match c {
+                BodyContent::Paragraph(p) => Some(p),
+                _ => None,
+            })
+            .flat_map(|p| p.content.iter())
+            .filter_map(|c| match c {
+                ParagraphContent::Insertion(ins) => {
+                    Some(("ins", ins.author.to_string(), ins.text()))
+                }
+                ParagraphContent::Deletion(del) => {
+                    Some(("del", del.author.to_string(), del.text()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    for name in ["deletion", "insertion", "move", "scrubbed_metadata"] {
+        let path = format!("./tests/pandoc/track_changes_{}.docx", name);
+        let book = DocxFile::from_file(path).unwrap();
+        let mut docx = book.parse().unwrap();
+        let before = revisions(&docx);
+        assert!(!before.is_empty(), "{} has no revisions", name);
+
+        let mut buffer = Vec::new();
+        docx.write(Cursor::new(&mut buffer)).unwrap();
+
+        let book = DocxFile::from_reader(Cursor::new(&buffer)).unwrap();
+        let docx = book.parse().unwrap();
+        assert_eq!(before, revisions(&docx));
+    }
+
+    let book = DocxFile::from_file("./tests/pandoc/track_changes_insertion.docx").unwrap();
+    let docx = book.parse().unwrap();
+    assert_eq!(
+        "This is a text with two exciting insertions.",
+        docx.document.body.text()
+    );
+}
+
+#[test]
+fn accept_and_reject_revisions() {
+    use docx_rust::document::{
+        BookmarkStart, DelInstrText, DelText, Deletion, DeletionMark, Hyperlink, Insertion,
+        InsertionMark, Paragraph, RevisionFilter, Run, SDTContent, SimpleField, Table, TableCell,
+        TableCellContent, TableRow, TableRowContent, SDT,
+    };
+    use docx_rust::formatting::{
+        CharacterProperty, ParagraphProperty, PreviousCharacterProperty, RevisionCharacterProperty,
+        TableRowProperty,
+    };
+    use docx_rust::Docx;
+
+    fn has_revisions(docx: &Docx) -> bool {
+        docx.document.body.content.iter().any(|c| match c {
+            BodyContent::Paragraph(p) => p.content.iter().any(|c| {
+                matches!(
+                    c,
+                    ParagraphContent::Insertion(_) | ParagraphContent::Deletion(_)
+                )
+            }),
+            _ => false,
+        })
+    }
+
+    let book = DocxFile::from_file("./tests/pandoc/track_changes_deletion.docx").unwrap();
+
+    let mut docx = book.parse().unwrap();
+    docx.accept_revisions(&RevisionFilter::default().author("someone-else"));
+    docx.reject_revisions(&RevisionFilter::default().since("2015-01-01"));
+    assert!(has_revisions(&docx));
+
+    docx.accept_all_revisions();
+    assert!(!has_revisions(&docx));
+    assert_eq!("This is a text with a deletion.", docx.document.body.text());
+
+    let mut docx = book.parse().unwrap();
+    docx.reject_revisions(&RevisionFilter::default().author("eng-dept").until("2015"));
+    assert!(!has_revisions(&docx));
+    assert_eq!(
+        "This is a text with an excessively modified deletion.",
+        docx.document.body.text()
+    );
+
+    let book = DocxFile::from_file("./tests/pandoc/track_changes_insertion.docx").unwrap();
+
+    let mut docx = book.parse().unwrap();
+    docx.reject_all_revisions();
+    assert!(!has_revisions(&docx));
+    assert_eq!(
+        "This is a text with  insertions.",
+        docx.document.body.text()
+    );
+
+    // a deleted paragraph mark joins the paragraph with the next one
+    let mut docx = Docx::default();
+    let deleted_mark = CharacterProperty {
+        deleted: Some(DeletionMark {
+            author: "John".into(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let bold_change = CharacterProperty {
+        r_pr_change: Some(RevisionCharacterProperty {
+            author: "John".into(),
+            previous_property: Some(PreviousCharacterProperty::default()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+    .bold(true);
+    docx.document.push(
+        Paragraph::default()
+            .property(ParagraphProperty {
+                r_pr: vec![deleted_mark],
+                ..Default::default()
+            })
+            .push_text("first "),
+    );
+    docx.document.push(
+        Paragraph::default()
+            .property(ParagraphProperty::default().style_id("Heading1"))
+            .push(Run::default().property(bold_change).push_text("second")),
+    );
+
+    let mut accepted = docx.clone();
+    accepted.accept_all_revisions();
+    assert_eq!("first second", accepted.document.body.text());
+    match &accepted.document.body.content[..] {
+        [BodyContent::Paragraph(p)] => {
+            assert_eq!(
+                p.property
+                    .as_ref()
+                    .unwrap()
+                    .style_id
+                    .as_ref()
+                    .unwrap()
+                    .value,
+                "Heading1"
+            );
+            match &p.content[1] {
+                ParagraphContent::Run(run) => {
+                    let prop = run.property.as_ref().unwrap();
+                    assert!(prop.bold.is_some() && prop.r_pr_change.is_none());
+                }
+                _ => panic!("expected a run"),
+            }
+        }
+        content => panic!("expected a single paragraph, got {:?}", content),
+    }
+
+    docx.reject_all_revisions();
+    assert_eq!("first \r\nsecond", docx.document.body.text());
+    match &docx.document.body.content[1] {
+        BodyContent::Paragraph(p) => match &p.content[0] {
+            ParagraphContent::Run(run) => {
+                let prop = run.property.as_ref().unwrap();
+                assert!(prop.bold.is_none() && prop.r_pr_change.is_none());
+            }
+            _ => panic!("expected a run"),
+        },
+        _ => panic!("expected a paragraph"),
+    }
+
+    // body-level revisions keep their hyperlinks, bookmarks and deleted field codes
+    let mut docx = Docx::default();
+    docx.document.push(
+        Insertion::default()
+            .author("John")
+            .push(BookmarkStart {
+                id: Some("0".into()),
+                name: Some("top".into()),
+            })
+            .push(Hyperlink::default().anchor("top").add_text("link")),
+    );
+    docx.document.push(
+        Deletion::default()
+            .author("John")
+            .push(Run::default().push(DelInstrText::from(" PAGE ")))
+            .push(
+                SimpleField::default()
+                    .instr("DATE")
+                    .push(Run::default().push(DelText::from("today"))),
+            ),
+    );
+    let mut accepted = docx.clone();
+    accepted.accept_all_revisions();
+    match &accepted.document.body.content[..] {
+        [BodyContent::Paragraph(p)] => {
+            assert!(matches!(p.content[0], ParagraphContent::BookmarkStart(_)));
+            assert_eq!(p.text(), "link");
+        }
+        content => panic!("expected a single paragraph, got {:?}", content),
+    }
+
+    docx.reject_all_revisions();
+    match &docx.document.body.content[..] {
+        [BodyContent::Paragraph(p)] => {
+            let ParagraphContent::Run(run) = &p.content[0] else {
+                panic!("expected a run")
+            };
+            assert!(
+                matches!(&run.content[0], RunContent::InstrText(instr) if instr.text == " PAGE ")
+            );
+            assert_eq!(p.text(), " PAGE today");
+        }
+        content => panic!("expected a single paragraph, got {:?}", content),
+    }
+
+    // tables whose rows are all removed are removed too, in the body, cells and content controls
+    let inserted_table = || {
+        Table::default().push_row(
+            TableRow::default()
+                .property(TableRowProperty::default().inserted(InsertionMark {
+                    author: "John".into(),
+                    ..Default::default()
+                }))
+                .push_cell(TableCell::paragraph(Paragraph::default().push_text("new"))),
+        )
+    };
+    let mut docx = Docx::default();
+    docx.document.push(inserted_table());
+    docx.document.push(Table::default().push_row(
+        TableRow::default().push_cell(
+            TableCell::from(inserted_table()).push(Paragraph::default().push_text("old")),
+        ),
+    ));
+    docx.document.push(SDT {
+        content: Some(SDTContent {
+            content: vec![
+                inserted_table().into(),
+                Paragraph::default().push_text("kept").into(),
+            ],
+        }),
+        ..Default::default()
+    });
+    docx.reject_all_revisions();
+    match &docx.document.body.content[..] {
+        [BodyContent::Table(table), BodyContent::Sdt(sdt)] => {
+            let TableRowContent::TableCell(cell) = &table.rows[0].cells[0] else {
+                panic!("expected a cell")
+            };
+            assert!(matches!(
+                &cell.content[..],
+                [TableCellContent::Paragraph(_)]
+            ));
+            let content = &sdt.content.as_ref().unwrap().content;
+            assert!(matches!(&content[..], [BodyContent::Paragraph(_)]));
+        }
+        content => panic!("expected a table and a content control, got {:?}", content),
+    }
+}
+
+#[test]
+fn replace_text_tracked() {
+    use docx_rust::document::{
+        Header, HeaderFooterReferenceType, Hyperlink, HyperlinkContent, Insertion, Paragraph, Run,
+        SDTContent, Table, TableCell, TableRow, TrackChanges, SDT,
+    };
+    use docx_rust::formatting::CharacterProperty;
+    use docx_rust::Docx;
+
+    let mut docx = Docx::default();
+    docx.document.push(
+        Paragraph::default().push(
+            Run::default()
+                .property(CharacterProperty::default().bold(true))
+                .push_text("Dear NAME, welcome to TEAM."),
+        ),
+    );
+
+    let mut track = TrackChanges::new("Bot")
+        .date("2024-01-01T00:00:00Z")
+        .next_id(10isize);
+    docx.document
+        .body
+        .replace_text_tracked([("NAME", "Jane"), ("TEAM", "")], &mut track)
+        .unwrap();
+    assert_eq!(13, track.next_id);
+
+    let para = match &docx.document.body.content[0] {
+        BodyContent::Paragraph(p) => p,
+        _ => panic!("expected a paragraph"),
+    };
+    let kinds: Vec<_> = para
+        .content
+        .iter()
+        .map(|c| match c {
+            ParagraphContent::Run(run) => {
+                assert!(run.property.as_ref().unwrap().bold.is_some());
+                format!("run:{}", run.text())
+            }
+            ParagraphContent::Insertion(ins) => {
+                assert_eq!("Bot", ins.author);
+                format!("ins{}:{}", ins.id, ins.text())
+            }
+            ParagraphContent::Deletion(del) => {
+                assert_eq!(Some("2024-01-01T00:00:00Z"), del.date.as_deref());
+                let deleted: String = del
+                    .content
+                    .iter()
+                    .flat_map(|c| match c {
+                        ParagraphContent::Run(run) => run.content.iter(),
+                        _ => [].iter(),
+                    })
+                    .filter_map(|c| match c {
+                        RunContent::DelText(t) => Some(t.text.as_ref()),
+                        _ => None,
+                    })
+                    .collect();
+                format!("del{}:{}", del.id, deleted)
+            }
+            _ => panic!("unexpected content"),
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            "run:Dear ",
+            "del10:NAME",
+            "ins11:Jane",
+            "run:, welcome to ",
+            "del12:TEAM",
+            "run:."
+        ],
+        kinds
+    );
+
+    let mut accepted = docx.clone();
+    accepted.accept_all_revisions();
+    assert_eq!("Dear Jane, welcome to .", accepted.document.body.text());
+
+    docx.reject_all_revisions();
+    assert_eq!("Dear NAME, welcome to TEAM.", docx.document.body.text());
+
+    // hyperlinks, insertions, content controls in cells, headers and footers are covered too
+    let mut docx = Docx::default();
+    docx.document.push(
+        Paragraph::default()
+            .push(Hyperlink::default().anchor("team").add_text("NAME's page"))
+            .push(Insertion::default().push(Run::default().push_text(" of TEAM"))),
+    );
+    let sdt = SDT {
+        content: Some(SDTContent {
+            content: vec![Paragraph::default().push_text("NAME").into()],
+        }),
+        ..Default::default()
+    };
+    let cell = TableCell::from(sdt);
+    docx.document
+        .push(Table::default().push_row(TableRow::default().push_cell(cell)));
+    let mut header = Header::default();
+    header.push(Paragraph::default().push_text("TEAM"));
+    docx.add_header(HeaderFooterReferenceType::Default, header);
+
+    let mut track = TrackChanges::new("Bot");
+    docx.replace_text_tracked([("NAME", "Jane"), ("TEAM", "Crew")], &mut track)
+        .unwrap();
+    assert_eq!(8, track.next_id);
+
+    let BodyContent::Paragraph(para) = &docx.document.body.content[0] else {
+        panic!("expected a paragraph")
+    };
+    let links: Vec<_> = para
+        .content
+        .iter()
+        .filter_map(|c| match c {
+            ParagraphContent::Link(link) => Some(link),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(1, links.len());
+    assert_eq!(Some("team"), links[0].anchor.as_deref());
+    let kinds: Vec<_> = links[0]
+        .content
+        .iter()
+        .map(|c| match c {
+            HyperlinkContent::Run(run) => format!("run:{}", run.text()),
+            HyperlinkContent::Insertion(ins) => format!("ins:{}", ins.text()),
+            HyperlinkContent::Deletion(_) => "del".to_string(),
+        })
+        .collect();
+    assert_eq!(vec!["del", "ins:Jane", "run:'s page"], kinds);
+
+    let mut accepted = docx.clone();
+    accepted.accept_all_revisions();
+    let table_text = |docx: &Docx| match &docx.document.body.content[1] {
+        BodyContent::Table(table) => table.iter_text().map(|t| t.to_string()).collect::<String>(),
+        _ => panic!("expected a table"),
+    };
+    assert_eq!("Jane's page of Crew", accepted.document.body.text());
+    assert_eq!("Jane", table_text(&accepted));
+    let header = accepted.headers.values().next().unwrap();
+    let BodyContent::Paragraph(para) = &header.content[0] else {
+        panic!("expected a paragraph")
+    };
+    assert_eq!("Crew", para.text());
+
+    docx.reject_all_revisions();
+    assert_eq!("NAME's page", docx.document.body.text());
+    assert_eq!("NAME", table_text(&docx));
+}
+
+#[test]
+fn replace_text_across_runs() {
+    use docx_rust::document::{Hyperlink, Paragraph, Run};
+    use docx_rust::formatting::CharacterProperty;
+
+    let mut para = Paragraph::default()
+        .push_text("Dear {{cust")
+        .push(
+            Run::default()
+                .property(CharacterProperty::default().bold(true))
+                .push_text("omer_"),
+        )
+        .push_text("name}}, see ")
+        .push(Hyperlink::default().add_text("{{li").add_text("nk}}"))
+        .push_text(".");
+
+    assert_eq!(vec![5..22], para.find_text("{{customer_name}}"));
+    assert_eq!(vec![28..36], para.find_text("{{link}}"));
+    assert!(para.find_text("{{missing}}").is_empty());
+
+    let matches =
+        para.replace_text_across_runs([("{{customer_name}}", "Jane Doe"), ("{{link}}", "here")]);
+    assert_eq!(vec![5..22, 28..36], matches);
+    assert_eq!("Dear Jane Doe, see here.", para.text());
+
+    // the bold run was emptied and the second run of the link too
+    let shape: Vec<_> = para
+        .content
+        .iter()
+        .map(|c| match c {
+            ParagraphContent::Run(run) => run.text(),
+            ParagraphContent::Link(link) => format!("<{}>", link.text()),
+            _ => panic!("unexpected content"),
+        })
+        .collect();
+    assert_eq!(vec!["Dear Jane Doe", ", see ", "<here>", "."], shape);
+}
+
+#[test]
+fn text_skips_deleted_content() {
+    use docx_rust::document::{Deletion, Insertion, MoveFrom, MoveTo, Paragraph, Run};
+    use docx_rust::Docx;
+
+    let mut docx = Docx::default();
+    docx.document.push(
+        Paragraph::default()
+            .push_text("one ")
+            .push(Deletion::default().push(Run::default().push_text("old ")))
+            .push(Insertion::default().push(Run::default().push_text("new ")))
+            .push(MoveFrom::default().push(Run::default().push_text("there ")))
+            .push(MoveTo::default().push(Run::default().push_text("here"))),
+    );
+    docx.document
+        .push(Insertion::default().push(Run::default().push_text("old added")));
+    docx.document
+        .push(Deletion::default().push(Run::default().push_text("old removed")));
+
+    let body = &mut docx.document.body;
+    assert_eq!("one new here\r\nold added", body.text());
+
+    body.replace_text_simple("old", "fresh");
+    assert_eq!("one new here\r\nfresh added", body.text());
+    let BodyContent::Paragraph(para) = &body.content[0] else {
+        unreachable!()
+    };
+    let ParagraphContent::Deletion(del) = &para.content[1] else {
+        unreachable!()
+    };
+    assert_eq!("old ", del.text());
+    let BodyContent::Deletion(del) = &body.content[2] else {
+        unreachable!()
+    };
+    assert_eq!("old removed", del.text());
+}
+
+#[test]
+fn table_logical_grid() {
+    use docx_rust::document::{Paragraph, Table, TableCell, TableRow};
+    use docx_rust::formatting::{
+        GridBefore, MergeType, TableCellProperty, TableRowProperty, VMerge,
+    };
+
+    fn cell<'a>(text: &'a str, prop: TableCellProperty<'a>) -> TableCell<'a> {
+        TableCell::paragraph(Paragraph::default().push_text(text)).property(prop)
+    }
+
+    let table = Table::default()
+        .push_row(
+            TableRow::default()
+                .push_cell(cell(
+                    "A",
+                    TableCellProperty::default()
+                        .grid_span(2isize)
+                        .v_merge(MergeType::Restart),
+                ))
+                .push_cell(cell("B", TableCellProperty::default())),
+        )
+        .push_row(
+            TableRow::default()
+                .push_cell(cell(
+                    "",
+                    TableCellProperty::default()
+                        .grid_span(2isize)
+                        .v_merge(VMerge::default()),
+                ))
+                .push_cell(cell("C", TableCellProperty::default())),
+        )
+        .push_row(
+            TableRow::default()
+                .push_cell(cell("D", TableCellProperty::default()))
+                .push_cell(cell(
+                    "E",
+                    TableCellProperty::default().h_merge(MergeType::Restart),
+                ))
+                .push_cell(cell(
+                    "",
+                    TableCellProperty::default().h_merge(MergeType::Continue),
+                )),
+        )
+        .push_row(
+            TableRow::default()
+                .property(TableRowProperty::default().grid_before(GridBefore::from(1)))
+                .push_cell(cell("F", TableCellProperty::default()))
+                .push_cell(cell("G", TableCellProperty::default())),
+        );
+
+    let grid: Vec<Vec<_>> = table
+        .logical_grid()
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|c| {
+                    let text: String = c.cell.iter_text().map(|t| t.to_string()).collect();
+                    (text, c.row, c.column, c.row_span, c.column_span)
+                })
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            vec![("A".to_string(), 0, 0, 2, 2), ("B".to_string(), 0, 2, 1, 1)],
+            vec![("C".to_string(), 1, 2, 1, 1)],
+            vec![("D".to_string(), 2, 0, 1, 1), ("E".to_string(), 2, 1, 1, 2)],
+            vec![("F".to_string(), 3, 1, 1, 1), ("G".to_string(), 3, 2, 1, 1)],
+        ],
+        grid
+    );
+}
+
+#[test]
+fn nested_table_in_cell() {
+    use docx_rust::document::{Table, TableCell, TableCellContent};
+    use hard_xml::{XmlRead, XmlWrite};
+
+    let xml = r#"<w:tc><w:tcPr><w:vAlign w:val="top"/></w:tcPr><w:p><w:r><w:t>outer</w:t></w:r></w:p><w:bookmarkStart w:id="0" w:name="cell"/><w:tbl><w:tblPr/><w:tblGrid/><w:tr><w:trPr/><w:tc><w:tcPr><w:vAlign w:val="top"/></w:tcPr><w:p><w:r><w:t>inner {{x}}</w:t></w:r></w:p></w:tc></w:tr></w:tbl><w:bookmarkEnd w:id="0"/><w:sdt><w:sdtContent><w:p><w:r><w:t>control {{x}}</w:t></w:r></w:p></w:sdtContent></w:sdt><w:p/></w:tc>"#;
+    let mut cell = TableCell::from_str(xml).unwrap();
+    assert_eq!(xml, cell.to_string().unwrap());
+    assert!(matches!(cell.content[2], TableCellContent::Table(_)));
+
+    let text: Vec<_> = cell.iter_text().map(|t| t.to_string()).collect();
+    assert_eq!(vec!["outer", "inner {{x}}", "control {{x}}"], text);
+
+    cell.replace_text([("{{x}}", "42")]).unwrap();
+    let text: Vec<_> = cell.iter_text().map(|t| t.to_string()).collect();
+    assert_eq!(vec!["outer", "inner 42", "control 42"], text);
+
+    let table = Table::default().push_row(docx_rust::document::TableRow::default().push_cell(cell));
+    assert_eq!(3, table.iter_text().count());
+}
+
+#[test]
+fn detect_image_dimensions() {
+    use docx_rust::media::{ImageFormat, ImageInfo};
+
+    // 3x2 PNG at 5669 pixels per metre (144 dpi)
+    let mut png =
+        b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x03\0\0\0\x02\x08\x02\0\0\0\0\0\0\0".to_vec();
+    png.extend(b"\0\0\0\x09pHYs\0\0\x16\x25\0\0\x16\x25\x01\0\0\0\0");
+    let info = ImageInfo::from_bytes(&png).unwrap();
+    assert_eq!(
+        (ImageFormat::Png, 3, 2, 144, 144),
+        (info.format, info.width, info.height, info.dpi_x, info.dpi_y)
+    );
+    assert_eq!((19050, 12700), info.extent());
+
+    // JFIF at 300 dpi followed by a baseline frame of 640x480
+    let jpeg = b"\xff\xd8\xff\xe0\x00\x10JFIF\x00\x01\x01\x01\x01\x2c\x01\x2c\x00\x00\xff\xc0\x00\x0b\x08\x01\xe0\x02\x80\x01\x01\x11\x00";
+    let info = ImageInfo::from_bytes(jpeg).unwrap();
+    assert_eq!(
+        (ImageFormat::Jpeg, 640, 480, 300, 300),
+        (info.format, info.width, info.height, info.dpi_x, info.dpi_y)
+    );
+
+    // top-down BMP of 4x5 at 3780 pixels per metre (96 dpi)
+    let mut bmp = b"BM".to_vec();
+    bmp.extend([0; 12]);
+    bmp.extend(40i32.to_le_bytes());
+    bmp.extend(4i32.to_le_bytes());
+    bmp.extend((-5i32).to_le_bytes());
+    bmp.extend([0; 12]);
+    bmp.extend(3780i32.to_le_bytes());
+    bmp.extend(3780i32.to_le_bytes());
+    let info = ImageInfo::from_bytes(&bmp).unwrap();
+    assert_eq!(
+        (ImageFormat::Bmp, 4, 5, 96, 96),
+        (info.format, info.width, info.height, info.dpi_x, info.dpi_y)
+    );
+
+    // big-endian TIFF of 7x9 at 72 dpi
+    let mut tiff = b"MM\0*\0\0\0\x08\0\x04".to_vec();
+    tiff.extend(b"\x01\x00\0\x03\0\0\0\x01\0\x07\0\0");
+    tiff.extend(b"\x01\x01\0\x04\0\0\0\x01\0\0\0\x09");
+    tiff.extend(b"\x01\x1a\0\x05\0\0\0\x01\0\0\0\x3a");
+    tiff.extend(b"\x01\x1b\0\x05\0\0\0\x01\0\0\0\x3a");
+    tiff.extend(b"\0\0\0\x48\0\0\0\x01");
+    let info = ImageInfo::from_bytes(&tiff).unwrap();
+    assert_eq!(
+        (ImageFormat::Tiff, 7, 9, 72, 72),
+        (info.format, info.width, info.height, info.dpi_x, info.dpi_y)
+    );
+
+    assert!(ImageInfo::from_bytes(b"not an image").is_none());
+}
+
+#[test]
+fn add_image() {
+    use docx_rust::document::{Drawing, Paragraph, Run};
+    use docx_rust::Docx;
+
+    fn drawing<'r>(run: &'r Run) -> &'r Drawing<'r> {
+        match &run.content[0] {
+            RunContent::Drawing(drawing) => drawing,
+            _ => panic!("expected a drawing"),
+        }
+    }
+
+    let gif = b"GIF89a\x20\x00\x10\x00\x00\x00\x00".to_vec();
+    let mut docx = Docx::default();
+
+    let natural = docx.add_image(gif.clone(), None, None).unwrap();
+    let inline = drawing(&natural).inline.as_ref().unwrap();
+    assert_eq!(1, inline.doc_property.id.unwrap());
+    assert_eq!(
+        (304800, 152400),
+        (
+            inline.extent.as_ref().unwrap().cx,
+            inline.extent.as_ref().unwrap().cy
+        )
+    );
+    let rel_id = inline.graphic.as_ref().unwrap().data.children[0]
+        .fill
+        .blip
+        .embed
+        .to_string();
+    assert_eq!(
+        Some("media/image1.gif"),
+        docx.document_rels.as_ref().unwrap().get_target(&rel_id)
+    );
+    docx.document.push(Paragraph::default().push(natural));
+
+    // the width is given, the height keeps the aspect ratio
+    let scaled = docx.add_image(gif, Some(914400), None).unwrap();
+    let inline = drawing(&scaled).inline.as_ref().unwrap();
+    assert_eq!(2, inline.doc_property.id.unwrap());
+    assert_eq!(457200, inline.extent.as_ref().unwrap().cy);
+    docx.document.push(Paragraph::default().push(scaled));
+
+    assert!(docx.media.contains_key("media/image2.gif"));
+    assert_eq!(
+        1,
+        docx.content_types
+            .defaults
+            .iter()
+            .filter(|default| default.ext == "gif" && default.ty == "image/gif")
+            .count()
+    );
+    assert!(docx
+        .add_image(b"not an image".to_vec(), None, None)
+        .is_err());
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    docx.write(&mut buf).unwrap();
+
+    let file = DocxFile::from_reader(std::io::Cursor::new(buf.into_inner())).unwrap();
+    let docx = file.parse().unwrap();
+    assert_eq!(2, docx.media.len());
+    assert_eq!(2, docx.document.body.content.len());
+}
+
+#[test]
+fn detect_vector_image_dimensions() {
+    use docx_rust::media::{ImageFormat, ImageInfo};
+
+    // EMF whose frame is 50.8 x 25.4 millimetres
+    let mut emf = 1u32.to_le_bytes().to_vec();
+    emf.extend([0; 20]);
+    for v in [0i32, 0, 5080, 2540] {
+        emf.extend(v.to_le_bytes());
+    }
+    emf.extend(b" EMF");
+    let info = ImageInfo::from_bytes(&emf).unwrap();
+    assert_eq!(ImageFormat::Emf, info.format);
+    assert_eq!((1828800, 914400), info.extent());
+
+    // placeable WMF of 1440 x 720 units at 1440 units per inch
+    let mut wmf = vec![0xD7, 0xCD, 0xC6, 0x9A, 0, 0];
+    for v in [0u16, 0, 1440, 720, 1440] {
+        wmf.extend(v.to_le_bytes());
+    }
+    let info = ImageInfo::from_bytes(&wmf).unwrap();
+    assert_eq!(ImageFormat::Wmf, info.format);
+    assert_eq!((914400, 457200), info.extent());
+
+    let svg = br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg" stroke-width="3" width="1in" height="48"/>"#;
+    let info = ImageInfo::from_bytes(svg).unwrap();
+    assert_eq!(
+        (ImageFormat::Svg, 96, 48),
+        (info.format, info.width, info.height)
+    );
+
+    let svg = br#"<svg viewBox="0 0 192 96"></svg>"#;
+    let info = ImageInfo::from_bytes(svg).unwrap();
+    assert_eq!((1828800, 914400), info.extent());
+
+    // unquoted values are not valid XML
+    let svg = "<svg width=\u{e9} viewBox='0 0 10 20'>".as_bytes();
+    let info = ImageInfo::from_bytes(svg).unwrap();
+    assert_eq!((10, 20), (info.width, info.height));
+    assert!(ImageInfo::from_bytes(b"<svg width=\xc3\xa9>").is_none());
+}
+
+#[test]
+fn add_svg_image_and_media() {
+    use docx_rust::document::Paragraph;
+    use docx_rust::media::MediaType;
+    use docx_rust::Docx;
+
+    let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="192" height="96"/>"#.to_vec();
+    let png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x02\0\0\0\x01\x08\x02\0\0\0\0\0\0\0".to_vec();
+
+    let mut docx = Docx::default();
+    assert!(docx.add_image(svg.clone(), None, None).is_err());
+
+    let run = docx.add_svg_image(svg, png, None, None).unwrap();
+    docx.document.push(Paragraph::default().push(run));
+    let video = docx.add_media("MP4", b"video".to_vec()).unwrap();
+    assert!(docx.add_media("xyz", b"unknown".to_vec()).is_err());
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    docx.write(&mut buf).unwrap();
+
+    let file = DocxFile::from_reader(std::io::Cursor::new(buf.into_inner())).unwrap();
+    let docx = file.parse().unwrap();
+    let rels = docx.document_rels.as_ref().unwrap();
+
+    let BodyContent::Paragraph(para) = &docx.document.body.content[0] else {
+        panic!("expected a paragraph")
+    };
+    let ParagraphContent::Run(run) = &para.content[0] else {
+        panic!("expected a run")
+    };
+    let RunContent::Drawing(drawing) = &run.content[0] else {
+        panic!("expected a drawing")
+    };
+    let inline = drawing.inline.as_ref().unwrap();
+    assert_eq!(1828800, inline.extent.as_ref().unwrap().cx);
+    let blip = &inline.graphic.as_ref().unwrap().data.children[0].fill.blip;
+    assert_eq!(Some("media/image1.png"), rels.get_target(&blip.embed));
+    assert_eq!(
+        Some("media/image1.svg"),
+        rels.get_target(blip.svg_embed().unwrap())
+    );
+
+    assert_eq!(Some("media/video1.mp4"), rels.get_target(&video));
+    assert!(matches!(
+        docx.media["media/video1.mp4"],
+        (MediaType::Video, _)
+    ));
+    for (ext, ty) in [
+        ("png", "image/png"),
+        ("svg", "image/svg+xml"),
+        ("mp4", "video/mp4"),
+    ] {
+        assert!(docx
+            .content_types
+            .defaults
+            .iter()
+            .any(|default| default.ext == ext && default.ty == ty));
+    }
+}
+
+#[test]
+fn deterministic_write() {
+    use docx_rust::document::{Footer, Header, Paragraph};
+    use docx_rust::Docx;
+
+    fn build() -> Vec<u8> {
+        let gif = b"GIF89a\x01\x00\x01\x00\x00\x00\x00".to_vec();
+        let mut docx = Docx::default();
+        for i in 1..=5 {
+            let mut header = Header::default();
+            header.push(Paragraph::default().push_text(format!("header {}", i)));
+            docx.headers.insert(format!("header{}.xml", i), header);
+            docx.footers
+                .insert(format!("footer{}.xml", i), Footer::default());
+            let run = docx.add_image(gif.clone(), None, None).unwrap();
+            docx.document.push(Paragraph::default().push(run));
+        }
+        let mut buf = std::io::Cursor::new(Vec::new());
+        docx.write(&mut buf).unwrap();
+        buf.into_inner()
+    }
+
+    let first = build();
+    assert_eq!(first, build());
+
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(first)).unwrap();
+    let names: Vec<_> = zip.file_names().map(str::to_string).collect();
+    let headers: Vec<_> = names.iter().filter(|n| n.contains("header")).collect();
+    assert_eq!(
+        vec![
+            "word/header1.xml",
+            "word/header2.xml",
+            "word/header3.xml",
+            "word/header4.xml",
+            "word/header5.xml"
+        ],
+        headers
+    );
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i).unwrap();
+        let time = entry.last_modified();
+        assert_eq!(
+            (1980, 1, 1, 0, 0),
+            (
+                time.year(),
+                time.month(),
+                time.day(),
+                time.hour(),
+                time.minute()
+            )
+        );
+    }
+}
+
+#[test]
+fn owned_docx() {
+    use docx_rust::Docx;
+
+    fn open() -> Docx<'static> {
+        let file = DocxFile::from_file("./tests/aaa/aa.docx").unwrap();
+        let docx = file.parse().unwrap();
+        docx.into_owned()
+    }
+
+    let owned = open();
+    let text = owned.document.body.text();
+    assert!(!text.is_empty());
+
+    // an owned document can be moved to another thread and written there
+    let written = std::thread::spawn(move || {
+        let mut docx = owned;
+        let mut buf = std::io::Cursor::new(Vec::new());
+        docx.write(&mut buf).unwrap();
+        buf.into_inner()
+    })
+    .join()
+    .unwrap();
+
+    let reread = Docx::from_reader(std::io::Cursor::new(written)).unwrap();
+    assert_eq!(text, reread.document.body.text());
+    assert_eq!(
+        Docx::from_file("./tests/aaa/aa.docx")
+            .unwrap()
+            .styles
+            .styles
+            .len(),
+        reread.styles.styles.len()
+    );
+}
+
+#[test]
+fn add_headers_and_footers() {
+    use docx_rust::document::{
+        Footer, Header, HeaderFooterReference, HeaderFooterReferenceType, Paragraph,
+    };
+    use docx_rust::Docx;
+
+    let mut docx = Docx::default();
+    docx.document.push(Paragraph::default().push_text("body"));
+    let mut ids = Vec::new();
+    for kind in [
+        HeaderFooterReferenceType::Default,
+        HeaderFooterReferenceType::First,
+        HeaderFooterReferenceType::Even,
+    ] {
+        let mut header = Header::default();
+        header.push(Paragraph::default().push_text(format!("{:?} header", kind)));
+        ids.push(docx.add_header(kind.clone(), header));
+        ids.push(docx.add_footer(kind, Footer::default()));
+    }
+    // content pushed after the headers still goes before the section properties
+    docx.document.push(Paragraph::default().push_text("after"));
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    docx.write(&mut buf).unwrap();
+    let docx = Docx::from_reader(std::io::Cursor::new(buf.into_inner())).unwrap();
+
+    let rels = docx.document_rels.as_ref().unwrap();
+    let sect_pr = match docx.document.body.content.last() {
+        Some(BodyContent::SectionProperty(sect_pr)) => sect_pr,
+        _ => panic!("missing section properties"),
+    };
+    assert_eq!(3, docx.document.body.content.len());
+    assert_eq!("body\r\nafter", docx.document.body.text());
+    assert_eq!(6, sect_pr.header_footer_references.len());
+    assert!(sect_pr.title_page.is_some());
+    assert!(docx.settings.unwrap().even_and_odd_headers.is_some());
+
+    for reference in &sect_pr.header_footer_references {
+        let (id, schema, content_type) = match reference {
+            HeaderFooterReference::Header(h) => (h.id.as_ref().unwrap(), "header", "header+xml"),
+            HeaderFooterReference::Footer(f) => (f.id.as_ref().unwrap(), "footer", "footer+xml"),
+        };
+        assert!(ids.contains(&id.to_string()));
+        let rel = rels.relationships.iter().find(|rel| rel.id == *id).unwrap();
+        assert!(rel.ty.ends_with(schema));
+        assert!(rel.target.starts_with(schema));
+        let part = format!("/word/{}", rel.target);
+        assert!(docx
+            .content_types
+            .overrides
+            .iter()
+            .any(|o| o.part == part && o.ty.ends_with(content_type)));
+    }
+    assert_eq!(3, docx.headers.len());
+    assert_eq!(3, docx.footers.len());
+    match &docx.headers["header2.xml"].content[0] {
+        BodyContent::Paragraph(para) => assert_eq!("First header", para.text()),
+        _ => panic!("missing header paragraph"),
+    }
+}
+
+#[test]
+fn reconcile_content_types() {
+    use docx_rust::document::{
+        Comments, EndNotes, FootNotes, Header, HeaderFooterReferenceType, Numbering, Paragraph,
+    };
+    use docx_rust::settings::Settings;
+    use docx_rust::Docx;
+    use std::io::Read;
+
+    let png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x02\0\0\0\x01\x08\x02\0\0\0\0\0\0\0".to_vec();
+    let mut docx = Docx {
+        numbering: Some(Numbering::default()),
+        footnotes: Some(FootNotes::default()),
+        endnotes: Some(EndNotes::default()),
+        comments: Some(Comments::default()),
+        settings: Some(Settings::default()),
+        ..Default::default()
+    };
+    docx.add_header(HeaderFooterReferenceType::Default, Header::default());
+    let run = docx.add_image(png, None, None).unwrap();
+    docx.document.push(Paragraph::default().push(run));
+    docx.content_types.add_default("gif", "image/gif");
+    docx.content_types
+        .add_override("/word/header9.xml", "application/xml");
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    docx.write(&mut buf).unwrap();
+    let written = buf.into_inner();
+    let reread = Docx::from_reader(std::io::Cursor::new(written.clone())).unwrap();
+    let content_types = &reread.content_types;
+
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(written)).unwrap();
+    let mut parts = Vec::new();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).unwrap();
+        let name = format!("/{}", entry.name());
+        if name != "/[Content_Types].xml" {
+            assert!(content_types.content_type(&name).is_some(), "{}", name);
+            parts.push(name);
+        }
+        entry.read_to_end(&mut Vec::new()).unwrap();
+    }
+    for o in &content_types.overrides {
+        assert!(parts.contains(&o.part.to_string()), "{}", o.part);
+    }
+
+    for (part, ty) in [
+        ("/word/numbering.xml", "numbering+xml"),
+        ("/word/footnotes.xml", "footnotes+xml"),
+        ("/word/endnotes.xml", "endnotes+xml"),
+        ("/word/comments.xml", "comments+xml"),
+        ("/word/settings.xml", "settings+xml"),
+        ("/word/header1.xml", "header+xml"),
+        ("/word/media/image1.png", "image/png"),
+    ] {
+        assert!(content_types.content_type(part).unwrap().ends_with(ty));
+    }
+    assert!(!content_types.defaults.iter().any(|d| d.ext == "gif"));
+    // stale overrides, like the one for the app properties `Docx::default()` starts with
+    for part in ["/docProps/app.xml", "/word/header9.xml"] {
+        assert!(!content_types.overrides.iter().any(|o| o.part == part));
+    }
+}
+
+#[test]
+fn add_external_link() {
+    use docx_rust::document::{Paragraph, Run};
+    use docx_rust::Docx;
+
+    let url = "https://example.com/?a=1&b=2";
+    let mut docx = Docx::default();
+    let first = docx.add_external_link(url, [Run::default().push_text("first")]);
+    let second = docx.add_external_link(
+        url.to_string(),
+        vec![
+            Run::default().push_text("second"),
+            Run::default()
+                .property(docx_rust::formatting::CharacterProperty::default().style_id("Strong"))
+                .push_text(" link"),
+        ],
+    );
+    assert_ne!(first.id, second.id);
+    docx.document
+        .push(Paragraph::default().push(first).push(second));
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    docx.write(&mut buf).unwrap();
+    let docx = Docx::from_reader(std::io::Cursor::new(buf.into_inner())).unwrap();
+
+    let links: Vec<_> = match &docx.document.body.content[0] {
+        BodyContent::Paragraph(para) => para
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                ParagraphContent::Link(link) => Some(link),
+                _ => None,
+            })
+            .collect(),
+        _ => panic!("missing paragraph"),
+    };
+    assert_eq!(2, links.len());
+    for link in &links {
+        assert_eq!(Some(url), docx.hyperlink_url(link));
+        let rel = docx
+            .document_rels
+            .as_ref()
+            .unwrap()
+            .relationships
+            .iter()
+            .find(|rel| Some(&rel.id) == link.id.as_ref())
+            .unwrap();
+        assert_eq!(Some(TargetMode::External), rel.target_mode);
+    }
+
+    let styles: Vec<_> = links[1]
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            docx_rust::document::HyperlinkContent::Run(run) => Some(run),
+            _ => None,
+        })
+        .map(|run| {
+            run.property
+                .as_ref()
+                .unwrap()
+                .style_id
+                .as_ref()
+                .unwrap()
+                .value
+                .to_string()
+        })
+        .collect();
+    assert_eq!(vec!["Hyperlink", "Strong"], styles);
+    assert_eq!(
+        1,
+        docx.styles
+            .styles
+            .iter()
+            .filter(|style| style.style_id == "Hyperlink")
+            .count()
+    );
+
+    // only relationships inside the package are shared by target
+    let mut rels = docx_rust::rels::Relationships::default();
+    let ids = [
+        rels.add_rel_with_target_mode("type", url, Some("External")),
+        rels.add_rel_with_target_mode("type", url, Some("External")),
+        rels.add_rel_with_target_mode("type", "media/image1.png", None),
+        rels.add_rel_with_target_mode("type", "media/image1.png", None),
+    ];
+    assert_eq!(ids, ["rId1", "rId2", "rId3", "rId3"]);
+    let ids: Vec<_> = rels.relationships.iter().map(|r| &*r.id).collect();
+    assert_eq!(ids, ["rId1", "rId2", "rId3"]);
+}
+
+#[test]
+fn insert_toc() {
+    use docx_rust::document::{Paragraph, SDT};
+    use docx_rust::formatting::{OutlineLvl, ParagraphProperty};
+    use docx_rust::styles::{Style, StyleType};
+    use docx_rust::Docx;
+
+    let heading = |style: &str, text: &str| {
+        Paragraph::default()
+            .property(ParagraphProperty::default().style_id(style.to_string()))
+            .push_text(text.to_string())
+    };
+    let mut docx = Docx::default();
+    for level in 1..=3 {
+        docx.styles.push(
+            Style::new(StyleType::Paragraph, format!("Heading{}", level))
+                .name(format!("heading {}", level)),
+        );
+    }
+    docx.document
+        .push(heading("Heading1", "Introduction"))
+        .push(Paragraph::default().push_text("Some text"))
+        .push(heading("Heading2", "Scope"))
+        .push(heading("Heading3", "Too deep"))
+        .push(
+            Paragraph::default()
+                .property(ParagraphProperty {
+                    outline_lvl: Some(OutlineLvl { value: 0 }),
+                    ..Default::default()
+                })
+                .push_text("Appendix"),
+        );
+
+    docx.insert_toc(2, Some("Contents"));
+    // a second call replaces the table and reuses the bookmarks
+    docx.insert_toc(2, Some("Contents"));
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    docx.write(&mut buf).unwrap();
+    let docx = Docx::from_reader(std::io::Cursor::new(buf.into_inner())).unwrap();
+
+    let body = &docx.document.body.content;
+    let is_toc = |content: &BodyContent| {
+        matches!(content, BodyContent::Sdt(SDT { property: Some(p), .. })
+        if p.doc_part_obj.as_ref().unwrap().doc_part_gallery.as_ref().unwrap().name.as_deref() == Some("Table of Contents"))
+    };
+    assert_eq!(1, body.iter().filter(|content| is_toc(content)).count());
+    assert!(is_toc(&body[0]));
+
+    // the bookmark of every heading
+    let mut bookmarks = HashMap::new();
+    for content in &body[1..] {
+        if let BodyContent::Paragraph(para) = content {
+            let starts: Vec<_> = para
+                .content
+                .iter()
+                .filter_map(|c| match c {
+                    ParagraphContent::BookmarkStart(start) => start.name.as_deref(),
+                    _ => None,
+                })
+                .collect();
+            assert!(starts.len() <= 1);
+            if let Some(name) = starts.first() {
+                bookmarks.insert(name.to_string(), para.text());
+            }
+        }
+    }
+    assert_eq!(3, bookmarks.len());
+
+    let BodyContent::Sdt(sdt) = &body[0] else {
+        unreachable!()
+    };
+    let paragraphs: Vec<_> = sdt
+        .content
+        .as_ref()
+        .unwrap()
+        .content
+        .iter()
+        .map(|c| match c {
+            BodyContent::Paragraph(para) => para,
+            _ => panic!("unexpected content"),
+        })
+        .collect();
+    assert_eq!("Contents", paragraphs[0].text());
+    let entries: Vec<_> = paragraphs[1..]
+        .iter()
+        .filter_map(|para| {
+            let style = &para.property.as_ref()?.style_id.as_ref()?.value;
+            let link = para.content.iter().find_map(|c| match c {
+                ParagraphContent::Link(link) => Some(link),
+                _ => None,
+            })?;
+            Some((
+                style.to_string(),
+                bookmarks[link.anchor.as_deref().unwrap()].clone(),
+            ))
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("TOC1".to_string(), "Introduction".to_string()),
+            ("TOC2".to_string(), "Scope".to_string()),
+            ("TOC1".to_string(), "Appendix".to_string()),
+        ],
+        entries
+    );
+
+    let instr: String = paragraphs
+        .iter()
+        .flat_map(|para| para.content.iter())
+        .filter_map(|c| match c {
+            ParagraphContent::Run(run) => Some(run),
+            _ => None,
+        })
+        .flat_map(|run| run.content.iter())
+        .filter_map(|c| match c {
+            RunContent::InstrText(instr) => Some(instr.text.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(" TOC \\o \"1-2\" \\h \\z \\u ", instr);
+
+    for id in ["TOCHeading", "TOC1", "TOC2"] {
+        assert!(docx.styles.styles.iter().any(|style| style.style_id == id));
+    }
+    let update = docx.settings.unwrap().update_fields.unwrap();
+    assert_eq!(Some(true), update.val);
+}
+
+#[test]
+fn refresh_toc() {
+    use docx_rust::document::{CharType, HyperlinkContent, Paragraph};
+    use docx_rust::formatting::ParagraphProperty;
+    use docx_rust::Docx;
+
+    fn toc_paragraphs<'d, 'a>(docx: &'d Docx<'a>) -> Vec<&'d Paragraph<'a>> {
+        match &docx.document.body.content[0] {
+            BodyContent::Sdt(sdt) => sdt
+                .content
+                .as_ref()
+                .unwrap()
+                .content
+                .iter()
+                .filter_map(|c| match c {
+                    BodyContent::Paragraph(para) => Some(para),
+                    _ => None,
+                })
+                .collect(),
+            _ => panic!("missing table of contents"),
+        }
+    }
+    fn entries(docx: &Docx) -> Vec<(String, String)> {
+        toc_paragraphs(docx)
+            .iter()
+            .flat_map(|para| para.content.iter())
+            .filter_map(|c| match c {
+                ParagraphContent::Link(link) => Some((
+                    link.anchor.as_deref().unwrap().to_string(),
+                    link.first_run().unwrap().text(),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+    fn bookmark_of(para: &Paragraph) -> Option<String> {
+        para.content.iter().find_map(|c| match c {
+            ParagraphContent::BookmarkStart(start) => start.name.as_deref().map(str::to_string),
+            _ => None,
+        })
+    }
+
+    let mut docx = Docx::from_file("./docx/toc/bigger/inhaltsverzeichnis-bigger.docx").unwrap();
+    let before = entries(&docx);
+    assert_eq!(10, before.len());
+
+    docx.document
+        .body
+        .replace_text_simple("Testüberschrift 1", "Einleitung");
+    docx.document.body.content.retain(|c| match c {
+        BodyContent::Paragraph(para) => bookmark_of(para).as_deref() != Some("_Toc198737729"),
+        _ => true,
+    });
+    let end = docx.document.body.content.len() - 1;
+    docx.document.body.content.insert(
+        end,
+        Paragraph::default()
+            .property(ParagraphProperty::default().style_id("berschrift2"))
+            .push_text("Neu")
+            .into(),
+    );
+
+    // a note between the entries, and the end of the field in the entry of the removed heading
+    let BodyContent::Sdt(sdt) = &mut docx.document.body.content[0] else {
+        panic!("missing table of contents")
+    };
+    let blocks = &mut sdt.content.as_mut().unwrap().content;
+    blocks.insert(3, Paragraph::default().push_text("Note").into());
+    let Some(BodyContent::Paragraph(end)) = blocks.pop() else {
+        panic!("missing end of the field")
+    };
+    let Some(BodyContent::Paragraph(last)) = blocks.last_mut() else {
+        panic!("missing last entry")
+    };
+    last.content.extend(end.content);
+
+    assert!(docx.refresh_toc());
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    docx.write(&mut buf).unwrap();
+    let docx = Docx::from_reader(std::io::Cursor::new(buf.into_inner())).unwrap();
+
+    let new_bookmark = docx
+        .document
+        .body
+        .content
+        .iter()
+        .rev()
+        .find_map(|c| match c {
+            BodyContent::Paragraph(para) if para.text() == "Neu" => bookmark_of(para),
+            _ => None,
+        })
+        .unwrap();
+    let mut expected = before.clone();
+    expected[0].1 = "Einleitung".to_string();
+    expected.pop();
+    expected.push((new_bookmark.clone(), "Neu".to_string()));
+    assert_eq!(expected, entries(&docx));
+
+    let paragraphs = toc_paragraphs(&docx);
+    // the title and the note are kept in place
+    assert_eq!("Inhaltsverzeichnis", paragraphs[0].text());
+    assert_eq!("Note", paragraphs[3].text());
+    assert_eq!(12, paragraphs.len());
+    let first = paragraphs[1];
+    assert!(matches!(&first.content[1], ParagraphContent::Run(run)
+        if matches!(&run.content[0], RunContent::InstrText(instr) if instr.text.contains("TOC"))));
+    // the formatting of the runs is kept
+    let ParagraphContent::Link(link) = first.content.last().unwrap() else {
+        panic!("missing hyperlink")
+    };
+    let run = link.first_run().unwrap();
+    assert!(run.property.as_ref().unwrap().style_id.is_some());
+
+    // the new entry is a copy of a level 2 entry
+    let last = paragraphs[11];
+    assert_eq!(
+        "Verzeichnis2",
+        last.property
+            .as_ref()
+            .unwrap()
+            .style_id
+            .as_ref()
+            .unwrap()
+            .value
+    );
+    // and ends the field
+    assert!(
+        matches!(last.content.last().unwrap(), ParagraphContent::Run(run)
+        if matches!(&run.content[0], RunContent::FieldChar(c) if matches!(c.ty, Some(CharType::End))))
+    );
+    let ParagraphContent::Link(link) = &last.content[last.content.len() - 2] else {
+        panic!("missing hyperlink")
+    };
+    let instr: Vec<_> = link
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            HyperlinkContent::Run(run) => Some(run),
+            _ => None,
+        })
+        .flat_map(|run| run.content.iter())
+        .filter_map(|c| match c {
+            RunContent::InstrText(instr) => Some(instr.text.trim().to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(vec![format!("PAGEREF {} \\h", new_bookmark)], instr);
+}
+
+#[test]
+fn fields() {
+    use docx_rust::document::{
+        CharType, FieldChar, FieldKind, Footer, HeaderFooterReferenceType, InstrText, Paragraph,
+        Run, SimpleField,
+    };
+    use docx_rust::formatting::CharacterProperty;
+    use docx_rust::Docx;
+
+    let begin = || Run::default().push(FieldChar::from(CharType::Begin));
+    let separate = || Run::default().push(FieldChar::from(CharType::Separate));
+    let end = || Run::default().push(FieldChar::from(CharType::End));
+    let instr = |text: &'static str| Run::default().push(InstrText::from(text));
+
+    let mut docx = Docx::default();
+    // an instruction split over runs and a formatted result
+    docx.document.push(
+        Paragraph::default()
+            .push(begin())
+            .push(instr(" MERGEFIELD "))
+            .push(instr(r#"Name \* Upper "#))
+            .push(separate())
+            .push(
+                Run::default()
+                    .property(CharacterProperty::default().bold(true))
+                    .push_text("«Name»"),
+            )
+            .push(end()),
+    );
+    // a result spanning paragraphs
+    docx.document
+        .push(
+            Paragraph::default()
+                .push(begin())
+                .push(instr(r#" REF intro \h "#))
+                .push(separate())
+                .push_text("Old"),
+        )
+        .push(
+            Paragraph::default()
+                .push_text(" result")
+                .push(end())
+                .push_text(" after"),
+        );
+    // a field nested in the instruction of another one and a field without result
+    docx.document.push(
+        Paragraph::default()
+            .push(begin())
+            .push(instr(" IF "))
+            .push(begin())
+            .push(instr(" MERGEFIELD Gender "))
+            .push(separate())
+            .push_text("«Gender»")
+            .push(end())
+            .push(instr(r#" = "F" "Ms." "Mr." "#))
+            .push(separate())
+            .push_text("Mr.")
+            .push(end())
+            .push(begin())
+            .push(instr(" NUMPAGES "))
+            .push(end()),
+    );
+    let mut footer = Footer::default();
+    footer.push(
+        Paragraph::default().push(
+            SimpleField::default()
+                .instr(" PAGE ")
+                .push(Run::default().push_text("1"))
+                .push(Run::default().push_text("0")),
+        ),
+    );
+    docx.add_footer(HeaderFooterReferenceType::Default, footer);
+
+    let fields = docx.fields();
+    let kinds: Vec<_> = fields.iter().map(|field| field.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        [
+            FieldKind::MergeField("Name".into()),
+            FieldKind::Ref("intro".into()),
+            FieldKind::If,
+            FieldKind::MergeField("Gender".into()),
+            FieldKind::NumPages,
+            FieldKind::Page,
+        ]
+    );
+    assert_eq!(fields[0].instruction, r#"MERGEFIELD Name \* Upper"#);
+    assert_eq!(
+        fields[0].switch("*").unwrap().value.as_deref(),
+        Some("Upper")
+    );
+    assert_eq!(fields[0].result_text(), "«Name»");
+    assert_eq!(fields[1].result_text(), "Old result");
+    assert_eq!(fields[1].result.len(), 2);
+    assert_eq!(fields[2].instruction, r#"IF "«Gender»" = "F" "Ms." "Mr.""#);
+    assert_eq!(fields[2].result_text(), "Mr.");
+    assert_eq!(fields[4].result_text(), "");
+    assert_eq!(fields[5].result_text(), "10");
+
+    let record: HashMap<&str, &str> = [("Name", "Alice"), ("Gender", "F")].into();
+    let replaced = docx.replace_field_results(|field| match &field.kind {
+        FieldKind::MergeField(name) => Some(record[name.as_str()].to_uppercase()),
+        FieldKind::Ref(_) => Some("New".into()),
+        FieldKind::If => {
+            let args = &field.arguments;
+            Some(
+                if args[0] == args[2] {
+                    &args[3]
+                } else {
+                    &args[4]
+                }
+                .clone(),
+            )
+        }
+        FieldKind::NumPages => Some("3".into()),
+        FieldKind::Page => Some("7".into()),
+        _ => None,
+    });
+    assert_eq!(replaced, 6);
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    docx.write(&mut buf).unwrap();
+    let docx = Docx::from_reader(std::io::Cursor::new(buf.into_inner())).unwrap();
+
+    let results: Vec<_> = docx.fields().iter().map(|f| f.result_text()).collect();
+    assert_eq!(results, ["ALICE", "New", "Ms.", "F", "3", "7"]);
+    // the formatting of the old result is kept
+    let fields = docx.fields();
+    let bold = fields[0].result[0].property.as_ref().unwrap().bold.as_ref();
+    assert_eq!(bold.unwrap().value, Some(true));
+    assert_eq!(fields[1].result.len(), 1);
+    assert_eq!(fields[2].instruction, r#"IF "F" = "F" "Ms." "Mr.""#);
+
+    // text after the end of the field is kept
+    let texts: Vec<_> = docx.document.body.content[1..3]
+        .iter()
+        .map(|content| match content {
+            BodyContent::Paragraph(para) => para.text(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(texts, [r#" REF intro \h New"#, " after"]);
+}
+
+#[test]
+fn mail_merge() {
+    use docx_rust::document::{
+        BookmarkEnd, BookmarkStart, CharType, FieldChar, FieldKind, Footer,
+        HeaderFooterReferenceType, InstrText, Paragraph, Run, SimpleField,
+    };
+    use docx_rust::formatting::CharacterProperty;
+    use docx_rust::settings::{MailMerge, Settings};
+    use docx_rust::Docx;
+    use std::collections::BTreeMap;
+
+    let begin = || Run::default().push(FieldChar::from(CharType::Begin));
+    let separate = || Run::default().push(FieldChar::from(CharType::Separate));
+    let end = || Run::default().push(FieldChar::from(CharType::End));
+    let instr = |text: &'static str| Run::default().push(InstrText::from(text));
+    let merge_field = |para: Paragraph<'static>, name: &'static str| {
+        para.push(begin())
+            .push(instr(name))
+            .push(separate())
+            .push(
+                Run::default()
+                    .property(CharacterProperty::default().bold(true))
+                    .push_text("«field»"),
+            )
+            .push(end())
+    };
+
+    let mut docx = Docx::default();
+    let greeting = merge_field(
+        Paragraph::default().push_text("Dear "),
+        " MERGEFIELD title ",
+    )
+    .push_text(" ")
+    .push(SimpleField::default().instr(r#" MERGEFIELD Name \* Upper "#));
+    docx.document.push(greeting);
+    // the amount is nested both as a condition and inside the quoted text
+    docx.document.push(
+        Paragraph::default()
+            .push(begin())
+            .push(instr(" IF "))
+            .push(begin())
+            .push(instr(" MERGEFIELD Amount "))
+            .push(end())
+            .push(instr(r#" > 0 "You owe "#))
+            .push(begin())
+            .push(instr(r##" MERGEFIELD Amount \# "#,##0.00" "##))
+            .push(end())
+            .push(instr(r#"." "Thank you." "#))
+            .push(end()),
+    );
+    docx.document.push(merge_field(
+        Paragraph::default().push_text("Due: "),
+        r#" MERGEFIELD Due \@ "dd.MM.yyyy" "#,
+    ));
+    docx.document.push(
+        Paragraph::default()
+            .push(BookmarkStart {
+                id: Some("0".into()),
+                name: Some("signature".into()),
+            })
+            .push_text("Regards")
+            .push(BookmarkEnd {
+                id: Some("0".into()),
+            }),
+    );
+    let mut footer = Footer::default();
+    footer.push(Paragraph::default().push(SimpleField::default().instr("PAGE")));
+    docx.add_footer(HeaderFooterReferenceType::Default, footer);
+    docx.settings = Some(Settings {
+        mail_merge: Some(MailMerge::default()),
+        ..Default::default()
+    });
+
+    let records = vec![
+        BTreeMap::from([
+            ("Title", "Ms."),
+            ("Name", "Alice"),
+            ("Amount", "1234.5"),
+            ("Due", "2024-03-05"),
+        ]),
+        BTreeMap::from([
+            ("Title", "Mr."),
+            ("Name", "Bob"),
+            ("Amount", "0"),
+            ("Due", "2024-12-24"),
+        ]),
+    ];
+    let letters = docx.mail_merge(records.clone());
+    assert_eq!(letters.len(), 2);
+
+    let texts = |docx: &Docx| -> Vec<String> {
+        docx.document
+            .body
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                BodyContent::Paragraph(para) => Some(para.text()),
+                _ => None,
+            })
+            .collect()
+    };
+    assert_eq!(
+        texts(&letters[0]),
+        [
+            "Dear Ms. ALICE",
+            "You owe 1,234.50.",
+            "Due: 05.03.2024",
+            "Regards"
+        ]
+    );
+    assert_eq!(
+        texts(&letters[1]),
+        ["Dear Mr. BOB", "Thank you.", "Due: 24.12.2024", "Regards"]
+    );
+
+    for letter in &letters {
+        // only the page number is left as a field
+        let kinds: Vec<_> = letter.fields().into_iter().map(|f| f.kind).collect();
+        assert_eq!(kinds, [FieldKind::Page]);
+        assert!(letter.settings.as_ref().unwrap().mail_merge.is_none());
+    }
+    // the formatting of the result is kept
+    let BodyContent::Paragraph(para) = &letters[0].document.body.content[0] else {
+        panic!()
+    };
+    let bold = para.content.iter().find_map(|content| match content {
+        ParagraphContent::Run(run) if run.text() == "Ms." => run.property.as_ref(),
+        _ => None,
+    });
+    assert_eq!(bold.unwrap().bold.as_ref().unwrap().value, Some(true));
+
+    // one document with a section per record
+    let mut combined = docx.mail_merge_combined(records).unwrap();
+    assert_eq!(
+        texts(&combined),
+        [
+            "Dear Ms. ALICE",
+            "You owe 1,234.50.",
+            "Due: 05.03.2024",
+            "Regards",
+            "Dear Mr. BOB",
+            "Thank you.",
+            "Due: 24.12.2024",
+            "Regards"
+        ]
+    );
+    assert_eq!(combined.document.body.sections_mut().len(), 2);
+    // bookmarks stay unique
+    let bookmarks: Vec<_> = combined
+        .document
+        .body
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            BodyContent::Paragraph(para) => para.content.iter().find_map(|c| match c {
+                ParagraphContent::BookmarkStart(start) => {
+                    Some((start.id.clone(), start.name.clone()))
+                }
+                _ => None,
+            }),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        bookmarks,
+        [
+            (Some("0".into()), Some("signature".into())),
+            (Some("1".into()), Some("signature_1".into())),
+        ]
+    );
+    assert!(docx
+        .mail_merge_combined(Vec::<Vec<(&str, &str)>>::new())
+        .is_none());
+}
+
+#[test]
+fn mail_merge_next_record() {
+    use docx_rust::document::{Paragraph, SimpleField};
+    use docx_rust::Docx;
+
+    // two labels per page
+    let mut docx = Docx::default();
+    docx.document
+        .push(Paragraph::default().push(SimpleField::default().instr("MERGEFIELD Name")))
+        .push(Paragraph::default().push(SimpleField::default().instr("NEXT")))
+        .push(Paragraph::default().push(SimpleField::default().instr("MERGEFIELD Name")))
+        .push(Paragraph::default().push(SimpleField::default().instr(r#"NEXTIF "x" = "y""#)))
+        .push(Paragraph::default().push(SimpleField::default().instr("MERGEREC")));
+
+    let records = ["Ann", "Ben", "Cid"].map(|name| vec![("name", name)]);
+    let pages: Vec<_> = docx
+        .mail_merge(records)
+        .iter()
+        .map(|docx| docx.document.body.text())
+        .collect();
+    assert_eq!(pages, ["Ann\r\n\r\nBen\r\n\r\n2", "Cid\r\n\r\n\r\n\r\n4"]);
+}
+
+#[test]
+fn update_fields() {
+    use docx_rust::app::{App, AppNoApNamespace};
+    use docx_rust::core::{Core, CoreNamespace};
+    use docx_rust::document::{
+        BookmarkEnd, BookmarkStart, Break, BreakType, CharType, DateTime, FieldChar, FieldContext,
+        Footer, HeaderFooterReferenceType, InstrText, Paragraph, Run, SimpleField,
+    };
+    use docx_rust::formatting::CharacterProperty;
+    use docx_rust::Docx;
+
+    let simple = |instr: &'static str, result: &'static str| {
+        SimpleField::default()
+            .instr(instr)
+            .push(Run::default().push_text(result))
+    };
+
+    let mut docx = Docx {
+        core: Some(Core::CoreNamespace(CoreNamespace {
+            title: Some("Quarterly report".into()),
+            creator: Some("Alice".into()),
+            ..Default::default()
+        })),
+        app: Some(App::AppNoApNamespace(AppNoApNamespace {
+            company: Some("ACME".into()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+
+    docx.document
+        .push(
+            Paragraph::default()
+                .push(BookmarkStart::default().id("0").name("_Ref1"))
+                .push_text("Results")
+                .push(BookmarkEnd::default().id("0")),
+        )
+        // a complex field whose formatted result is stale
+        .push(
+            Paragraph::default()
+                .push(Run::default().push(FieldChar::from(CharType::Begin)))
+                .push(Run::default().push(InstrText::from(r#" DATE \@ "dd.MM.yyyy" "#)))
+                .push(Run::default().push(FieldChar::from(CharType::Separate)))
+                .push(
+                    Run::default()
+                        .property(CharacterProperty::default().bold(true))
+                        .push_text("01.01.2000"),
+                )
+                .push(Run::default().push(FieldChar::from(CharType::End))),
+        )
+        .push(
+            Paragraph::default()
+                .push_text("Figure ")
+                .push(simple("SEQ Figure \\* ARABIC", "9"))
+                .push_text(": sales"),
+        )
+        .push(
+            Paragraph::default()
+                .push_text("Figure ")
+                .push(simple("SEQ Figure \\* ARABIC", "9"))
+                .push_text(": costs"),
+        )
+        .push(
+            Paragraph::default()
+                .push(Run::default().push(Break::from(BreakType::Page)))
+                .push(BookmarkStart::default().id("1").name("Summary"))
+                .push_text("All good")
+                .push(BookmarkEnd::default().id("1")),
+        )
+        .push(
+            Paragraph::default()
+                .push_text("See ")
+                .push(simple("REF _Ref1 \\h", "Error!"))
+                .push_text(" on page ")
+                .push(simple("PAGEREF Summary \\h", "?"))
+                .push_text(" of ")
+                .push(simple("NUMPAGES", "?"))
+                .push_text(", ")
+                .push(simple("REF Missing", "old")),
+        )
+        .push(
+            Paragraph::default()
+                .push(simple("TITLE \\* Upper", ""))
+                .push_text(" by ")
+                .push(simple("AUTHOR", ""))
+                .push_text(", ")
+                .push(simple("DOCPROPERTY Company", ""))
+                .push_text(", ")
+                .push(simple("DOCPROPERTY Manager", "nobody")),
+        );
+
+    let mut footer = Footer::default();
+    footer.push(
+        Paragraph::default()
+            .push(simple("PAGE", "1"))
+            .push_text(" ")
+            .push(simple("FILENAME", "old.docx")),
+    );
+    docx.add_footer(HeaderFooterReferenceType::Default, footer);
+
+    let context = FieldContext::default().now(DateTime::parse("2024-03-05").unwrap());
+    assert_eq!(docx.update_fields(&context), 9);
+
+    let texts: Vec<String> = docx
+        .document
+        .body
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            BodyContent::Paragraph(para) => Some(para.text()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        [
+            "Results",
+            r#" DATE \@ "dd.MM.yyyy" 05.03.2024"#,
+            "Figure 1: sales",
+            "Figure 2: costs",
+            "All good",
+            "See Results on page 2 of 2, old",
+            "QUARTERLY REPORT by Alice, ACME, nobody",
+        ]
+    );
+
+    // the formatting of the result is kept
+    let BodyContent::Paragraph(para) = &docx.document.body.content[1] else {
+        panic!()
+    };
+    let property = para.content.iter().find_map(|content| match content {
+        ParagraphContent::Run(run) if run.text() == "05.03.2024" => run.property.as_ref(),
+        _ => None,
+    });
+    assert_eq!(property.unwrap().bold.as_ref().unwrap().value, Some(true));
+
+    // a page count given by the context takes precedence over the estimate
+    let context = context.pages(7);
+    docx.update_fields(&context);
+    let BodyContent::Paragraph(para) = &docx.document.body.content[5] else {
+        panic!()
+    };
+    assert_eq!(para.text(), "See Results on page 2 of 7, old");
+
+    let footer = docx.footers.values().next().unwrap();
+    let BodyContent::Paragraph(para) = &footer.content[0] else {
+        panic!()
+    };
+    assert_eq!(para.text(), "1 old.docx");
+}
+
+#[test]
+fn custom_properties() {
+    use docx_rust::custom::CustomProperties;
+    use docx_rust::document::{DateTime, FieldContext, Paragraph, SimpleField};
+    use docx_rust::Docx;
+    use std::io::Cursor;
+
+    let mut custom = CustomProperties::default();
+    custom.set("DocumentId", "DMS-4711");
+    custom.set("Classification", "Internal");
+    custom.set("Approved", true);
+    custom.set("Reviewed", DateTime::parse("2024-03-05T09:00").unwrap());
+    let mut docx = Docx {
+        custom_properties: Some(custom),
+        ..Default::default()
+    };
+    docx.document.push(
+        Paragraph::default()
+            .push(SimpleField::default().instr(r#"DOCPROPERTY DocumentId \* Lower"#))
+            .push_text(" ")
+            .push(SimpleField::default().instr(r#"DOCPROPERTY "Reviewed" \@ "d.M.yyyy""#))
+            .push_text(" ")
+            .push(SimpleField::default().instr("DOCPROPERTY Approved")),
+    );
+    docx.update_fields(&FieldContext::default());
+    assert_eq!(docx.document.body.text(), "dms-4711 5.3.2024 Y");
+
+    let mut buffer = Vec::new();
+    docx.write(Cursor::new(&mut buffer)).unwrap();
+    let book = DocxFile::from_reader(Cursor::new(&buffer)).unwrap();
+    let mut docx = book.parse().unwrap().into_owned();
+
+    // the part is typed, declared and related, but not kept as raw custom XML
+    assert!(docx.custom_xml.is_empty());
+    assert!(docx.content_types.overrides.iter().any(|o| {
+        o.part == "/docProps/custom.xml"
+            && o.ty == "application/vnd.openxmlformats-officedocument.custom-properties+xml"
+    }));
+    assert!(docx
+        .rels
+        .relationships
+        .iter()
+        .any(|rel| rel.target == "docProps/custom.xml" && rel.ty.ends_with("/custom-properties")));
+
+    let custom = docx.custom_properties.as_mut().unwrap();
+    assert_eq!(custom.get("DocumentId").unwrap().as_str(), Some("DMS-4711"));
+    assert_eq!(custom.get("Approved").unwrap().as_bool(), Some(true));
+    assert_eq!(
+        custom.get("Reviewed").unwrap().as_date_time(),
+        DateTime::parse("2024-03-05T09:00")
+    );
+
+    // updating a value keeps the id of the property
+    custom.set("Classification", "Confidential");
+    let pids: Vec<_> = custom.properties.iter().map(|p| p.pid).collect();
+    assert_eq!(pids, [2, 3, 4, 5]);
+
+    let mut buffer = Vec::new();
+    docx.write(Cursor::new(&mut buffer)).unwrap();
+    let book = DocxFile::from_reader(Cursor::new(&buffer)).unwrap();
+    let docx = book.parse().unwrap();
+    let custom = docx.custom_properties.as_ref().unwrap();
+    assert_eq!(
+        custom.get("Classification").unwrap().as_str(),
+        Some("Confidential")
+    );
+    assert_eq!(custom.properties.len(), 4);
+}
+
+#[test]
+fn effective_run_properties() {
+    use docx_rust::document::{BodyContent, Numbering, Paragraph, Table, TableCellContent};
+    use docx_rust::styles::Styles;
+    use docx_rust::Docx;
+    use hard_xml::XmlRead;
+
+    let styles = Styles::from_str(
+        r#"<w:styles>
+            <w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:asciiTheme="minorHAnsi" w:eastAsia="SimSun"/><w:sz w:val="22"/></w:rPr></w:rPrDefault></w:docDefaults>
+            <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:rPr><w:color w:val="333333"/></w:rPr></w:style>
+            <w:style w:type="paragraph" w:styleId="ListParagraph"><w:basedOn w:val="Normal"/><w:pPr><w:numPr><w:numId w:val="1"/></w:numPr></w:pPr><w:rPr><w:i/></w:rPr></w:style>
+            <w:style w:type="character" w:styleId="Emphasis"><w:rPr><w:i/><w:rFonts w:ascii="Georgia"/></w:rPr></w:style>
+            <w:style w:type="table" w:default="1" w:styleId="Grid">
+                <w:rPr><w:sz w:val="20"/></w:rPr>
+                <w:tblStylePr w:type="firstRow"><w:rPr><w:b/><w:color w:val="FFFFFF"/></w:rPr></w:tblStylePr>
+                <w:tblStylePr w:type="band1Horz"><w:rPr><w:color w:val="0000FF"/></w:rPr></w:tblStylePr>
+            </w:style>
+        </w:styles>"#,
+    )
+    .unwrap();
+    let numbering = Numbering::from_str(
+        r#"<w:numbering>
+            <w:abstractNum w:abstractNumId="0"><w:multiLevelType w:val="hybridMultilevel"/><w:lvl w:ilvl="0"><w:rPr><w:sz w:val="18"/></w:rPr></w:lvl></w:abstractNum>
+            <w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>
+        </w:numbering>"#,
+    )
+    .unwrap();
+    let table = Table::from_str(
+        r#"<w:tbl><w:tblPr/><w:tblGrid/>
+            <w:tr><w:tc><w:p><w:r><w:t>Header</w:t></w:r></w:p></w:tc></w:tr>
+            <w:tr><w:tc><w:p><w:r><w:t>Body</w:t></w:r></w:p></w:tc></w:tr>
+            <w:tr><w:tc><w:p><w:pPr><w:cnfStyle w:val="100000000000"/></w:pPr><w:r><w:t>Marked</w:t></w:r></w:p></w:tc></w:tr>
+        </w:tbl>"#,
+    )
+    .unwrap();
+    let list = Paragraph::from_str(
+        r#"<w:p><w:pPr><w:pStyle w:val="ListParagraph"/></w:pPr><w:r><w:t>Item</w:t></w:r><w:r><w:rPr><w:rStyle w:val="Emphasis"/><w:sz w:val="28"/></w:rPr><w:t>stressed</w:t></w:r></w:p>"#,
+    )
+    .unwrap();
+
+    let mut docx = Docx {
+        styles,
+        numbering: Some(numbering),
+        ..Default::default()
+    };
+    docx.document.push(list).push(table);
+
+    let paragraphs: Vec<&Paragraph> = docx
+        .document
+        .body
+        .content
+        .iter()
+        .flat_map(|block| match block {
+            BodyContent::Paragraph(para) => vec![para],
+            BodyContent::Table(table) => table
+                .rows
+                .iter()
+                .flat_map(|row| row.iter_cells())
+                .flat_map(|cell| cell.content.iter())
+                .filter_map(|content| match content {
+                    TableCellContent::Paragraph(para) => Some(para),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        })
+        .collect();
+    let resolve = |para: &Paragraph<'static>, index: usize| {
+        let run = para
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                ParagraphContent::Run(run) => Some(run),
+                _ => None,
+            })
+            .nth(index)
+            .unwrap();
+        docx.effective_run_properties(para, run)
+    };
+    let is_on = |value: Option<Option<bool>>| value.flatten() == Some(true);
+
+    // numbering level and paragraph style chain
+    let item = resolve(paragraphs[0], 0);
+    assert_eq!(item.size.as_ref().unwrap().value, 18);
+    assert_eq!(item.color.as_ref().unwrap().value, "333333");
+    assert!(is_on(item.italics.map(|i| i.value)));
+    let fonts = item.fonts.unwrap();
+    assert!(fonts.ascii_theme.is_some());
+    assert_eq!(fonts.east_asia.as_deref(), Some("SimSun"));
+
+    // the character style toggles italics off again, direct formatting wins
+    let stressed = resolve(paragraphs[0], 1);
+    assert!(!is_on(stressed.italics.map(|i| i.value)));
+    assert_eq!(stressed.size.unwrap().value, 28);
+    let fonts = stressed.fonts.unwrap();
+    assert_eq!(fonts.ascii.as_deref(), Some("Georgia"));
+    assert!(fonts.ascii_theme.is_none());
+
+    // the default table style, with the first row and banded rows of the default look
+    let header = resolve(paragraphs[1], 0);
+    assert!(is_on(header.bold.map(|b| b.value)));
+    assert_eq!(header.size.unwrap().value, 20);
+    assert_eq!(header.color.unwrap().value, "333333");
+    let body = resolve(paragraphs[2], 0);
+    assert!(!is_on(body.bold.map(|b| b.value)));
+
+    // a cnfStyle written by Word decides on its own
+    let marked = resolve(paragraphs[3], 0);
+    assert!(is_on(marked.bold.map(|b| b.value)));
+}
+
+#[test]
+fn theme_resolver() {
+    use docx_rust::formatting::{Color, Fonts, ThemeColor, ThemeFont};
+    use docx_rust::settings::ColorSchemeIndex;
+    use std::io::Cursor;
+
+    let book = DocxFile::from_file("./tests/aaa/aa.docx").unwrap();
+    let mut docx = book.parse().unwrap();
+
+    let resolver = docx.theme_resolver().unwrap();
+    let accent = Color::from("4472C4").theme_color(ThemeColor::Accent1);
+    assert_eq!(resolver.color(&accent).as_deref(), Some("4472C4"));
+    let lighter = Color::from("8EAADB")
+        .theme_color(ThemeColor::Accent1)
+        .theme_tint("99");
+    assert_eq!(resolver.color(&lighter).as_deref(), Some("8FAADC"));
+    assert_eq!(resolver.color(&Color::from("auto")), None);
+    assert_eq!(
+        resolver.color(&Color::from("FF0000")).as_deref(),
+        Some("FF0000")
+    );
+
+    let fonts = resolver.fonts(
+        &Fonts::default()
+            .ascii_theme(ThemeFont::MinorAscii)
+            .h_ansi_theme(ThemeFont::MajorHansi)
+            .east_asia("SimSun"),
+    );
+    assert_eq!(fonts.ascii.as_deref(), Some("等线"));
+    assert_eq!(fonts.h_ansi.as_deref(), Some("等线 Light"));
+    assert_eq!(fonts.east_asia.as_deref(), Some("SimSun"));
+    assert!(fonts.ascii_theme.is_none());
+
+    // the mapping is kept and swaps text and background colors
+    let text = Color::from("000000").theme_color(ThemeColor::Text1);
+    let dark = docx.theme_resolver().unwrap().color(&text);
+    let mapping = docx
+        .settings
+        .as_mut()
+        .unwrap()
+        .clr_scheme_mapping
+        .as_mut()
+        .unwrap();
+    assert!(matches!(mapping.text1, Some(ColorSchemeIndex::Dark1)));
+    mapping.text1 = Some(ColorSchemeIndex::Light1);
+
+    let mut buffer = Vec::new();
+    docx.write(Cursor::new(&mut buffer)).unwrap();
+    let book = DocxFile::from_reader(Cursor::new(&buffer)).unwrap();
+    let docx = book.parse().unwrap();
+    let light = docx.theme_resolver().unwrap().color(&text);
+    assert_ne!(dark, light);
+    assert_eq!(
+        light,
+        docx.theme_resolver()
+            .unwrap()
+            .color(&Color::from("FFFFFF").theme_color(ThemeColor::Light1))
+    );
+}
+
+#[test]
+fn builtin_styles() {
+    use docx_rust::document::Paragraph;
+    use docx_rust::formatting::ParagraphProperty;
+    use docx_rust::styles::Styles;
+    use docx_rust::Docx;
+    use std::io::Cursor;
+
+    let mut docx = Docx::with_default_styles();
+    docx.document
+        .push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().style_id("Heading1"))
+                .push_text("Introduction"),
+        )
+        .push(Paragraph::default().push_text("Body"));
+
+    let mut buffer = Vec::new();
+    docx.write(Cursor::new(&mut buffer)).unwrap();
+    let book = DocxFile::from_reader(Cursor::new(&buffer)).unwrap();
+    let docx = book.parse().unwrap();
+
+    assert_eq!(docx.styles.styles.len(), Styles::builtin().styles.len());
+    let latent = docx.styles.latent_styles.as_ref().unwrap();
+    assert!(latent
+        .styles
+        .iter()
+        .any(|style| style.name.as_deref() == Some("heading 1") && style.q_format == Some(true)));
+    let heading = docx.styles.get("Heading1").unwrap();
+    assert_eq!(heading.priority.as_ref().unwrap().value, Some(9));
+    assert!(heading.q_format.is_some());
+    assert_eq!(heading.next.as_ref().unwrap().value, "Normal");
+    let grid = docx.styles.get("TableGrid").unwrap();
+    assert!(grid.table.as_ref().unwrap().borders.is_some());
+
+    let paragraphs: Vec<&Paragraph> = docx
+        .document
+        .body
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            BodyContent::Paragraph(para) => Some(para),
+            _ => None,
+        })
+        .collect();
+    let resolve = |para: &Paragraph| {
+        let ParagraphContent::Run(run) = &para.content[0] else {
+            unreachable!()
+        };
+        docx.effective_run_properties(para, run).size.unwrap().value
+    };
+    assert_eq!(resolve(paragraphs[0]), 32);
+    assert_eq!(resolve(paragraphs[1]), 22);
+
+    // only the styles asked for, with the ones they depend on
+    let mut docx = Docx::default();
+    assert!(docx.styles.add_builtin("TableGrid"));
+    assert!(docx.styles.add_builtin("TableGrid"));
+    let ids: Vec<_> = docx.styles.styles.iter().map(|s| &*s.style_id).collect();
+    assert_eq!(ids, ["TableGrid", "TableNormal"]);
+}
+
+#[test]
+fn remove_unused_and_remap_styles() {
+    use docx_rust::document::{Header, HeaderFooterReferenceType, Paragraph, Run};
+    use docx_rust::formatting::{CharacterProperty, ParagraphProperty};
+    use docx_rust::Docx;
+
+    let mut docx = Docx::with_default_styles();
+    docx.document.push(
+        Paragraph::default()
+            .property(ParagraphProperty::default().style_id("Heading2"))
+            .push_text("Body"),
+    );
+    let mut header = Header::default();
+    header.push(
+        Paragraph::default()
+            .property(ParagraphProperty::default().style_id("Caption"))
+            .push(
+                Run::default()
+                    .property(CharacterProperty::default().style_id("FootnoteReference"))
+                    .push_text("1"),
+            ),
+    );
+    docx.add_header(HeaderFooterReferenceType::Default, header);
+
+    assert_eq!(docx.remap_style("Heading2", "Heading3"), 2);
+    assert!(docx.styles.get("Heading2").is_none());
+    // Heading2Char still links to the heading it belonged to
+    let char_link = docx.styles.get("Heading2Char").unwrap().link.as_ref();
+    assert_eq!(char_link.unwrap().value, "Heading3");
+
+    assert_eq!(docx.remap_style("Caption", "Label"), 1);
+    assert_eq!(
+        docx.styles
+            .get("Label")
+            .unwrap()
+            .name
+            .as_ref()
+            .unwrap()
+            .value,
+        "caption"
+    );
+
+    let mut styles = std::mem::take(&mut docx.styles);
+    styles.remove_unused(&docx);
+    docx.styles = styles;
+
+    let mut ids: Vec<_> = docx.styles.styles.iter().map(|s| &*s.style_id).collect();
+    ids.sort_unstable();
+    assert_eq!(
+        ids,
+        [
+            "DefaultParagraphFont",
+            "FootnoteReference",
+            "Heading3",
+            "Heading3Char",
+            "Label",
+            "NoList",
+            "Normal",
+            "TableNormal",
+        ]
+    );
+}
+
+#[test]
+fn append_document() {
+    use docx_rust::document::{
+        AbstractNum, AbstractNumId, BookmarkEnd, BookmarkStart, FootNote, FootNotes,
+        FootnoteReference, Header, HeaderFooterReference, HeaderFooterReferenceType, Hyperlink,
+        Num, Numbering, Paragraph, Run,
+    };
+    use docx_rust::formatting::{CharacterProperty, ParagraphProperty};
+    use docx_rust::rels::Relationships;
+    use docx_rust::{Docx, DocxFile};
+    use hard_xml::XmlRead;
+    use std::io::Cursor;
+
+    fn part(label: &str, gif: &[u8], heading_size: isize) -> Docx<'static> {
+        let mut docx = Docx::with_default_styles();
+        let mut styles = docx.styles.styles.iter_mut();
+        let heading = styles.find(|s| s.style_id == "Heading1").unwrap();
+        heading.character = Some(CharacterProperty::default().size(heading_size));
+
+        let mut header = Header::default();
+        header.push(Paragraph::default().push_text(label.to_string()));
+        docx.add_header(HeaderFooterReferenceType::Default, header);
+
+        docx.numbering = Some(Numbering {
+            abstract_numberings: vec![AbstractNum {
+                abstract_num_id: Some(0),
+                ..Default::default()
+            }],
+            numberings: vec![Num {
+                num_id: Some(1),
+                abstract_num_id: Some(AbstractNumId { value: Some(0) }),
+                ..Default::default()
+            }],
+        });
+        docx.footnotes = Some(FootNotes {
+            content: vec![FootNote {
+                id: Some(1),
+                content: vec![Paragraph::default().push_text("note").into()],
+                ..Default::default()
+            }],
+        });
+
+        let image = docx.add_image(gif.to_vec(), None, None).unwrap();
+        docx.document.push(
+            Paragraph::default()
+                .property(
+                    ParagraphProperty::default()
+                        .style_id("Heading1")
+                        .numbering((1isize, 0isize)),
+                )
+                .push(BookmarkStart {
+                    id: Some("0".into()),
+                    name: Some("intro".into()),
+                })
+                .push_text(label.to_string())
+                .push(BookmarkEnd {
+                    id: Some("0".into()),
+                })
+                .push(Run::default().push(FootnoteReference {
+                    id: Some("1".into()),
+                    ..Default::default()
+                }))
+                .push(image)
+                .push(Hyperlink {
+                    anchor: Some("intro".into()),
+                    ..Default::default()
+                }),
+        );
+        docx
+    }
+
+    let mut docx = part("first", b"GIF89a\x01\x00\x01\x00\x00\x00\x00", 32);
+    docx.append_document(part("second", b"GIF89a\x02\x00\x02\x00\x00\x00\x00", 40));
+
+    let mut buffer = Vec::new();
+    docx.write(Cursor::new(&mut buffer)).unwrap();
+    let book = DocxFile::from_reader(Cursor::new(&buffer)).unwrap();
+    let docx = book.parse().unwrap();
+    let rels = docx.document_rels.as_ref().unwrap();
+
+    let paragraphs: Vec<&Paragraph> = docx
+        .document
+        .body
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            BodyContent::Paragraph(para) => Some(para),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(paragraphs.len(), 2);
+    let property = |i: usize| paragraphs[i].property.as_ref().unwrap();
+
+    // each section keeps its own header
+    let first_section = property(0).section_property.as_ref().unwrap();
+    let last_section = docx
+        .document
+        .body
+        .content
+        .iter()
+        .find_map(|block| match block {
+            BodyContent::SectionProperty(section) => Some(section),
+            _ => None,
+        });
+    let header_text = |section: &docx_rust::formatting::SectionProperty| {
+        let HeaderFooterReference::Header(header) = &section.header_footer_references[0] else {
+            unreachable!()
+        };
+        let target = rels.get_target(header.id.as_ref().unwrap()).unwrap();
+        docx.headers[target].content[0].clone()
+    };
+    let text = |block: BodyContent| match block {
+        BodyContent::Paragraph(para) => para.text(),
+        _ => unreachable!(),
+    };
+    assert_eq!(text(header_text(first_section)), "first");
+    assert_eq!(text(header_text(last_section.unwrap())), "second");
+
+    // the heading of the second document is defined differently
+    assert_eq!(property(0).style_id.as_ref().unwrap().value, "Heading1");
+    assert_eq!(property(1).style_id.as_ref().unwrap().value, "Heading11");
+    let renamed = docx.styles.get("Heading11").unwrap();
+    assert_eq!(renamed.name.as_ref().unwrap().value, "heading 1 1");
+    assert_eq!(
+        renamed
+            .character
+            .as_ref()
+            .unwrap()
+            .size
+            .as_ref()
+            .unwrap()
+            .value,
+        40
+    );
+
+    let numbering = docx.numbering.as_ref().unwrap();
+    let num_id = property(1).numbering.as_ref().unwrap().id.as_ref().unwrap();
+    assert_eq!(num_id.value, 2);
+    let num = numbering.numberings.iter().find(|n| n.num_id == Some(2));
+    assert_eq!(
+        num.unwrap().abstract_num_id.as_ref().unwrap().value,
+        Some(1)
+    );
+
+    let notes = &docx.footnotes.as_ref().unwrap().content;
+    assert_eq!(
+        notes.iter().map(|n| n.id).collect::<Vec<_>>(),
+        [Some(1), Some(2)]
+    );
+
+    let content = &paragraphs[1].content;
+    let ParagraphContent::BookmarkStart(start) = &content[0] else {
+        unreachable!()
+    };
+    assert_eq!(start.id.as_deref(), Some("1"));
+    assert_eq!(start.name.as_deref(), Some("intro_1"));
+    let ParagraphContent::Run(note) = &content[3] else {
+        unreachable!()
+    };
+    let RunContent::FootnoteReference(note) = &note.content[0] else {
+        unreachable!()
+    };
+    assert_eq!(note.id.as_deref(), Some("2"));
+    let ParagraphContent::Run(image) = &content[4] else {
+        unreachable!()
+    };
+    let RunContent::Drawing(drawing) = &image.content[0] else {
+        unreachable!()
+    };
+    let inline = drawing.inline.as_ref().unwrap();
+    assert_eq!(inline.doc_property.id, Some(2));
+    let blip = &inline.graphic.as_ref().unwrap().data.children[0].fill.blip;
+    let target = rels.get_target(&blip.embed).unwrap();
+    assert_eq!(&docx.media[target].1[6..7], b"\x02");
+    let ParagraphContent::Link(link) = &content[5] else {
+        unreachable!()
+    };
+    assert_eq!(link.anchor.as_deref(), Some("intro_1"));
+    // the relationships of notes are merged too
+    let mut docx = DocxFile::from_file("tests/pandoc/notes.docx")
+        .unwrap()
+        .parse()
+        .unwrap()
+        .into_owned();
+    let other = DocxFile::from_file("tests/pandoc/link_in_notes.docx")
+        .unwrap()
+        .parse()
+        .unwrap()
+        .into_owned();
+    docx.append_document(other);
+    let mut bytes = Vec::new();
+    docx.write(Cursor::new(&mut bytes)).unwrap();
+    let file = DocxFile::from_reader(Cursor::new(bytes)).unwrap();
+    let docx = file.parse().unwrap();
+    let rels = docx.opaque_parts["word/_rels/footnotes.xml.rels"].clone();
+    let rels = Relationships::from_str(std::str::from_utf8(&rels).unwrap()).unwrap();
+    let link = docx
+        .footnotes
+        .as_ref()
+        .unwrap()
+        .content
+        .iter()
+        .flat_map(|note| note.content.iter())
+        .find_map(|content| match content {
+            BodyContent::Paragraph(para) => para.content.iter().find_map(|c| match c {
+                ParagraphContent::Link(link) => link.id.clone(),
+                _ => None,
+            }),
+            _ => None,
+        });
+    assert_eq!(
+        rels.get_target(&link.unwrap()),
+        Some("http://wikipedia.org/")
+    );
+}